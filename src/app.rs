@@ -3,21 +3,34 @@
     // Allow truncation when casting from usize to i32 since board dimensions are always small enough to fit in i32
     clippy::cast_possible_truncation,
     // Allow potential wrapping when casting between types as board coordinates are within reasonable ranges
-    clippy::cast_possible_wrap
+    clippy::cast_possible_wrap,
+    // Allow undocumented errors since loading a replay only fails with file and JSON errors
+    clippy::missing_errors_doc
 )]
 
 use bevy_ecs::prelude::*;
+use log::{error, info};
 use std::error;
-use std::time::{Duration, Instant};
+use std::io;
+use std::path::Path;
 
 use crate::Time;
-use crate::components::{Board, CoyoteTime, GameState, Input, ScreenShake, TetrominoType};
-use crate::config::Config;
-use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
+use crate::components::{
+    AutoShift, Board, GameState, Handling, InitialInputs, Input, LockDelay, Phase, ScreenShake,
+};
+use crate::config::{Config, Ruleset};
+use crate::finesse::Finesse;
+use crate::highscores::{HighScores, PendingHighScore};
 use crate::menu::MenuRenderer;
-use crate::menu_types::Menu;
+use crate::menu_types::{Menu, MenuOption, MenuState, PauseOption};
+use crate::mode::{GameMode, GameModeKind};
+use crate::pieces::PieceSet;
+use crate::randomizer::seed_game;
+use crate::replay::{Replay, ReplayPlayer, ReplayRecorder};
 use crate::sound::AudioState;
-use crate::systems::spawn_tetromino;
+use crate::stats::GameStats;
+use crate::suspend::SuspendedGame;
+use crate::systems::{self, spawn_tetromino};
 
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
@@ -29,6 +42,53 @@ pub struct App {
     pub menu: Menu,
     pub menu_renderer: MenuRenderer,
     pub config: Config,
+    pub recorder: Option<ReplayRecorder>, // Records the game being played, saved when it ends
+    pub replay_player: Option<ReplayPlayer>, // Set while a replay is being watched
+    pub high_scores: HighScores,
+    pub pending_high_score: Option<PendingHighScore>, // A finished game waiting on initials
+    pub high_score_place: Option<usize>, // Where the last game entered landed on its table
+    pub show_summary: bool,              // The stats summary is shown over a finished game's board
+}
+
+/// Sets up a fresh game of the mode under the given rules, dealt from the seed
+pub fn start_game(world: &mut World, mode: GameModeKind, ruleset: &Ruleset, seed: u64) {
+    // Nothing from the previous game carries over, apart from the grid setting
+    world.clear_entities();
+    let mut game_state = world.remove_resource::<GameState>().unwrap_or_default();
+    let show_grid = game_state.show_grid;
+    game_state.reset();
+    game_state.show_grid = show_grid;
+    game_state.scoring_rule = ruleset.scoring_rule;
+    game_state.partial_lock_out = ruleset.partial_lock_out;
+    game_state.piece_set = ruleset.piece_set;
+    world.insert_resource(game_state);
+    world.insert_resource(GameMode::new(mode));
+
+    let piece_set = ruleset.piece_set.load().unwrap_or_else(|err| {
+        error!("Failed to load the custom piece set, playing with the classic one: {err}");
+        PieceSet::classic()
+    });
+    // A custom set is only read here, so its pieces may need a wider board than the ruleset's
+    let board_width = ruleset.board_width.max(piece_set.min_board_width());
+    let mut board = Board::with_piece_set(board_width, ruleset.board_height, piece_set.clone());
+    board.clear();
+    world.insert_resource(board);
+
+    world.insert_resource(Input::default());
+    world.insert_resource(ScreenShake::default());
+    world.insert_resource(LockDelay {
+        duration: ruleset.timings.lock_delay(),
+        ..LockDelay::default()
+    });
+    world.insert_resource(ruleset.timings);
+    world.insert_resource(Phase::default());
+    world.insert_resource(InitialInputs::default());
+    world.insert_resource(AutoShift::default());
+    world.insert_resource(GameStats::default());
+    world.insert_resource(Finesse::new(ruleset.finesse_training));
+    seed_game(world, ruleset.randomizer, &piece_set, seed);
+
+    spawn_tetromino(world);
 }
 
 impl App {
@@ -49,12 +109,26 @@ impl App {
         // Create GameState with config values
         let mut game_state = GameState::default();
         game_state.show_grid = config.show_grid;
+        game_state.scoring_rule = config.scoring_rule_for(config.game_mode);
+        game_state.partial_lock_out = config.partial_lock_out;
         world.insert_resource(game_state);
+        world.insert_resource(GameMode::new(config.game_mode));
 
         world.insert_resource(Input::default());
         world.insert_resource(ScreenShake::default());
-        world.insert_resource(Board::new(BOARD_WIDTH, BOARD_HEIGHT));
-        world.insert_resource(CoyoteTime::default());
+        let (board_width, board_height) = config.board_size();
+        world.insert_resource(Board::new(board_width, board_height));
+        world.insert_resource(LockDelay::default());
+        seed_game(
+            &mut world,
+            config.randomizer,
+            &PieceSet::classic(),
+            fastrand::u64(..),
+        );
+        world.insert_resource(AutoShift::default());
+        world.insert_resource(GameStats::default());
+        world.insert_resource(Finesse::new(config.finesse_training));
+        world.insert_resource(config.handling());
 
         // Create the app instance
         let mut app = Self {
@@ -65,15 +139,43 @@ impl App {
             menu: Menu::new(),
             menu_renderer: MenuRenderer::new(),
             config,
+            recorder: None,
+            replay_player: None,
+            high_scores: HighScores::load(),
+            pending_high_score: None,
+            high_score_place: None,
+            show_summary: false,
         };
 
         // Spawn initial tetromino
         spawn_tetromino(&mut app.world);
 
+        // Offer to pick up a game suspended last time
+        app.menu.can_continue = SuspendedGame::exists();
+        if app.menu.can_continue {
+            app.menu.selected_option = MenuOption::Continue;
+        }
+
         app
     }
 
-    // Update config from current settings and save it to disk
+    // Push the configured DAS/ARR and soft drop timings to the game systems
+    pub fn apply_handling(&mut self) {
+        self.world.insert_resource(self.config.handling());
+    }
+
+    // Whether the audio hotkeys changed the sound settings since the config was last saved
+    #[must_use]
+    pub fn audio_settings_changed(&self) -> bool {
+        self.world
+            .get_resource::<AudioState>()
+            .is_some_and(|audio_state| {
+                audio_state.is_music_enabled() != self.config.music_enabled
+                    || audio_state.is_sound_enabled() != self.config.sound_enabled
+                    || (audio_state.get_volume() - self.config.volume).abs() > f32::EPSILON
+            })
+    }
+
     pub fn save_config(&mut self) {
         // Update config from current game state
         if let Some(audio_state) = self.world.get_resource::<AudioState>() {
@@ -115,26 +217,156 @@ impl App {
             }
         }
 
-        // Get blocks from active tetrominos
-        let tetromino_blocks: Vec<_> = self
+        // Get blocks from active tetrominos, shaped by the board's piece set
+        let pieces: Vec<_> = self
             .world
             .query::<(&crate::components::Tetromino, &crate::components::Position)>()
             .iter(&self.world)
-            .flat_map(|(tetromino, pos)| {
-                tetromino.get_blocks().into_iter().map(move |(dx, dy)| {
+            .map(|(tetromino, pos)| (*tetromino, *pos))
+            .collect();
+        let Some(board) = self.world.get_resource::<crate::components::Board>() else {
+            return blocks;
+        };
+        let tetromino_blocks = pieces.into_iter().flat_map(|(tetromino, pos)| {
+            board
+                .piece_set
+                .blocks(&tetromino)
+                .into_iter()
+                .map(move |(dx, dy)| {
                     let block_pos = crate::components::Position {
                         x: pos.x + dx,
                         y: pos.y + dy,
                     };
                     (block_pos, tetromino.tetromino_type)
                 })
-            })
-            .collect();
+        });
 
         blocks.extend(tetromino_blocks);
         blocks
     }
 
+    /// Starts a new game of the mode with the configured rules, recording it as a replay
+    pub fn start_game(&mut self, mode: GameModeKind) {
+        self.leave_game();
+
+        let seed = fastrand::u64(..);
+        let ruleset = self.config.ruleset(mode);
+        start_game(&mut self.world, mode, &ruleset, seed);
+
+        let handling = self
+            .world
+            .get_resource::<Handling>()
+            .copied()
+            .unwrap_or_default();
+        self.recorder = Some(ReplayRecorder::new(seed, mode, ruleset, handling));
+    }
+
+    // Runs one simulation frame, through the recorder when a game is being recorded
+    pub fn step_frame(&mut self) {
+        let Some(recorder) = self.recorder.as_mut() else {
+            systems::step_frame(&mut self.world);
+            return;
+        };
+        recorder.step(&mut self.world);
+
+        // A finished game is saved straight away, and asks for initials if it made the table
+        if self.world.resource::<GameState>().game_over {
+            self.pending_high_score = self
+                .high_scores
+                .candidate(&self.world, &self.config.player_name);
+            self.save_replay();
+        }
+    }
+
+    // Puts the game waiting on initials on its table and saves the tables
+    pub fn submit_high_score(&mut self) {
+        let Some(mut pending) = self.pending_high_score.take() else {
+            return;
+        };
+        if pending.entry.name.is_empty() {
+            pending.entry.name = "???".to_string();
+        } else {
+            self.config.player_name.clone_from(&pending.entry.name);
+            self.save_config();
+        }
+
+        self.high_score_place =
+            self.high_scores
+                .insert(pending.mode, &pending.ruleset, pending.entry);
+        if let Err(err) = self.high_scores.save() {
+            error!("Failed to save high scores: {err}");
+        }
+    }
+
+    // Saves the replay of the game being left and forgets what was shown when it ended
+    fn leave_game(&mut self) {
+        self.save_replay();
+        self.replay_player = None;
+        self.pending_high_score = None;
+        self.high_score_place = None;
+        self.show_summary = false;
+    }
+
+    // Writes the game recorded so far to the replay directory, if one was being recorded
+    pub fn save_replay(&mut self) {
+        let Some(recorder) = self.recorder.take() else {
+            return;
+        };
+        let replay = recorder.finish(&self.world);
+        if replay.frames == 0 {
+            return;
+        }
+        match replay.save() {
+            Ok(path) => info!("Saved replay to {}", path.display()),
+            Err(err) => error!("Failed to save replay: {err}"),
+        }
+    }
+
+    // Saves the game being played so it can be continued next time, a game that's over or
+    // isn't being played just has its replay saved
+    pub fn suspend_game(&mut self) {
+        let playing = self.menu.state == MenuState::Game
+            && self.replay_player.is_none()
+            && !self.world.resource::<GameState>().game_over;
+        if !playing {
+            self.save_replay();
+            return;
+        }
+
+        let game = SuspendedGame::capture(&mut self.world, self.recorder.take());
+        match game.save() {
+            Ok(()) => info!("Suspended the game in progress"),
+            Err(err) => {
+                error!("Failed to suspend the game: {err}");
+                self.recorder = game.recorder;
+                self.save_replay();
+            }
+        }
+    }
+
+    // Picks the suspended game back up on the pause menu. The suspend file is removed either
+    // way, so one that can't be read isn't offered again.
+    pub fn continue_game(&mut self) -> io::Result<()> {
+        let game = SuspendedGame::load();
+        SuspendedGame::discard();
+        self.menu.can_continue = false;
+        let game = game?;
+
+        self.leave_game();
+        self.recorder = game.restore(&mut self.world);
+        self.config.game_mode = self.world.resource::<GameMode>().kind;
+        self.menu.pause_selected = PauseOption::Resume;
+        Ok(())
+    }
+
+    // Loads a saved replay and starts playing it back in place of the game
+    pub fn watch_replay(&mut self, path: &Path) -> io::Result<()> {
+        let replay = Replay::load(path)?;
+        self.leave_game();
+        self.replay_player = Some(ReplayPlayer::new(replay, &mut self.world));
+        Ok(())
+    }
+
     // Update app state from game state
     pub fn sync_game_state(&mut self) {
         let game_state = self.world.resource::<GameState>();
@@ -145,9 +377,17 @@ impl App {
     pub fn on_tick(&mut self) {
         // Update last key if needed
         let input = self.world.resource::<Input>();
-        if input.left || input.right || input.down || input.rotate || input.hard_drop {
+        if input.left
+            || input.right
+            || input.down
+            || input.rotate
+            || input.rotate_ccw
+            || input.rotate_180
+            || input.hard_drop
+        {
+            let frame = self.world.resource::<Time>().frame();
             let mut game_state = self.world.resource_mut::<GameState>();
-            game_state.last_move = Instant::now();
+            game_state.last_move = frame;
         }
     }
 
@@ -169,38 +409,11 @@ impl App {
             .get_resource::<AudioState>()
             .map(|audio| audio.is_sound_enabled());
 
-        let show_grid = self
-            .world
-            .get_resource::<GameState>()
-            .map(|game| game.show_grid);
-
         // Save current menu state
         let current_menu_state = self.menu.state.clone();
 
-        // Reset game state
-        let mut game_state = GameState::default();
-        // Restore grid setting
-        if let Some(grid) = show_grid {
-            game_state.show_grid = grid;
-        }
-        self.world.insert_resource(game_state);
-
-        // Reset board
-        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
-        board.clear();
-        self.world.insert_resource(board);
-
-        // Reset input handler
-        let input = Input::default();
-        self.world.insert_resource(input);
-
-        // Reset screen shake
-        let screen_shake = ScreenShake::default();
-        self.world.insert_resource(screen_shake);
-
-        // Reset coyote time
-        let coyote_time = CoyoteTime::default();
-        self.world.insert_resource(coyote_time);
+        // Start the configured mode under its rules, recording the new game
+        self.start_game(self.config.game_mode);
 
         // Restore audio state
         let mut audio_state = AudioState::new();
@@ -219,13 +432,11 @@ impl App {
         self.menu_renderer = MenuRenderer::new();
         self.menu = Menu::new();
         self.menu.state = current_menu_state;
+        self.menu.can_continue = SuspendedGame::exists();
 
         // Reset game stats
         self.level = 1;
         self.lines_cleared = 0;
-
-        // Spawn initial tetromino
-        spawn_tetromino(&mut self.world);
     }
 }
 
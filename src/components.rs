@@ -14,67 +14,45 @@
 
 use bevy_ecs::prelude::*;
 use crossterm::event::KeyEvent;
-use std::time::Instant;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TetrominoType {
-    I,
-    J,
-    L,
-    O,
-    S,
-    T,
-    Z,
-}
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
-impl TetrominoType {
-    #[must_use]
-    pub fn random() -> Self {
-        match fastrand::u8(0..7) {
-            0 => TetrominoType::I,
-            1 => TetrominoType::J,
-            2 => TetrominoType::L,
-            3 => TetrominoType::O,
-            4 => TetrominoType::S,
-            5 => TetrominoType::T,
-            _ => TetrominoType::Z,
-        }
-    }
+use crate::pieces::{PieceSet, PieceSetKind};
+use crate::scoring::ScoringRuleKind;
 
-    #[must_use]
-    pub fn get_blocks(self) -> Vec<(i32, i32)> {
-        match self {
-            TetrominoType::I => vec![(0, 0), (0, 1), (0, 2), (0, 3)],
-            TetrominoType::J => vec![(0, 0), (0, 1), (0, 2), (-1, 2)],
-            TetrominoType::L => vec![(0, 0), (0, 1), (0, 2), (1, 2)],
-            TetrominoType::O => vec![(0, 0), (0, 1), (1, 0), (1, 1)],
-            TetrominoType::S => vec![(0, 0), (0, 1), (1, 1), (1, 2)],
-            TetrominoType::T => vec![(0, 0), (0, 1), (0, 2), (1, 1)],
-            TetrominoType::Z => vec![(0, 0), (0, 1), (-1, 1), (-1, 2)],
-        }
-    }
+/// A piece of the game's piece set, by its index there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TetrominoType(pub u8);
 
-    #[must_use]
-    pub fn get_color(self) -> ratatui::style::Color {
-        match self {
-            TetrominoType::I => ratatui::style::Color::Cyan,
-            TetrominoType::J => ratatui::style::Color::Blue,
-            TetrominoType::L => ratatui::style::Color::LightYellow,
-            TetrominoType::O => ratatui::style::Color::Yellow,
-            TetrominoType::S => ratatui::style::Color::Green,
-            TetrominoType::T => ratatui::style::Color::Magenta,
-            TetrominoType::Z => ratatui::style::Color::Red,
-        }
-    }
+#[allow(dead_code)] // The game itself goes by the loaded set, these name the classic pieces
+impl TetrominoType {
+    // The pieces of the classic set
+    pub const I: TetrominoType = TetrominoType(0);
+    pub const J: TetrominoType = TetrominoType(1);
+    pub const L: TetrominoType = TetrominoType(2);
+    pub const O: TetrominoType = TetrominoType(3);
+    pub const S: TetrominoType = TetrominoType(4);
+    pub const T: TetrominoType = TetrominoType(5);
+    pub const Z: TetrominoType = TetrominoType(6);
+
+    pub const ALL: [TetrominoType; 7] = [
+        TetrominoType::I,
+        TetrominoType::J,
+        TetrominoType::L,
+        TetrominoType::O,
+        TetrominoType::S,
+        TetrominoType::T,
+        TetrominoType::Z,
+    ];
 }
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
-#[derive(Component, Debug, Clone, Copy)]
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Tetromino {
     pub tetromino_type: TetrominoType,
     pub rotation: usize,
@@ -89,133 +67,215 @@ impl Tetromino {
         }
     }
 
-    #[must_use]
-    pub fn get_blocks(self) -> Vec<(i32, i32)> {
-        let blocks = self.tetromino_type.get_blocks();
-        match self.rotation {
-            0 => blocks,
-            1 => blocks.iter().map(|(x, y)| (-y, *x)).collect(),
-            2 => blocks.iter().map(|(x, y)| (-x, -y)).collect(),
-            3 => blocks.iter().map(|(x, y)| (*y, -x)).collect(),
-            _ => unreachable!(),
-        }
-    }
-
     pub fn rotate(&mut self) {
         self.rotation = (self.rotation + 1) % 4;
     }
+
+    pub fn rotate_counter_clockwise(&mut self) {
+        self.rotation = (self.rotation + 3) % 4;
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.rotation = (self.rotation + 2) % 4;
+    }
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct Board {
     pub width: usize,
-    pub height: usize,
-    pub cells: Vec<Vec<Option<TetrominoType>>>,
+    pub height: usize,                           // Visible rows
+    pub hidden_rows: usize, // Buffer rows above the visible field, at negative y
+    pub cells: Vec<Vec<Option<TetrominoType>>>, // Visible field, cells[x][y] with y = 0 the top row
+    pub hidden: Vec<Vec<Option<TetrominoType>>>, // Buffer, hidden[x][0] is the topmost row at y = -hidden_rows
+    #[serde(default)]
+    pub piece_set: PieceSet, // The pieces played on the board, their shapes and kicks
 }
 
 impl Board {
     #[must_use]
     pub fn new(width: usize, height: usize) -> Self {
+        Self::with_piece_set(width, height, PieceSet::classic())
+    }
+
+    #[must_use]
+    pub fn with_piece_set(width: usize, height: usize, piece_set: PieceSet) -> Self {
+        let hidden_rows = crate::game::HIDDEN_ROWS;
         Self {
             width,
             height,
+            hidden_rows,
             cells: vec![vec![None; height]; width],
+            hidden: vec![vec![None; hidden_rows]; width],
+            piece_set,
         }
     }
 
-    pub fn clear(&mut self) {
-        for x in 0..self.width {
-            for y in 0..self.height {
-                self.cells[x][y] = None;
-            }
-        }
+    // Row index of the topmost buffer row
+    #[must_use]
+    pub fn top(&self) -> i32 {
+        -(self.hidden_rows as i32)
     }
 
     #[must_use]
-    pub fn is_valid_position(&self, position: Position, tetromino: &Tetromino) -> bool {
-        let blocks = tetromino.get_blocks();
-
-        for (block_x, block_y) in blocks {
-            let x = position.x + block_x;
-            let y = position.y + block_y;
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.width as i32 && y >= self.top() && y < self.height as i32
+    }
 
-            // Check if block is out of bounds
-            if x < 0
-                || x >= i32::try_from(self.width).unwrap_or(i32::MAX)
-                || y < 0
-                || y >= i32::try_from(self.height).unwrap_or(i32::MAX)
-            {
-                return false;
-            }
+    // Contents of a cell, rows above the visible field are read from the buffer
+    #[must_use]
+    pub fn cell(&self, x: i32, y: i32) -> Option<TetrominoType> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        if y < 0 {
+            self.hidden[x as usize][(y - self.top()) as usize]
+        } else {
+            self.cells[x as usize][y as usize]
+        }
+    }
 
-            // Check if space is already occupied
-            if self.cells[x as usize][y as usize].is_some() {
-                return false;
-            }
+    pub fn set_cell(&mut self, x: i32, y: i32, value: Option<TetrominoType>) {
+        if !self.in_bounds(x, y) {
+            return;
         }
+        if y < 0 {
+            let row = (y - self.top()) as usize;
+            self.hidden[x as usize][row] = value;
+        } else {
+            self.cells[x as usize][y as usize] = value;
+        }
+    }
 
-        true
+    pub fn clear(&mut self) {
+        for column in self.cells.iter_mut().chain(self.hidden.iter_mut()) {
+            column.fill(None);
+        }
     }
 
-    pub fn lock_tetromino(&mut self, position: Position, tetromino: &Tetromino) {
-        let blocks = tetromino.get_blocks();
+    // True when nothing is left anywhere on the board, buffer included
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().flatten().all(Option::is_none) && self.is_buffer_empty()
+    }
 
-        for (block_x, block_y) in blocks {
-            let x = position.x + block_x;
-            let y = position.y + block_y;
+    #[must_use]
+    pub fn is_buffer_empty(&self) -> bool {
+        self.hidden.iter().flatten().all(Option::is_none)
+    }
 
-            if x >= 0
-                && x < i32::try_from(self.width).unwrap_or(i32::MAX)
-                && y >= 0
-                && y < i32::try_from(self.height).unwrap_or(i32::MAX)
-            {
-                self.cells[x as usize][y as usize] = Some(tetromino.tetromino_type);
+    // Empty cells with a block somewhere above them in the same column, buffer included
+    #[must_use]
+    pub fn holes(&self) -> u32 {
+        let mut holes = 0;
+        for x in 0..self.width as i32 {
+            let mut covered = false;
+            for y in self.top()..self.height as i32 {
+                if self.cell(x, y).is_some() {
+                    covered = true;
+                } else if covered {
+                    holes += 1;
+                }
             }
         }
+        holes
     }
 
-    /// Clears completed lines and returns the number of lines cleared and their indices
-    pub fn clear_lines_with_indices(&mut self) -> (usize, Vec<usize>) {
-        let mut lines_cleared = 0;
-        let mut cleared_indices = Vec::new();
+    #[must_use]
+    pub fn is_valid_position(&self, position: Position, tetromino: &Tetromino) -> bool {
+        self.piece_set
+            .blocks(tetromino)
+            .into_iter()
+            .all(|(block_x, block_y)| {
+                let x = position.x + block_x;
+                let y = position.y + block_y;
+                self.in_bounds(x, y) && self.cell(x, y).is_none()
+            })
+    }
 
-        // First identify which lines need to be cleared
-        for y in 0..self.height {
-            let mut is_line_full = true;
-            for x in 0..self.width {
-                if self.cells[x][y].is_none() {
-                    is_line_full = false;
-                    break;
-                }
-            }
+    /// Tries each kick for rotating `from` into `to` in order, returning the
+    /// kicked position together with the index of the kick test that succeeded
+    #[must_use]
+    pub fn find_kick(
+        &self,
+        position: Position,
+        from: &Tetromino,
+        to: &Tetromino,
+    ) -> Option<(Position, usize)> {
+        self.piece_set
+            .kick_tests(from, to)
+            .into_iter()
+            .enumerate()
+            .find_map(|(kick_index, (dx, dy))| {
+                let kicked = Position {
+                    x: position.x + dx,
+                    y: position.y + dy,
+                };
+                self.is_valid_position(kicked, to)
+                    .then_some((kicked, kick_index))
+            })
+    }
 
-            if is_line_full {
-                cleared_indices.push(y);
+    /// Moves a piece down until it rests on the stack or has fallen `max_rows` rows
+    #[must_use]
+    pub fn fall(&self, position: Position, tetromino: &Tetromino, max_rows: u32) -> Position {
+        let mut landed = position;
+        for _ in 0..max_rows {
+            let below = Position {
+                x: landed.x,
+                y: landed.y + 1,
+            };
+            if !self.is_valid_position(below, tetromino) {
+                break;
             }
+            landed = below;
         }
+        landed
+    }
 
-        // Then clear them from bottom to top to avoid affecting indices
-        for &y in cleared_indices.iter().rev() {
-            // Move all lines above down one
-            for y2 in (1..=y).rev() {
-                for x in 0..self.width {
-                    self.cells[x][y2] = self.cells[x][y2 - 1];
+    pub fn lock_tetromino(&mut self, position: Position, tetromino: &Tetromino) {
+        for (block_x, block_y) in self.piece_set.blocks(tetromino) {
+            self.set_cell(
+                position.x + block_x,
+                position.y + block_y,
+                Some(tetromino.tetromino_type),
+            );
+        }
+    }
+
+    /// Clears completed lines, buffer rows included, and returns the number of lines cleared
+    /// along with the indices of the cleared visible rows
+    pub fn clear_lines_with_indices(&mut self) -> (usize, Vec<usize>) {
+        let width = self.width as i32;
+
+        // First identify which lines need to be cleared
+        let full_rows: Vec<i32> = (self.top()..self.height as i32)
+            .filter(|&y| (0..width).all(|x| self.cell(x, y).is_some()))
+            .collect();
+
+        // Then clear them from top to bottom, so shifting the rows above never moves a full row
+        // that is still waiting to be cleared
+        for &y in &full_rows {
+            for row in (self.top() + 1..=y).rev() {
+                for x in 0..width {
+                    self.set_cell(x, row, self.cell(x, row - 1));
                 }
             }
 
             // Clear top line
-            for x in 0..self.width {
-                self.cells[x][0] = None;
+            for x in 0..width {
+                self.set_cell(x, self.top(), None);
             }
-
-            lines_cleared += 1;
         }
 
-        (lines_cleared, cleared_indices)
+        let visible_indices = full_rows
+            .iter()
+            .filter_map(|&y| usize::try_from(y).ok())
+            .collect();
+        (full_rows.len(), visible_indices)
     }
 }
 
-#[derive(Debug, Resource, Clone)]
+#[derive(Debug, Resource, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub score: u32,
     pub level: u32,
@@ -226,17 +286,25 @@ pub struct GameState {
     pub perfect_clear_count: u32,
     pub combo_count: u32,
     pub back_to_back: bool,
-    pub next_tetromino: Option<TetrominoType>,
-    pub last_move: Instant,
+    pub next_queue: VecDeque<TetrominoType>, // Upcoming pieces, the front one is dealt next
+    pub held_tetromino: Option<TetrominoType>,
+    pub hold_used: bool, // Set once the active piece came out of a hold swap, cleared when a new piece spawns
+    pub last_move: u64,  // Simulation frame of the last player input
+    #[serde(skip)]
     pub last_key: Option<KeyEvent>,
     pub was_paused_for_resize: bool,
+    pub paused: bool, // Paused from the pause menu, the board is hidden meanwhile
+    pub resume_countdown: f32, // Seconds left of the countdown after resuming
     pub hard_drop_distance: u32,
-    pub drop_timer: f32,
-    pub coyote_time_active: bool,
-    pub coyote_time_timer: f32,
+    pub gravity_progress: f32, // Rows of gravity banked towards the next fall
     pub soft_drop_distance: u32,
     pub last_clear_was_difficult: bool,
     pub show_grid: bool,
+    pub scoring_rule: ScoringRuleKind,
+    pub top_out: Option<TopOut>, // Why the game ended, None while playing or after completing a mode
+    pub partial_lock_out: bool,  // Also top out when a piece locks with any block above the field
+    #[serde(default)]
+    pub piece_set: PieceSetKind, // Where the board's piece set came from
 }
 
 impl Default for GameState {
@@ -251,216 +319,140 @@ impl Default for GameState {
             perfect_clear_count: 0,
             combo_count: 0,
             back_to_back: false,
-            next_tetromino: None,
-            last_move: Instant::now(),
+            next_queue: VecDeque::new(),
+            held_tetromino: None,
+            hold_used: false,
+            last_move: 0,
             last_key: None,
             was_paused_for_resize: false,
+            paused: false,
+            resume_countdown: 0.0,
             hard_drop_distance: 0,
-            drop_timer: 0.0,
-            coyote_time_active: false,
-            coyote_time_timer: 0.0,
+            gravity_progress: 0.0,
             soft_drop_distance: 0,
             last_clear_was_difficult: false,
             show_grid: false,
+            scoring_rule: ScoringRuleKind::default(),
+            top_out: None,
+            partial_lock_out: false,
+            piece_set: PieceSetKind::default(),
         }
     }
 }
 
 impl GameState {
     pub fn reset(&mut self) {
-        *self = Self::default();
+        // The scoring and top-out rules and the pieces are choices made before the game, so a
+        // restart keeps them
+        *self = Self {
+            scoring_rule: self.scoring_rule,
+            partial_lock_out: self.partial_lock_out,
+            piece_set: self.piece_set,
+            ..Self::default()
+        };
     }
 
     // Check if board is completely clear (perfect clear)
     #[must_use]
     pub fn is_perfect_clear(board: &Board) -> bool {
-        for y in 0..board.height {
-            for x in 0..board.width {
-                if board.cells[x][y].is_some() {
-                    return false; // Found a block, so not a perfect clear
-                }
-            }
-        }
-        true
+        board.is_empty()
     }
 
-    // Check if the last move was a T-spin
-    // The simplified algorithm checks if:
-    // 1. The piece is a T tetromino
-    // 2. The piece was just rotated (not moved)
-    // 3. At least 3 of the 4 corners around the T center are blocked
+    // Classifies a T-spin-eligible piece about to lock using the guideline 3-corner rule.
+    // The last successful action has to be a rotation and at least 3 of the 4 corners around the
+    // piece's center must be blocked (walls and floor count). It's a full T-spin when both corners
+    // the piece points towards are blocked, or when the rotation needed the final kick test;
+    // otherwise it's a Mini.
     #[must_use]
-    pub fn is_t_spin(board: &Board, position: Position, tetromino: &Tetromino) -> bool {
-        // Check if this is a T tetromino
-        if tetromino.tetromino_type != TetrominoType::T {
-            return false;
-        }
-
-        // Get the center of the T (assuming the center of T is at offset (1, 1) from piece origin)
-        let center_x = position.x + 1;
-        let center_y = position.y + 1;
-
-        // Check the 4 corners around the T center
-        let corners = [
-            (center_x - 1, center_y - 1), // Top-left
-            (center_x + 1, center_y - 1), // Top-right
-            (center_x - 1, center_y + 1), // Bottom-left
-            (center_x + 1, center_y + 1), // Bottom-right
-        ];
-
-        // Count how many corners are blocked (either by a block or the boundary)
-        let mut blocked_corners = 0;
-        for (x, y) in &corners {
-            // Check if corner is outside the board
-            if *x < 0
-                || *x >= i32::try_from(board.width).unwrap_or(i32::MAX)
-                || *y < 0
-                || *y >= i32::try_from(board.height).unwrap_or(i32::MAX)
-            {
-                blocked_corners += 1;
-                continue;
-            }
-
-            // Check if corner has a block
-            #[allow(clippy::cast_sign_loss)]
-            if board.cells[*x as usize][*y as usize].is_some() {
-                blocked_corners += 1;
-            }
-        }
-
-        // A T-spin requires at least 3 corners to be blocked
-        blocked_corners >= 3
-    }
-
-    // Calculate score based on advanced mechanics
-    pub fn update_score(&mut self, lines_cleared: usize, is_t_spin: bool, is_perfect_clear: bool) {
-        if lines_cleared == 0 {
-            // Reset combo count if no lines were cleared
-            self.combo_count = 0;
-            return;
+    pub fn detect_t_spin(
+        board: &Board,
+        position: Position,
+        tetromino: &Tetromino,
+        last_action: LastAction,
+    ) -> TSpin {
+        if !board.piece_set.piece(tetromino.tetromino_type).t_spin {
+            return TSpin::None;
         }
 
-        // Increment combo count for consecutive line clears
-        self.combo_count += 1;
-
-        // Determine base points based on clear type
-        let mut base_points = if is_t_spin {
-            // T-spin line clears
-            match lines_cleared {
-                1 => {
-                    self.t_spin_count += 1;
-                    crate::game::TSPIN_SINGLE
-                }
-                2 => {
-                    self.t_spin_count += 1;
-                    crate::game::TSPIN_DOUBLE
-                }
-                3 => {
-                    self.t_spin_count += 1;
-                    crate::game::TSPIN_TRIPLE
-                }
-                _ => 0, // T-spins with more than 3 lines cleared are not standard
-            }
-        } else {
-            // Standard line clears
-            match lines_cleared {
-                1 => crate::game::POINTS_SINGLE,
-                2 => crate::game::POINTS_DOUBLE,
-                3 => crate::game::POINTS_TRIPLE,
-                4 => {
-                    self.tetris_count += 1;
-                    crate::game::POINTS_TETRIS
-                }
-                _ => 0,
-            }
+        let LastAction::Rotate { kick } = last_action else {
+            return TSpin::None;
         };
 
-        // Check if the clear qualifies as a difficult clear
-        let is_difficult_clear = lines_cleared == 4 || is_t_spin;
-
-        // Apply back-to-back bonus if applicable
-        if is_difficult_clear && self.last_clear_was_difficult && self.back_to_back {
-            base_points = (base_points as f32 * crate::game::BACK_TO_BACK_MULTIPLIER) as u32;
-        }
-
-        // Update back-to-back status
-        self.back_to_back = is_difficult_clear;
-        self.last_clear_was_difficult = is_difficult_clear;
-
-        // Apply combo bonus
-        let combo_bonus = if self.combo_count > 1 {
-            ((self.combo_count - 1) as f32 * crate::game::COMBO_MULTIPLIER * base_points as f32)
-                as u32
-        } else {
-            0
+        // The T rotates about its center, which sits at the piece position
+        let is_blocked = |(dx, dy): (i32, i32)| {
+            let x = position.x + dx;
+            let y = position.y + dy;
+            !board.in_bounds(x, y) || board.cell(x, y).is_some()
         };
 
-        // Apply perfect clear bonus
-        let perfect_clear_bonus = if is_perfect_clear {
-            self.perfect_clear_count += 1;
-            crate::game::PERFECT_CLEAR_BONUS
-        } else {
-            0
+        // Front corners flank the side the T points towards, the back corners are the other two
+        let (front, back) = match tetromino.rotation % 4 {
+            0 => ([(-1, -1), (1, -1)], [(-1, 1), (1, 1)]),
+            1 => ([(1, -1), (1, 1)], [(-1, -1), (-1, 1)]),
+            2 => ([(-1, 1), (1, 1)], [(-1, -1), (1, -1)]),
+            _ => ([(-1, -1), (-1, 1)], [(1, -1), (1, 1)]),
         };
 
-        // Apply soft drop and hard drop bonuses
-        let drop_bonus = (self.soft_drop_distance * crate::game::SOFT_DROP_POINTS)
-            + (self.hard_drop_distance * crate::game::HARD_DROP_POINTS);
+        let front_blocked = front
+            .into_iter()
+            .filter(|&corner| is_blocked(corner))
+            .count();
+        let back_blocked = back
+            .into_iter()
+            .filter(|&corner| is_blocked(corner))
+            .count();
+
+        if front_blocked + back_blocked < 3 {
+            TSpin::None
+        } else if front_blocked == 2 || kick == 4 {
+            TSpin::Full
+        } else {
+            TSpin::Mini
+        }
+    }
 
-        // Reset drop distances
-        self.soft_drop_distance = 0;
-        self.hard_drop_distance = 0;
+    // Score a locked piece with the active scoring rule
+    pub fn update_score(&mut self, lines_cleared: usize, t_spin: TSpin, is_perfect_clear: bool) {
+        self.scoring_rule
+            .rule()
+            .score_lock(self, lines_cleared, t_spin, is_perfect_clear);
+        self.update_level();
+    }
 
-        // Calculate total points with level multiplier
-        let level_multiplier = self.level;
-        let total_points =
-            (base_points * level_multiplier) + combo_bonus + perfect_clear_bonus + drop_bonus;
+    // Whether play is frozen, by the pause menu, its resume countdown or a too small terminal
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.paused || self.resume_countdown > 0.0 || self.was_paused_for_resize
+    }
 
-        // Update score
-        self.score += total_points;
-        self.lines_cleared += u32::try_from(lines_cleared).unwrap_or(u32::MAX);
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.resume_countdown = 0.0;
+    }
 
-        // Update level based on lines cleared and score
-        self.update_level();
+    // Leaves the pause menu, play picks up once the countdown runs out
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.resume_countdown = crate::game::RESUME_COUNTDOWN;
     }
 
-    // Update level based on both lines cleared and score thresholds
+    // Update level according to the active scoring rule
     pub fn update_level(&mut self) {
-        // Traditional level progression based on lines cleared
-        let lines_level =
-            (self.lines_cleared / crate::game::LINES_PER_LEVEL) + crate::game::STARTING_LEVEL;
-
-        // Level progression based on score thresholds
-        let mut score_level = crate::game::STARTING_LEVEL;
-        for &(threshold, level) in crate::game::LEVEL_SCORE_THRESHOLDS {
-            if self.score >= threshold {
-                score_level = level;
-            } else {
-                break;
-            }
-        }
+        self.scoring_rule.rule().update_level(self);
+    }
 
-        // Take the maximum of the two approaches, but cap at MAX_LEVEL
-        self.level = std::cmp::min(
-            std::cmp::max(lines_level, score_level),
-            crate::game::MAX_LEVEL,
-        );
+    // Guideline gravity in cells per frame, a row takes (0.8-(level-1)*0.007)^(level-1) seconds
+    #[must_use]
+    pub fn gravity(&self) -> f32 {
+        let level = self.level.max(1) as f32 - 1.0;
+        let seconds_per_row = (0.8 - level * 0.007).powf(level);
+        (1.0 / (seconds_per_row * crate::game::FRAMES_PER_SECOND)).min(crate::game::MAX_GRAVITY)
     }
 
+    // Seconds for gravity to pull the piece down one row
     #[must_use]
     pub fn get_drop_delay(&self) -> f32 {
-        // Speed increases with level (faster drops as level increases)
-        // More aggressive curve with higher levels
-        if self.level < 10 {
-            // Levels 1-9: linear decrease
-            0.8 - (self.level as f32 - 1.0) * 0.07
-        } else if self.level < 20 {
-            // Levels 10-19: steeper decrease
-            0.2 - (self.level as f32 - 10.0) * 0.01
-        } else {
-            // Levels 20+: minimum delay
-            0.1
-        }
+        1.0 / (self.gravity() * crate::game::FRAMES_PER_SECOND)
     }
 
     pub fn update_hard_drop_score(&mut self, drop_distance: u32) {
@@ -482,7 +474,7 @@ pub struct Particle {
 }
 
 // Screen shake effect
-#[derive(Debug, Clone, Resource, Default)]
+#[derive(Debug, Clone, Resource, Default, Serialize, Deserialize)]
 pub struct ScreenShake {
     pub intensity: f32,
     pub duration: f32,
@@ -491,25 +483,350 @@ pub struct ScreenShake {
     pub horizontal_bias: bool, // When true, shake will prioritize horizontal movement
 }
 
+// The last successful action on the active tetromino, used to tell T-spins from plain drops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Default, Serialize, Deserialize)]
+pub enum LastAction {
+    #[default]
+    Spawn,
+    Move, // Shifted sideways or fell at least one row
+    Rotate {
+        kick: usize,
+    }, // Rotated, with the index of the SRS kick test that fit
+}
+
+// How a locking T piece scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TSpin {
+    #[default]
+    None,
+    Mini,
+    Full,
+}
+
+// Guideline ways of topping out, named as the guideline names them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum TopOut {
+    BlockOut,       // A new piece spawned overlapping the stack
+    LockOut,        // A piece locked entirely above the visible field
+    PartialLockOut, // A piece locked with some of its blocks above the visible field
+}
+
+impl TopOut {
+    // Short explanation shown on the game over screen
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            TopOut::BlockOut => "Block out",
+            TopOut::LockOut => "Lock out",
+            TopOut::PartialLockOut => "Partial lock out",
+        }
+    }
+}
+
 // Input state for keyboard controls
 #[derive(Resource, Debug, Clone, Default)]
 pub struct Input {
     pub left: bool,
     pub right: bool,
     pub down: bool,
-    pub rotate: bool,
+    pub rotate: bool,     // Rotate clockwise
+    pub rotate_ccw: bool, // Rotate counter-clockwise
+    pub rotate_180: bool, // Rotate by 180 degrees
     pub hard_drop: bool,
     pub hard_drop_released: bool, // Track if the hard drop key has been released
+    pub hold: bool,               // Swap the active tetromino with the hold slot
     pub toggle_music: bool,       // Toggle background music on/off
     pub volume_up: bool,          // Increase volume
     pub volume_down: bool,        // Decrease volume
 }
 
-// Coyote time mechanic (last chance to move after landing)
-#[derive(Debug, Clone, Resource, Default)]
-pub struct CoyoteTime {
-    pub active: bool,
-    pub timer: f32,
+// Rotation and hold pressed while waiting for the next piece, which spawns already rotated or
+// swapped (IRS and IHS)
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InitialInputs {
+    pub rotation: usize, // Quarter turns clockwise, from the latest rotation pressed
+    pub hold: bool,
+}
+
+impl InitialInputs {
+    // Notes the rotation and hold keys pressed this frame, in input_system's order of precedence
+    pub fn record(&mut self, input: &Input) {
+        if input.rotate {
+            self.rotation = 1;
+        } else if input.rotate_ccw {
+            self.rotation = 3;
+        } else if input.rotate_180 {
+            self.rotation = 2;
+        }
+        self.hold |= input.hold;
+    }
+}
+
+// Held state of the auto-repeating movement keys, kept across ticks unlike Input
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoShift {
+    pub left_held: bool,
+    pub right_held: bool,
+    pub down_held: bool,
+    pub direction: i32, // Direction being auto-shifted, the most recently pressed key wins
+    pub das_timer: f32, // How long the current direction has been held
+    pub arr_timer: f32, // Time banked towards the next repeated shift
+    pub soft_drop_timer: f32, // Time banked towards the next soft drop step
+}
+
+impl AutoShift {
+    pub fn press_horizontal(&mut self, dx: i32) {
+        if dx < 0 {
+            self.left_held = true;
+        } else {
+            self.right_held = true;
+        }
+        self.direction = dx.signum();
+        self.das_timer = 0.0;
+        self.arr_timer = 0.0;
+    }
+
+    pub fn release_horizontal(&mut self, dx: i32) {
+        if dx < 0 {
+            self.left_held = false;
+        } else {
+            self.right_held = false;
+        }
+
+        // Fall back to the opposite key if it's still down, recharging DAS for it
+        if self.direction == dx.signum() {
+            self.direction = i32::from(self.right_held) - i32::from(self.left_held);
+            self.das_timer = 0.0;
+            self.arr_timer = 0.0;
+        }
+    }
+
+    pub fn press_down(&mut self) {
+        self.down_held = true;
+        self.soft_drop_timer = 0.0;
+    }
+
+    pub fn release_down(&mut self) {
+        self.down_held = false;
+        self.soft_drop_timer = 0.0;
+    }
+
+    // Advances the DAS and ARR timers, returning how many cells the held direction should shift.
+    // Once DAS charges the piece shifts one cell, then one more every ARR; an ARR of zero returns
+    // u32::MAX, meaning shift all the way to the wall.
+    pub fn horizontal_steps(&mut self, delta_seconds: f32, das: f32, arr: f32) -> u32 {
+        if self.direction == 0 {
+            return 0;
+        }
+
+        let was_charged = self.das_timer >= das;
+        self.das_timer += delta_seconds;
+        if self.das_timer < das {
+            return 0;
+        }
+
+        if arr <= 0.0 {
+            return u32::MAX;
+        }
+
+        let mut steps = 0;
+        if was_charged {
+            self.arr_timer += delta_seconds;
+        } else {
+            // DAS just charged: shift once now and bank the leftover time towards the next repeat
+            steps += 1;
+            self.arr_timer += self.das_timer - das;
+        }
+
+        while self.arr_timer >= arr {
+            self.arr_timer -= arr;
+            steps += 1;
+        }
+
+        steps
+    }
+
+    // Advances the soft drop timer, returning how many cells a held soft drop should fall
+    pub fn soft_drop_steps(&mut self, delta_seconds: f32, interval: f32) -> u32 {
+        if !self.down_held || interval <= 0.0 {
+            return 0;
+        }
+
+        self.soft_drop_timer += delta_seconds;
+        let mut steps = 0;
+        while self.soft_drop_timer >= interval {
+            self.soft_drop_timer -= interval;
+            steps += 1;
+        }
+
+        steps
+    }
+}
+
+// Auto-repeat timings for held movement keys, copied from the config
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handling {
+    pub das_ms: u32,
+    pub arr_ms: u32,
+    pub soft_drop_factor: u32,
+}
+
+impl Default for Handling {
+    fn default() -> Self {
+        Self {
+            das_ms: crate::game::DEFAULT_DAS_MS,
+            arr_ms: crate::game::DEFAULT_ARR_MS,
+            soft_drop_factor: crate::game::DEFAULT_SOFT_DROP_FACTOR,
+        }
+    }
+}
+
+// How long each phase of a piece lasts, part of the rules a game is played under
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::struct_field_names)] // Every timing is a delay, named as in the config file
+pub struct Timings {
+    pub lock_delay_ms: u32,
+    pub line_clear_delay_ms: u32, // Cleared rows stay up, flashing, for this long
+    pub entry_delay_ms: u32,      // ARE, the wait between a piece locking and the next one spawning
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self {
+            lock_delay_ms: crate::game::DEFAULT_LOCK_DELAY_MS,
+            line_clear_delay_ms: crate::game::DEFAULT_LINE_CLEAR_DELAY_MS,
+            entry_delay_ms: crate::game::DEFAULT_ENTRY_DELAY_MS,
+        }
+    }
+}
+
+impl Timings {
+    #[must_use]
+    pub fn lock_delay(self) -> f32 {
+        self.lock_delay_ms as f32 / 1000.0
+    }
+
+    #[must_use]
+    pub fn line_clear_delay(self) -> f32 {
+        self.line_clear_delay_ms as f32 / 1000.0
+    }
+
+    #[must_use]
+    pub fn entry_delay(self) -> f32 {
+        self.entry_delay_ms as f32 / 1000.0
+    }
+}
+
+// Where the game is in the life of a piece: Falling, Locking, LineClear and EntryDelay in turn,
+// with the next piece spawning as the entry delay runs out
+#[derive(Resource, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Phase {
+    #[default]
+    Falling, // The active piece is in the air
+    Locking, // The active piece rests on the stack while the lock delay runs
+    LineClear {
+        rows: Vec<usize>, // Visible rows that are full, flashing until they're removed
+        timer: f32,
+    },
+    EntryDelay {
+        timer: f32,
+    },
+}
+
+impl Phase {
+    // Whether the board is between pieces, with nothing in play
+    #[must_use]
+    pub fn is_between_pieces(&self) -> bool {
+        matches!(self, Phase::LineClear { .. } | Phase::EntryDelay { .. })
+    }
+
+    // Rows of the line clear lit up by its flash right now, none outside a line clear
+    #[must_use]
+    pub fn flashing_rows(&self) -> &[usize] {
+        match self {
+            Phase::LineClear { rows, timer }
+                if ((timer / crate::game::LINE_CLEAR_FLASH_SECONDS) as u32).is_multiple_of(2) =>
+            {
+                rows
+            }
+            _ => &[],
+        }
+    }
+}
+
+// Guideline lock delay: a piece resting on the stack locks once the timer runs out.
+// Moves and rotations restart the timer up to MAX_LOCK_RESETS times, and reaching a new
+// lowest row hands the resets back, so a piece can't be kept alive forever.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct LockDelay {
+    pub active: bool,    // The piece is resting on the stack and the timer is running
+    pub timer: f32,      // Time spent on the ground since the last reset
+    pub duration: f32,   // How long a resting piece waits before it locks
+    pub resets: u32,     // Resets used since the piece last reached a new lowest row
+    pub lowest_row: i32, // Lowest row the piece has reached so far
+}
+
+impl Default for LockDelay {
+    fn default() -> Self {
+        Self {
+            active: false,
+            timer: 0.0,
+            duration: Timings::default().lock_delay(),
+            resets: 0,
+            lowest_row: i32::MIN,
+        }
+    }
+}
+
+impl LockDelay {
+    // Starts tracking a freshly spawned piece at the given row
+    pub fn start_piece(&mut self, row: i32) {
+        self.active = false;
+        self.timer = 0.0;
+        self.resets = 0;
+        self.lowest_row = row;
+    }
+
+    // Restarts the timer after a successful move or rotation on the ground, while resets remain
+    pub fn register_move(&mut self) {
+        if self.active && self.resets < crate::game::MAX_LOCK_RESETS {
+            self.resets += 1;
+            self.timer = 0.0;
+        }
+    }
+
+    // Records the piece reaching a row, a new lowest row hands back all the resets
+    pub fn reach_row(&mut self, row: i32) {
+        if row > self.lowest_row {
+            self.lowest_row = row;
+            self.resets = 0;
+            self.timer = 0.0;
+        }
+    }
+
+    // Advances the timer, returning true once a grounded piece should lock.
+    // A piece that has used up its resets locks as soon as it touches the ground.
+    pub fn tick(&mut self, grounded: bool, delta_seconds: f32) -> bool {
+        if !grounded {
+            self.active = false;
+            self.timer = 0.0;
+            return false;
+        }
+
+        self.active = true;
+        self.timer += delta_seconds;
+        self.timer >= self.duration || self.resets >= crate::game::MAX_LOCK_RESETS
+    }
+
+    // Share of the lock delay still left, used by the indicator under the piece
+    #[must_use]
+    pub fn remaining_fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - self.timer / self.duration).clamp(0.0, 1.0)
+    }
 }
 
 // Ghost piece that shows where the tetromino will land
@@ -1,14 +1,145 @@
+use crate::components::{Handling, Timings};
+use crate::game::{
+    BOARD_HEIGHT, BOARD_WIDTH, DEFAULT_ARR_MS, DEFAULT_DAS_MS, DEFAULT_PREVIEW_COUNT,
+    DEFAULT_SOFT_DROP_FACTOR, MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MAX_PREVIEW_COUNT,
+    MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH,
+};
+use crate::mode::GameModeKind;
+use crate::pieces::PieceSetKind;
+use crate::randomizer::RandomizerKind;
+use crate::scoring::ScoringRuleKind;
+use crate::stats::StatKind;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io;
 use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Each flag is an independent on/off setting
 pub struct Config {
     pub music_enabled: bool,
     pub sound_enabled: bool,
     pub volume: f32,
     pub show_grid: bool,
+    #[serde(default)]
+    pub randomizer: RandomizerKind,
+    #[serde(default = "default_preview_count")]
+    pub preview_count: usize,
+    #[serde(default = "default_das_ms")]
+    pub das_ms: u32,
+    #[serde(default = "default_arr_ms")]
+    pub arr_ms: u32,
+    #[serde(default = "default_soft_drop_factor")]
+    pub soft_drop_factor: u32,
+    #[serde(default)]
+    pub game_mode: GameModeKind,
+    #[serde(default)]
+    pub scoring_rules: BTreeMap<GameModeKind, ScoringRuleKind>, // Modes missing here use their default rule
+    #[serde(default = "default_board_width")]
+    pub board_width: usize,
+    #[serde(default = "default_board_height")]
+    pub board_height: usize,
+    #[serde(default)]
+    pub partial_lock_out: bool,
+    #[serde(default)]
+    pub finesse_training: bool,
+    #[serde(default)]
+    pub piece_set: PieceSetKind,
+    #[serde(default)]
+    pub timings: Timings, // Lock, line clear and entry delays, cycled through presets in the options menu
+    #[serde(default)]
+    pub player_name: String, // Initials last entered for a high score, offered again next time
+    #[serde(default = "default_hud_stats")]
+    pub hud_stats: Vec<StatKind>, // Live stats shown in the HUD, in StatKind::ALL order
+}
+
+/// The rules a game is played under, everything besides the mode and seed that decides how it plays out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub scoring_rule: ScoringRuleKind,
+    pub randomizer: RandomizerKind,
+    pub board_width: usize,
+    pub board_height: usize,
+    pub partial_lock_out: bool,
+    #[serde(default)]
+    pub finesse_training: bool, // Pieces placed with a finesse fault are tried again
+    #[serde(default)]
+    pub piece_set: PieceSetKind,
+    #[serde(default)]
+    pub timings: Timings,
+}
+
+// Choices the options menu cycles through for each handling setting
+const DAS_PRESETS_MS: [u32; 8] = [50, 83, 100, 117, 133, 167, 200, 267];
+const ARR_PRESETS_MS: [u32; 6] = [0, 17, 33, 50, 67, 100];
+const SOFT_DROP_FACTOR_PRESETS: [u32; 6] = [1, 5, 10, 20, 40, 80];
+const BOARD_WIDTH_PRESETS: [usize; 7] = [4, 6, 8, 10, 12, 16, 20];
+const BOARD_HEIGHT_PRESETS: [usize; 5] = [12, 16, 20, 24, 30];
+
+// Timings the options menu cycles through, named after the games they follow
+const TIMING_PRESETS: [(&str, Timings); 3] = [
+    (
+        "Modern",
+        Timings {
+            lock_delay_ms: 500,
+            line_clear_delay_ms: 0,
+            entry_delay_ms: 0,
+        },
+    ),
+    (
+        "NES",
+        Timings {
+            lock_delay_ms: 0,
+            line_clear_delay_ms: 300,
+            entry_delay_ms: 167,
+        },
+    ),
+    (
+        "TGM",
+        Timings {
+            lock_delay_ms: 500,
+            line_clear_delay_ms: 683,
+            entry_delay_ms: 500,
+        },
+    ),
+];
+
+fn default_preview_count() -> usize {
+    DEFAULT_PREVIEW_COUNT
+}
+
+fn default_das_ms() -> u32 {
+    DEFAULT_DAS_MS
+}
+
+fn default_arr_ms() -> u32 {
+    DEFAULT_ARR_MS
+}
+
+fn default_soft_drop_factor() -> u32 {
+    DEFAULT_SOFT_DROP_FACTOR
+}
+
+fn default_hud_stats() -> Vec<StatKind> {
+    vec![StatKind::Pps, StatKind::Apm, StatKind::Kpp]
+}
+
+fn default_board_width() -> usize {
+    BOARD_WIDTH
+}
+
+fn default_board_height() -> usize {
+    BOARD_HEIGHT
+}
+
+// Returns the preset after the current value, wrapping around to the first one
+fn next_preset<T: Copy + PartialOrd>(presets: &[T], current: T) -> T {
+    presets
+        .iter()
+        .copied()
+        .find(|&preset| preset > current)
+        .unwrap_or(presets[0])
 }
 
 impl Default for Config {
@@ -18,6 +149,21 @@ impl Default for Config {
             sound_enabled: true,
             volume: 0.5,
             show_grid: false,
+            randomizer: RandomizerKind::default(),
+            preview_count: DEFAULT_PREVIEW_COUNT,
+            das_ms: DEFAULT_DAS_MS,
+            arr_ms: DEFAULT_ARR_MS,
+            soft_drop_factor: DEFAULT_SOFT_DROP_FACTOR,
+            game_mode: GameModeKind::default(),
+            scoring_rules: BTreeMap::new(),
+            board_width: BOARD_WIDTH,
+            board_height: BOARD_HEIGHT,
+            partial_lock_out: false,
+            finesse_training: false,
+            piece_set: PieceSetKind::default(),
+            timings: Timings::default(),
+            player_name: String::new(),
+            hud_stats: default_hud_stats(),
         }
     }
 }
@@ -36,6 +182,121 @@ impl Config {
         }
     }
 
+    // Number of next pieces to show, kept within what the queue holds
+    #[must_use]
+    pub fn visible_previews(&self) -> usize {
+        self.preview_count.clamp(1, MAX_PREVIEW_COUNT)
+    }
+
+    // Cycles the preview count through 1..=MAX_PREVIEW_COUNT, used by the options menu
+    pub fn cycle_preview_count(&mut self) {
+        self.preview_count = self.visible_previews() % MAX_PREVIEW_COUNT + 1;
+    }
+
+    // The handling resource the game systems read, built from the saved timings
+    #[must_use]
+    pub fn handling(&self) -> Handling {
+        Handling {
+            das_ms: self.das_ms,
+            arr_ms: self.arr_ms,
+            soft_drop_factor: self.soft_drop_factor.max(1),
+        }
+    }
+
+    pub fn cycle_das(&mut self) {
+        self.das_ms = next_preset(&DAS_PRESETS_MS, self.das_ms);
+    }
+
+    pub fn cycle_arr(&mut self) {
+        self.arr_ms = next_preset(&ARR_PRESETS_MS, self.arr_ms);
+    }
+
+    pub fn cycle_soft_drop_factor(&mut self) {
+        self.soft_drop_factor = next_preset(&SOFT_DROP_FACTOR_PRESETS, self.soft_drop_factor);
+    }
+
+    // Columns and visible rows of the board, kept within the sizes the layout can draw and wide
+    // enough for every piece of the set to spawn
+    #[must_use]
+    pub fn board_size(&self) -> (usize, usize) {
+        let min_width = MIN_BOARD_WIDTH.max(self.piece_set.min_board_width());
+        (
+            self.board_width.clamp(min_width, MAX_BOARD_WIDTH),
+            self.board_height.clamp(MIN_BOARD_HEIGHT, MAX_BOARD_HEIGHT),
+        )
+    }
+
+    pub fn cycle_board_width(&mut self) {
+        self.board_width = next_preset(&BOARD_WIDTH_PRESETS, self.board_size().0);
+    }
+
+    pub fn cycle_board_height(&mut self) {
+        self.board_height = next_preset(&BOARD_HEIGHT_PRESETS, self.board_size().1);
+    }
+
+    // Shows or hides a live stat in the HUD
+    pub fn toggle_hud_stat(&mut self, kind: StatKind) {
+        if self.hud_stats.contains(&kind) {
+            self.hud_stats.retain(|&shown| shown != kind);
+        } else {
+            self.hud_stats.push(kind);
+            self.hud_stats.sort_by_key(|shown| {
+                StatKind::ALL
+                    .iter()
+                    .position(|stat| stat == shown)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+    }
+
+    // The scoring rule picked for a mode in the mode picker
+    #[must_use]
+    pub fn scoring_rule_for(&self, mode: GameModeKind) -> ScoringRuleKind {
+        self.scoring_rules
+            .get(&mode)
+            .copied()
+            .unwrap_or_else(|| mode.default_scoring_rule())
+    }
+
+    // The rules a new game of the mode is played under
+    #[must_use]
+    pub fn ruleset(&self, mode: GameModeKind) -> Ruleset {
+        let (board_width, board_height) = self.board_size();
+        Ruleset {
+            scoring_rule: self.scoring_rule_for(mode),
+            randomizer: self.randomizer,
+            board_width,
+            board_height,
+            partial_lock_out: self.partial_lock_out,
+            finesse_training: self.finesse_training,
+            piece_set: self.piece_set,
+            timings: self.timings,
+        }
+    }
+
+    // Name of the timing preset in use, or Custom for timings edited into the config file
+    #[must_use]
+    pub fn timings_name(&self) -> &'static str {
+        TIMING_PRESETS
+            .iter()
+            .find(|(_, timings)| *timings == self.timings)
+            .map_or("Custom", |(name, _)| name)
+    }
+
+    // Moves on to the next timing preset, custom timings go back to the first one
+    pub fn cycle_timings(&mut self) {
+        let next = TIMING_PRESETS
+            .iter()
+            .position(|(_, timings)| *timings == self.timings)
+            .map_or(0, |index| (index + 1) % TIMING_PRESETS.len());
+        self.timings = TIMING_PRESETS[next].1;
+    }
+
+    pub fn cycle_scoring_rule(&mut self, mode: GameModeKind) {
+        let next = self.scoring_rule_for(mode).next();
+        self.scoring_rules.insert(mode, next);
+    }
+
     pub fn save(&self) -> io::Result<()> {
         // Create config directory if it doesn't exist
         let config_dir = Self::get_config_dir()?;
@@ -66,7 +327,11 @@ impl Config {
         Ok(config)
     }
 
-    fn get_config_dir() -> io::Result<std::path::PathBuf> {
+    /// Directory holding the config file and the other saved data, such as replays
+    ///
+    /// # Errors
+    /// Fails when the home directory can't be found
+    pub fn get_config_dir() -> io::Result<std::path::PathBuf> {
         // Get home directory
         let home_dir = dirs::home_dir().ok_or_else(|| {
             io::Error::new(io::ErrorKind::NotFound, "Could not find home directory")
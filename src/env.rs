@@ -0,0 +1,197 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow precision loss when handing score differences to agents as rewards
+    clippy::cast_precision_loss
+)]
+
+use bevy_ecs::prelude::*;
+
+use crate::app::start_game;
+use crate::components::{Board, GameState, Input, Position, Tetromino, TetrominoType};
+use crate::config::{Config, Ruleset};
+use crate::mode::GameModeKind;
+use crate::systems::step_frame;
+
+/// The discrete moves an agent picks from, each a key pressed for one frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Noop,
+    Left,
+    Right,
+    SoftDrop,
+    RotateCw,
+    RotateCcw,
+    Rotate180,
+    HardDrop,
+    Hold,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::Noop,
+        Action::Left,
+        Action::Right,
+        Action::SoftDrop,
+        Action::RotateCw,
+        Action::RotateCcw,
+        Action::Rotate180,
+        Action::HardDrop,
+        Action::Hold,
+    ];
+
+    // The action at an index of the action space, for agents that pick by number
+    #[must_use]
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    #[must_use]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    // The keys the action presses
+    fn input(self) -> Input {
+        let mut input = Input::default();
+        match self {
+            Action::Noop => {}
+            Action::Left => input.left = true,
+            Action::Right => input.right = true,
+            Action::SoftDrop => input.down = true,
+            Action::RotateCw => input.rotate = true,
+            Action::RotateCcw => input.rotate_ccw = true,
+            Action::Rotate180 => input.rotate_180 = true,
+            Action::HardDrop => input.hard_drop = true,
+            Action::Hold => input.hold = true,
+        }
+        input
+    }
+}
+
+/// The piece in play, where it is and which way it's turned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivePiece {
+    pub piece: TetrominoType,
+    pub rotation: usize, // Quarter turns clockwise from the spawn state
+    pub x: i32,
+    pub y: i32, // Rows above the visible field are negative
+}
+
+/// What an agent sees of the game after each step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub width: usize,
+    pub height: usize,
+    pub board: Vec<bool>, // Occupancy of the visible field row by row from the top, width * height cells
+    pub active: Option<ActivePiece>,
+    pub queue: Vec<TetrominoType>, // Next pieces, the one dealt next first
+    pub hold: Option<TetrominoType>,
+    pub hold_used: bool, // The hold slot can't be used again until the piece locks
+}
+
+impl Observation {
+    #[must_use]
+    pub fn occupied(&self, x: usize, y: usize) -> bool {
+        self.board[y * self.width + x]
+    }
+}
+
+/// A game without a terminal or audio, stepped by an agent one action per frame through the same
+/// systems and rules as the game itself
+pub struct GameEnv {
+    world: World,
+    mode: GameModeKind,
+    ruleset: Ruleset,
+}
+
+impl Default for GameEnv {
+    fn default() -> Self {
+        let mode = GameModeKind::default();
+        Self::new(mode, Config::default().ruleset(mode))
+    }
+}
+
+impl GameEnv {
+    /// An environment playing a mode under a ruleset, its first game dealt from seed 0
+    #[must_use]
+    pub fn new(mode: GameModeKind, ruleset: Ruleset) -> Self {
+        let mut env = Self {
+            world: World::new(),
+            mode,
+            ruleset,
+        };
+        env.reset(0);
+        env
+    }
+
+    /// Starts a new game, the same seed deals the same pieces every time
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        start_game(&mut self.world, self.mode, &self.ruleset, seed);
+        self.observation()
+    }
+
+    /// Presses the action's key for a frame and runs it. The reward is the score the frame made,
+    /// and the game is done once it ends, after which stepping changes nothing.
+    pub fn step(&mut self, action: Action) -> (Observation, f32, bool) {
+        if self.is_done() {
+            return (self.observation(), 0.0, true);
+        }
+
+        let score_before = self.world.resource::<GameState>().score;
+        *self.world.resource_mut::<Input>() = action.input();
+        step_frame(&mut self.world);
+
+        let reward = self
+            .world
+            .resource::<GameState>()
+            .score
+            .saturating_sub(score_before) as f32;
+        (self.observation(), reward, self.is_done())
+    }
+
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.world.resource::<GameState>().game_over
+    }
+
+    /// The board, active piece, queue and hold as they stand
+    #[must_use]
+    pub fn observation(&self) -> Observation {
+        let board = self.world.resource::<Board>();
+        let game_state = self.world.resource::<GameState>();
+
+        let active = self.world.iter_entities().find_map(|entity| {
+            let tetromino = entity.get::<Tetromino>()?;
+            let position = entity.get::<Position>()?;
+            Some(ActivePiece {
+                piece: tetromino.tetromino_type,
+                rotation: tetromino.rotation % 4,
+                x: position.x,
+                y: position.y,
+            })
+        });
+
+        Observation {
+            width: board.width,
+            height: board.height,
+            board: (0..board.height)
+                .flat_map(|y| (0..board.width).map(move |x| board.cells[x][y].is_some()))
+                .collect(),
+            active,
+            queue: game_state.next_queue.iter().copied().collect(),
+            hold: game_state.held_tetromino,
+            hold_used: game_state.hold_used,
+        }
+    }
+
+    /// The world the game runs in, for anything the observation leaves out
+    #[must_use]
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// The world to set a position up in before stepping, for curricula and tests
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
@@ -0,0 +1,62 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use bevy_ecs::prelude::*;
+
+use crate::components::{Position, TSpin, Tetromino};
+
+/// A piece locked into the board, with how it scored
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PieceLocked {
+    pub tetromino: Tetromino,
+    pub position: Position,
+    pub lines_cleared: usize,
+    pub t_spin: TSpin,
+    pub perfect_clear: bool,
+    pub new_holes: u32, // Holes the piece left under itself
+}
+
+/// Lines completed by a piece, sent as it locks while the rows are still on the board
+#[derive(Event, Debug, Clone)]
+pub struct LinesCleared {
+    pub lines: usize,     // Buffer rows included
+    pub rows: Vec<usize>, // The visible ones among them
+    pub t_spin: TSpin,
+    pub perfect_clear: bool,
+}
+
+/// The lines a piece cleared took the game up a level
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LevelUp;
+
+/// The game ended with the stack reaching the top, `GameState::top_out` says how
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TopOut;
+
+/// A piece went into the hold slot, before it spawned or while in play
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HoldUsed;
+
+// Gives the world a queue for every game event
+pub fn register(world: &mut World) {
+    world.init_resource::<Events<PieceLocked>>();
+    world.init_resource::<Events<LinesCleared>>();
+    world.init_resource::<Events<LevelUp>>();
+    world.init_resource::<Events<TopOut>>();
+    world.init_resource::<Events<HoldUsed>>();
+}
+
+// Sends a game event, worlds driven by hand get its queue on the first one sent
+pub fn send<E: Event>(world: &mut World, event: E) {
+    world
+        .get_resource_or_insert_with(Events::<E>::default)
+        .send(event);
+}
+
+// Moves every queue on by a frame, an event is kept for the frame it's sent in and the next
+pub fn update_events(world: &mut World) {
+    world.resource_mut::<Events<PieceLocked>>().update();
+    world.resource_mut::<Events<LinesCleared>>().update();
+    world.resource_mut::<Events<LevelUp>>().update();
+    world.resource_mut::<Events<TopOut>>().update();
+    world.resource_mut::<Events<HoldUsed>>().update();
+}
@@ -0,0 +1,163 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow truncation when casting from usize to i32 since board dimensions are always small enough to fit in i32
+    clippy::cast_possible_truncation,
+    // Allow potential wrapping when casting between types as board coordinates are within reasonable ranges
+    clippy::cast_possible_wrap
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+use crate::components::{Board, Input, Position, Tetromino, TetrominoType};
+
+/// A piece that took more keys than it needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinesseFault {
+    pub tetromino_type: TetrominoType,
+    pub used: u32,
+    pub needed: u32,
+}
+
+/// Keeps count of the keys each piece takes against the fewest that would have placed it
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Finesse {
+    pub training: bool, // Pieces placed with a fault go back to the top to be tried again
+    pub faults: u32,    // Keys pressed beyond the fewest needed, over the whole game
+    pub pieces: u32,    // Pieces judged
+    pub clean_pieces: u32,
+    pub last_fault: Option<FinesseFault>,
+    presses: u32,       // Movement and rotation keys pressed for the active piece
+    soft_dropped: bool, // Soft dropped pieces may need extra keys to tuck or spin, so aren't judged
+}
+
+impl Finesse {
+    #[must_use]
+    pub fn new(training: bool) -> Self {
+        Self {
+            training,
+            ..Self::default()
+        }
+    }
+
+    // Starts counting keys afresh for a new piece
+    pub fn start_piece(&mut self) {
+        self.presses = 0;
+        self.soft_dropped = false;
+    }
+
+    // Counts the movement and rotation keys input_system acted on this frame
+    pub fn record_input(&mut self, input: &Input) {
+        let presses = [
+            input.left || input.right,
+            input.rotate || input.rotate_ccw || input.rotate_180,
+        ];
+        self.presses += presses.into_iter().filter(|&pressed| pressed).count() as u32;
+        self.soft_dropped |= input.down;
+    }
+
+    /// Judges a piece about to lock at its final spot, returning true if it took too many keys
+    pub fn judge(&mut self, board: &Board, tetromino: &Tetromino, position: Position) -> bool {
+        if self.soft_dropped {
+            return false;
+        }
+        let Some(needed) = minimal_inputs(board, tetromino, position) else {
+            return false;
+        };
+
+        self.pieces += 1;
+        if self.presses <= needed {
+            self.clean_pieces += 1;
+            return false;
+        }
+        self.faults += self.presses - needed;
+        self.last_fault = Some(FinesseFault {
+            tetromino_type: tetromino.tetromino_type,
+            used: self.presses,
+            needed,
+        });
+        true
+    }
+}
+
+// Columns and shape a piece covers once dropped, the same for rotations that look alike
+fn footprint(board: &Board, tetromino: &Tetromino, position: Position) -> Vec<(i32, i32)> {
+    let blocks: Vec<(i32, i32)> = board
+        .piece_set
+        .blocks(tetromino)
+        .into_iter()
+        .map(|(dx, dy)| (position.x + dx, position.y + dy))
+        .collect();
+    let bottom = blocks.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    let mut footprint: Vec<(i32, i32)> = blocks.into_iter().map(|(x, y)| (x, y - bottom)).collect();
+    footprint.sort_unstable();
+    footprint
+}
+
+/// The fewest keys that bring a fresh piece over the spot it locked in, on an empty board of
+/// the same size and pieces. Taps, holding to the wall and each rotation all count as one key.
+#[must_use]
+pub fn minimal_inputs(board: &Board, tetromino: &Tetromino, position: Position) -> Option<u32> {
+    let board = Board::with_piece_set(board.width, board.height, board.piece_set.clone());
+    let target = footprint(&board, tetromino, position);
+    let start = (
+        Tetromino::new(tetromino.tetromino_type),
+        Position {
+            x: ((board.width - 1) / 2) as i32,
+            y: 2,
+        },
+    );
+
+    // Breadth first over the pieces reachable with one more key each time
+    let mut seen = HashSet::from([(start.0.rotation, start.1.x, start.1.y)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some(((piece, at), keys)) = queue.pop_front() {
+        if footprint(&board, &piece, at) == target {
+            return Some(keys);
+        }
+
+        let shifted = |dx: i32| {
+            let moved = Position { x: at.x + dx, ..at };
+            board
+                .is_valid_position(moved, &piece)
+                .then_some((piece, moved))
+        };
+        let held = |dx: i32| {
+            let mut end = at;
+            while board.is_valid_position(
+                Position {
+                    x: end.x + dx,
+                    ..end
+                },
+                &piece,
+            ) {
+                end.x += dx;
+            }
+            (end != at).then_some((piece, end))
+        };
+        let rotated = |turn: fn(&mut Tetromino)| {
+            let mut turned = piece;
+            turn(&mut turned);
+            board
+                .find_kick(at, &piece, &turned)
+                .map(|(kicked, _)| (turned, kicked))
+        };
+
+        let next = [
+            shifted(-1),
+            shifted(1),
+            held(-1),
+            held(1),
+            rotated(Tetromino::rotate),
+            rotated(Tetromino::rotate_counter_clockwise),
+            rotated(Tetromino::rotate_180),
+        ];
+        for (piece, at) in next.into_iter().flatten() {
+            if seen.insert((piece.rotation, at.x, at.y)) {
+                queue.push_back(((piece, at), keys + 1));
+            }
+        }
+    }
+    None
+}
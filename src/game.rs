@@ -1,11 +1,49 @@
 #![warn(clippy::all, clippy::pedantic)]
 
-// Game board dimensions
+// Default game board dimensions, the board size is a runtime setting
 pub const BOARD_WIDTH: usize = 10;
 pub const BOARD_HEIGHT: usize = 20;
+pub const MIN_BOARD_WIDTH: usize = 4;
+pub const MAX_BOARD_WIDTH: usize = 20;
+pub const MIN_BOARD_HEIGHT: usize = 8;
+pub const MAX_BOARD_HEIGHT: usize = 40;
+pub const HIDDEN_ROWS: usize = 20; // Buffer rows above the visible field where pieces spawn
+
+// Next queue
+pub const MAX_PREVIEW_COUNT: usize = 6; // The queue always holds this many pieces, however many are shown
+pub const DEFAULT_PREVIEW_COUNT: usize = 5;
+
+// Handling defaults for held movement keys
+pub const DEFAULT_DAS_MS: u32 = 167; // Delayed Auto Shift, how long a direction is held before it repeats
+pub const DEFAULT_ARR_MS: u32 = 33; // Auto Repeat Rate, time between repeated shifts (0 shifts straight to the wall)
+pub const DEFAULT_SOFT_DROP_FACTOR: u32 = 20; // How many times faster than gravity a held soft drop falls
 
 // Game timing
-pub const COYOTE_TIME_DURATION: f32 = 0.05; // Time in seconds for coyote time (last chance to move/rotate)
+pub const FRAMES_PER_SECOND: f32 = 60.0; // Gravity is measured in cells per frame at this rate
+pub const FIXED_TIMESTEP: f32 = 1.0 / FRAMES_PER_SECOND; // Seconds simulated by each frame
+pub const MAX_GRAVITY: f32 = 20.0; // 20G, a piece falls the whole board in a single frame
+pub const DEFAULT_LOCK_DELAY_MS: u32 = 500; // How long a piece rests on the stack before locking
+pub const DEFAULT_LINE_CLEAR_DELAY_MS: u32 = 0; // How long cleared rows flash before they're removed
+pub const DEFAULT_ENTRY_DELAY_MS: u32 = 0; // Entry delay (ARE), how long the board stays still before the next piece
+pub const LINE_CLEAR_FLASH_SECONDS: f32 = 0.1; // Cleared rows switch between lit and unlit this often
+pub const MAX_LOCK_RESETS: u32 = 15; // Moves and rotations that may restart the lock delay before the piece reaches a new lowest row
+pub const RESUME_COUNTDOWN: f32 = 3.0; // Seconds of 3-2-1 countdown before play picks up after a pause
+
+// Replays
+pub const REPLAY_SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0]; // Playback speeds, slowest first
+pub const REPLAY_SEEK_SECONDS: f32 = 5.0; // How far one seek jumps
+
+// Attack, the garbage lines a clear would send in a versus game
+pub const ATTACK_LINES: [u32; 5] = [0, 0, 1, 2, 4]; // By lines cleared
+pub const ATTACK_TSPIN_LINES: [u32; 4] = [0, 2, 4, 6];
+pub const ATTACK_TSPIN_MINI_LINES: [u32; 3] = [0, 0, 1];
+pub const ATTACK_BACK_TO_BACK: u32 = 1; // Extra line for a difficult clear that continues a chain
+pub const ATTACK_PERFECT_CLEAR: u32 = 10;
+pub const ATTACK_COMBO: [u32; 12] = [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 4, 5]; // By combo, the last entry holds from then on
+
+// High scores
+pub const MAX_HIGH_SCORES: usize = 10; // Entries kept per mode and ruleset
+pub const HIGH_SCORE_NAME_LENGTH: usize = 3; // Initials typed in at game over
 
 // Basic line clear scoring (level 1 values, will be multiplied by level)
 pub const POINTS_SINGLE: u32 = 40;
@@ -21,9 +59,18 @@ pub const SOFT_DROP_POINTS: u32 = 1; // Points per cell soft dropped
 pub const HARD_DROP_POINTS: u32 = 2; // Points per cell hard dropped
 
 // T-spin bonuses
+pub const TSPIN_ZERO: u32 = 400; // T-spin without clearing any lines
 pub const TSPIN_SINGLE: u32 = 800; // T-spin with single line clear
 pub const TSPIN_DOUBLE: u32 = 1200; // T-spin with double line clear
 pub const TSPIN_TRIPLE: u32 = 1600; // T-spin with triple line clear
+pub const TSPIN_MINI_ZERO: u32 = 100; // T-spin mini without clearing any lines
+pub const TSPIN_MINI_SINGLE: u32 = 200; // T-spin mini with single line clear
+pub const TSPIN_MINI_DOUBLE: u32 = 400; // T-spin mini with double line clear
+
+// Game mode goals
+pub const SPRINT_LINES: u32 = 40; // Lines to clear as fast as possible
+pub const ULTRA_TIME_LIMIT: f32 = 120.0; // Seconds to score as much as possible in
+pub const MARATHON_LINES: u32 = 150; // Lines that complete a marathon
 
 // Level progression
 pub const LINES_PER_LEVEL: u32 = 10;
@@ -0,0 +1,206 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow undocumented errors since they're all plain file and JSON errors
+    clippy::missing_errors_doc
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::components::{Board, GameState, Timings};
+use crate::config::{Config, Ruleset};
+use crate::finesse::Finesse;
+use crate::game::{HIGH_SCORE_NAME_LENGTH, MAX_HIGH_SCORES};
+use crate::mode::{GameMode, GameModeKind, ModeResult};
+use crate::randomizer::PieceRandomizer;
+use crate::replay::unix_now;
+
+/// One finished game on the high score table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    pub lines: u32,
+    pub level: u32,
+    pub time: f32, // Seconds on the mode's clock when the game ended
+    pub date: u64, // Unix time the game ended
+}
+
+/// The best games of one mode played under one ruleset, best first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    pub mode: GameModeKind,
+    pub ruleset: Ruleset,
+    pub entries: Vec<HighScore>,
+}
+
+/// Every high score table, saved next to the config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    #[serde(default)]
+    pub tables: Vec<HighScoreTable>,
+}
+
+/// A finished game that made the table, waiting on the player's initials
+#[derive(Debug, Clone)]
+pub struct PendingHighScore {
+    pub mode: GameModeKind,
+    pub ruleset: Ruleset,
+    pub entry: HighScore,
+}
+
+impl PendingHighScore {
+    // Adds a typed letter or digit to the initials, upper cased
+    pub fn type_char(&mut self, c: char) {
+        if c.is_ascii_alphanumeric() && self.entry.name.len() < HIGH_SCORE_NAME_LENGTH {
+            self.entry.name.push(c.to_ascii_uppercase());
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.entry.name.pop();
+    }
+}
+
+// Orders two results of the mode, the better one first
+fn compare(mode: GameModeKind, a: &HighScore, b: &HighScore) -> Ordering {
+    if mode.ranks_by_time() {
+        a.time.total_cmp(&b.time)
+    } else {
+        b.score.cmp(&a.score)
+    }
+}
+
+// The rules the game in the world is being played under
+fn played_ruleset(world: &World) -> Option<Ruleset> {
+    let game_state = world.get_resource::<GameState>()?;
+    let board = world.get_resource::<Board>()?;
+    Some(Ruleset {
+        scoring_rule: game_state.scoring_rule,
+        randomizer: world.get_resource::<PieceRandomizer>()?.kind(),
+        board_width: board.width,
+        board_height: board.height,
+        partial_lock_out: game_state.partial_lock_out,
+        piece_set: game_state.piece_set,
+        timings: world.get_resource::<Timings>().copied().unwrap_or_default(),
+        finesse_training: world
+            .get_resource::<Finesse>()
+            .is_some_and(|finesse| finesse.training),
+    })
+}
+
+impl HighScores {
+    /// Loads the saved tables, starting empty when there are none or they can't be read
+    #[must_use]
+    pub fn load() -> Self {
+        let load = || -> io::Result<Self> {
+            let json = fs::read_to_string(high_scores_path()?)?;
+            Ok(serde_json::from_str(&json)?)
+        };
+        load().unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = high_scores_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    // The entries for a mode under a ruleset, best first
+    #[must_use]
+    pub fn table(&self, mode: GameModeKind, ruleset: &Ruleset) -> &[HighScore] {
+        self.tables
+            .iter()
+            .find(|table| table.mode == mode && table.ruleset == *ruleset)
+            .map_or(&[], |table| table.entries.as_slice())
+    }
+
+    // Whether the result would make the table
+    #[must_use]
+    pub fn qualifies(&self, mode: GameModeKind, ruleset: &Ruleset, entry: &HighScore) -> bool {
+        let table = self.table(mode, ruleset);
+        table.len() < MAX_HIGH_SCORES
+            || table
+                .last()
+                .is_some_and(|last| compare(mode, entry, last) == Ordering::Less)
+    }
+
+    /// Puts the result on its table and returns its place, from 0, if it stayed on
+    pub fn insert(
+        &mut self,
+        mode: GameModeKind,
+        ruleset: &Ruleset,
+        entry: HighScore,
+    ) -> Option<usize> {
+        let index = if let Some(index) = self
+            .tables
+            .iter()
+            .position(|table| table.mode == mode && table.ruleset == *ruleset)
+        {
+            index
+        } else {
+            self.tables.push(HighScoreTable {
+                mode,
+                ruleset: *ruleset,
+                entries: Vec::new(),
+            });
+            self.tables.len() - 1
+        };
+
+        // Ties go to the game that got there first
+        let entries = &mut self.tables[index].entries;
+        let place = entries
+            .iter()
+            .position(|other| compare(mode, &entry, other) == Ordering::Less)
+            .unwrap_or(entries.len());
+        entries.insert(place, entry);
+        entries.truncate(MAX_HIGH_SCORES);
+        (place < entries.len()).then_some(place)
+    }
+
+    /// The finished game in the world, if it earned a place on the table. Sprint only counts
+    /// once all its lines are cleared, and a score mode needs something on the board.
+    #[must_use]
+    pub fn candidate(&self, world: &World, name: &str) -> Option<PendingHighScore> {
+        let mode = world.get_resource::<GameMode>()?;
+        let game_state = world.get_resource::<GameState>()?;
+        if !mode.kind.is_ranked() || !game_state.game_over {
+            return None;
+        }
+        let finished = if mode.kind.ranks_by_time() {
+            mode.result == Some(ModeResult::Completed)
+        } else {
+            game_state.score > 0
+        };
+        if !finished {
+            return None;
+        }
+
+        let ruleset = played_ruleset(world)?;
+        let entry = HighScore {
+            name: name.chars().take(HIGH_SCORE_NAME_LENGTH).collect(),
+            score: game_state.score,
+            lines: game_state.lines_cleared,
+            level: game_state.level,
+            time: mode.elapsed,
+            date: unix_now(),
+        };
+        self.qualifies(mode.kind, &ruleset, &entry)
+            .then_some(PendingHighScore {
+                mode: mode.kind,
+                ruleset,
+                entry,
+            })
+    }
+}
+
+// Where the high scores are kept, next to the config file
+fn high_scores_path() -> io::Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join("highscores.json"))
+}
@@ -3,12 +3,23 @@
 pub mod app;
 pub mod components;
 pub mod config;
+pub mod env;
+pub mod events;
+pub mod finesse;
 pub mod game;
+pub mod highscores;
 pub mod menu;
 pub mod menu_types;
+pub mod mode;
 pub mod particles;
+pub mod pieces;
+pub mod randomizer;
+pub mod replay;
+pub mod scoring;
 pub mod screenshake;
 pub mod sound;
+pub mod stats;
+pub mod suspend;
 pub mod systems;
 pub mod ui;
 
@@ -16,33 +27,140 @@ pub mod ui;
 pub mod tests;
 
 use bevy_ecs::prelude::Resource;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use crate::game::FIXED_TIMESTEP;
+
+// Frames run at most to catch up after the clock jumps ahead
+pub const MAX_CATCH_UP_FRAMES: u32 = 10;
+
+/// A monotonic source of time, injectable so tests, replays and bots can drive the simulation
+pub trait Clock: Send + Sync + Debug {
+    /// Time passed since the clock started
+    fn elapsed(&self) -> Duration;
+}
+
+/// Wall clock time, used when playing
+#[derive(Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A clock that only moves when told to, clones share the same time
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut elapsed) = self.elapsed.lock() {
+            *elapsed += duration;
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn elapsed(&self) -> Duration {
+        self.elapsed
+            .lock()
+            .map_or(Duration::ZERO, |elapsed| *elapsed)
+    }
+}
+
+/// Turns clock time into fixed-length simulation frames
 #[derive(Resource, Debug, Clone)]
 pub struct Time {
+    clock: Arc<dyn Clock>,
     delta: Duration,
-    last_update: Instant,
+    last_update: Duration,
+    accumulator: Duration, // Clock time not yet simulated
+    frame: u64,            // Simulation frames run so far
 }
 
 impl Time {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock::new()))
+    }
+
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        let last_update = clock.elapsed();
         Self {
+            clock,
             delta: Duration::default(),
-            last_update: Instant::now(),
+            last_update,
+            accumulator: Duration::default(),
+            frame: 0,
         }
     }
 
+    /// Reads the clock and banks the time passed since the last update for the simulation
     pub fn update(&mut self) {
-        let now = Instant::now();
-        self.delta = now.duration_since(self.last_update);
+        let now = self.clock.elapsed();
+        self.delta = now.saturating_sub(self.last_update);
         self.last_update = now;
+
+        // After a long stall the simulation skips ahead rather than running a burst of frames
+        let max_backlog = Duration::from_secs_f32(FIXED_TIMESTEP) * MAX_CATCH_UP_FRAMES;
+        self.accumulator = (self.accumulator + self.delta).min(max_backlog);
+    }
+
+    /// Takes one frame's worth of banked time, returning false once less than a frame is left
+    pub fn step(&mut self) -> bool {
+        let step = Duration::from_secs_f32(FIXED_TIMESTEP);
+        if self.accumulator < step {
+            return false;
+        }
+        self.accumulator -= step;
+        self.frame += 1;
+        true
     }
 
     #[must_use]
     pub fn delta_seconds(&self) -> f32 {
         self.delta.as_secs_f32()
     }
+
+    #[must_use]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// How far the clock is into the next frame, from 0 to 1, for rendering between frames
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f32() / FIXED_TIMESTEP).min(1.0)
+    }
 }
 
 impl Default for Time {
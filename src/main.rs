@@ -3,12 +3,22 @@
 mod app;
 mod components;
 mod config;
+mod events;
+mod finesse;
 mod game;
+mod highscores;
 mod menu;
 mod menu_types;
+mod mode;
 mod particles;
+mod pieces;
+mod randomizer;
+mod replay;
+mod scoring;
 mod screenshake;
 mod sound;
+mod stats;
+mod suspend;
 mod systems;
 mod ui;
 
@@ -17,16 +27,23 @@ use std::os::fd::AsRawFd;
 use std::time::{Duration, Instant};
 
 use app::{App, AppResult};
-use components::{Board, GameState, Input};
+use components::{AutoShift, GameState, Input};
 use config::Config;
 use crossterm::event::KeyCode;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
 use fallingblocks::Time;
 use log::{debug, error, info};
+use mode::GameMode;
 use ratatui::{Terminal, prelude::*};
 use sound::{AudioState, SoundEffect};
 
@@ -67,17 +84,29 @@ fn main() -> AppResult<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    // Ask for key release events so held keys can be auto-repeated with DAS/ARR.
+    // Terminals without the keyboard enhancement protocol fall back to their own key repeat.
+    let key_release_supported = matches!(supports_keyboard_enhancement(), Ok(true));
+    if key_release_supported {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run it
+    // Create app and run it, the game logic runs in fixed frames on its own clock
     let tick_rate = Duration::from_millis(33); // ~30 FPS
-    let game_tick_rate = Duration::from_millis(50); // Game logic updates less often
 
     let app = App::new();
-    let res = run_app(&mut terminal, app, tick_rate, game_tick_rate);
+    let res = run_app(&mut terminal, app, tick_rate, key_release_supported);
 
     // Restore terminal
+    if key_release_supported {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -93,14 +122,83 @@ fn main() -> AppResult<()> {
     Ok(())
 }
 
+// Applies the highlighted entry of the pause menu
+fn select_pause_option(app: &mut App) {
+    if let Some(audio_state) = app.world.get_resource::<AudioState>() {
+        audio_state.play_sound(SoundEffect::Move);
+    }
+
+    match app.menu.pause_selected {
+        menu_types::PauseOption::Resume => app.world.resource_mut::<GameState>().resume(),
+        menu_types::PauseOption::Restart => app.reset(),
+        menu_types::PauseOption::Options => {
+            app.menu.options_from_pause = true;
+            app.menu.state = menu_types::MenuState::Options;
+        }
+        menu_types::PauseOption::QuitToMenu => {
+            app.save_replay();
+            app.menu.state = menu_types::MenuState::MainMenu;
+        }
+    }
+}
+
+// While a replay is being watched the keys drive playback instead of the game
+fn handle_replay_key(app: &mut App, key: event::KeyEvent) {
+    if key.kind == event::KeyEventKind::Release {
+        return;
+    }
+    let Some(player) = app.replay_player.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(' ' | 'p' | 'P') => player.toggle_pause(),
+        KeyCode::Up => player.faster(),
+        KeyCode::Down => player.slower(),
+        KeyCode::Left => player.skip(&mut app.world, false),
+        KeyCode::Right => player.skip(&mut app.world, true),
+        KeyCode::Tab if app.world.resource::<GameState>().game_over => {
+            app.show_summary = !app.show_summary;
+        }
+        KeyCode::Char('.') => {
+            // Stepping pauses playback so the frame stays on screen
+            player.paused = true;
+            player.step(&mut app.world);
+        }
+        KeyCode::Esc => {
+            app.replay_player = None;
+            app.menu.open_replays();
+        }
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// A game that made the high scores takes typed initials until they're entered or skipped
+fn handle_initials_key(app: &mut App, key: event::KeyEvent) {
+    if key.kind == event::KeyEventKind::Release {
+        return;
+    }
+    let Some(pending) = app.pending_high_score.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(c) => pending.type_char(c),
+        KeyCode::Backspace => pending.backspace(),
+        KeyCode::Enter => app.submit_high_score(),
+        KeyCode::Esc => app.pending_high_score = None,
+        _ => {}
+    }
+}
+
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut app: App,
     tick_rate: Duration,
-    game_tick_rate: Duration,
+    key_release_supported: bool,
 ) -> AppResult<()> {
     let mut last_render = Instant::now();
-    let mut last_game_tick = Instant::now();
 
     // Initialize the Time resource
     app.world.insert_resource(Time::new());
@@ -108,8 +206,10 @@ fn run_app<B: Backend>(
     app.world.insert_resource(Input::default());
     // Initialize the AudioState resource
     app.world.insert_resource(AudioState::new());
-    // Initialize CoyoteTime resource
-    app.world.insert_resource(components::CoyoteTime::default());
+    // Initialize LockDelay resource
+    app.world.insert_resource(components::LockDelay::default());
+    // Initialize the held key state used for auto-repeat
+    app.world.insert_resource(AutoShift::default());
 
     // Explicitly flush any pending input events that might be in the buffer
     while crossterm::event::poll(Duration::from_millis(0))? {
@@ -136,6 +236,17 @@ fn run_app<B: Backend>(
             if let Event::Key(key) = event::read()? {
                 debug!("Key event: {key:?}");
 
+                if app.replay_player.is_some() && app.menu.state == menu_types::MenuState::Game {
+                    handle_replay_key(&mut app, key);
+                    continue;
+                }
+
+                if app.pending_high_score.is_some() && app.menu.state == menu_types::MenuState::Game
+                {
+                    handle_initials_key(&mut app, key);
+                    continue;
+                }
+
                 // Check for key release events
                 if key.kind == event::KeyEventKind::Release {
                     // Track key releases for key-repeat prevention
@@ -144,9 +255,25 @@ fn run_app<B: Backend>(
                         input.hard_drop_released = true;
                         debug!("E key released, setting hard_drop_released = true");
                     }
+
+                    // Stop auto-repeating released movement keys
+                    let mut auto_shift = app.world.resource_mut::<AutoShift>();
+                    match key.code {
+                        KeyCode::Left => auto_shift.release_horizontal(-1),
+                        KeyCode::Right | KeyCode::Char('d') => auto_shift.release_horizontal(1),
+                        KeyCode::Down | KeyCode::Char('s') => auto_shift.release_down(),
+                        _ => (),
+                    }
                     continue; // Skip the rest of the input processing for release events
                 }
 
+                // Held keys in game are repeated by DAS/ARR, so the terminal's own repeats are dropped
+                if key.kind == event::KeyEventKind::Repeat
+                    && app.menu.state == menu_types::MenuState::Game
+                {
+                    continue;
+                }
+
                 // Handle key press events for hard drop
                 if key.code == KeyCode::Char('e') {
                     let mut input = app.world.resource_mut::<Input>();
@@ -190,19 +317,40 @@ fn run_app<B: Backend>(
                                 match app.menu.state {
                                     menu_types::MenuState::MainMenu => {
                                         match app.menu.selected_option {
+                                            menu_types::MenuOption::Continue => {
+                                                match app.continue_game() {
+                                                    Ok(()) => {
+                                                        app.menu.state =
+                                                            menu_types::MenuState::Game;
+                                                    }
+                                                    Err(err) => {
+                                                        error!("Failed to continue game: {err}");
+                                                        app.menu.selected_option =
+                                                            menu_types::MenuOption::NewGame;
+                                                        app.menu.notice = Some(format!(
+                                                            "Couldn't continue the saved game: {err}"
+                                                        ));
+                                                    }
+                                                }
+                                            }
                                             menu_types::MenuOption::NewGame => {
                                                 // Play sound effect
                                                 if let Some(mut audio_state) =
                                                     app.world.get_resource_mut::<AudioState>()
                                                 {
                                                     if audio_state.is_sound_enabled() {
-                                                        audio_state
-                                                            .play_sound(SoundEffect::LevelUp);
+                                                        audio_state.play_sound(SoundEffect::Move);
                                                     }
                                                 }
-                                                // Change state and reset app
-                                                app.menu.state = menu_types::MenuState::Game;
-                                                app.reset();
+                                                // Pick a mode first, starting from the last one played
+                                                app.menu.mode_selected = app.config.game_mode;
+                                                app.menu.state = menu_types::MenuState::ModeSelect;
+                                            }
+                                            menu_types::MenuOption::Replays => {
+                                                app.menu.open_replays();
+                                            }
+                                            menu_types::MenuOption::HighScores => {
+                                                app.menu.open_high_scores(app.config.game_mode);
                                             }
                                             menu_types::MenuOption::Options => {
                                                 // Play sound effect
@@ -215,7 +363,39 @@ fn run_app<B: Backend>(
                                                 }
                                                 app.menu.state = menu_types::MenuState::Options;
                                             }
-                                            _ => {}
+                                            menu_types::MenuOption::Quit => {} // Handled above
+                                        }
+                                    }
+                                    menu_types::MenuState::ModeSelect => {
+                                        // Play sound effect
+                                        if let Some(audio_state) =
+                                            app.world.get_resource::<AudioState>()
+                                        {
+                                            audio_state.play_sound(SoundEffect::LevelUp);
+                                        }
+                                        // Remember the mode, then change state and reset app
+                                        app.config.game_mode = app.menu.mode_selected;
+                                        app.save_config();
+                                        app.menu.state = menu_types::MenuState::Game;
+                                        app.reset();
+                                    }
+                                    menu_types::MenuState::Replays => {
+                                        let path = app
+                                            .menu
+                                            .replays
+                                            .get(app.menu.replay_selected)
+                                            .map(|entry| entry.path.clone());
+                                        if let Some(path) = path {
+                                            match app.watch_replay(&path) {
+                                                Ok(()) => {
+                                                    app.menu.state = menu_types::MenuState::Game;
+                                                }
+                                                Err(err) => {
+                                                    error!("Failed to load replay: {err}");
+                                                    app.menu.replay_message =
+                                                        Some(format!("Can't play replay: {err}"));
+                                                }
+                                            }
                                         }
                                     }
                                     menu_types::MenuState::Options => {
@@ -268,8 +448,78 @@ fn run_app<B: Backend>(
                                                     app.save_config();
                                                 }
                                             }
+                                            menu_types::OptionsOption::RandomizerCycle => {
+                                                app.config.randomizer =
+                                                    app.config.randomizer.next();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            // A new piece set takes effect from the next game
+                                            menu_types::OptionsOption::PieceSetCycle => {
+                                                app.config.piece_set = app.config.piece_set.next();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::PreviewCycle => {
+                                                app.config.cycle_preview_count();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::DasCycle => {
+                                                app.config.cycle_das();
+                                                app.apply_handling();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::ArrCycle => {
+                                                app.config.cycle_arr();
+                                                app.apply_handling();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::SoftDropCycle => {
+                                                app.config.cycle_soft_drop_factor();
+                                                app.apply_handling();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::BoardWidthCycle => {
+                                                app.config.cycle_board_width();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::BoardHeightCycle => {
+                                                app.config.cycle_board_height();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::PartialLockOutToggle => {
+                                                app.config.partial_lock_out =
+                                                    !app.config.partial_lock_out;
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            // New timings take effect from the next game
+                                            menu_types::OptionsOption::TimingsCycle => {
+                                                app.config.cycle_timings();
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::FinesseTrainingToggle => {
+                                                app.config.finesse_training =
+                                                    !app.config.finesse_training;
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
+                                            menu_types::OptionsOption::HudStats => {
+                                                let stat =
+                                                    stats::StatKind::ALL[app.menu.hud_stat_cursor];
+                                                app.config.toggle_hud_stat(stat);
+                                                // Save config after changing settings
+                                                app.save_config();
+                                            }
                                             menu_types::OptionsOption::Back => {
-                                                app.menu.state = menu_types::MenuState::MainMenu;
+                                                app.menu.leave_options();
                                             }
                                         }
                                     }
@@ -278,12 +528,43 @@ fn run_app<B: Backend>(
                             }
                         }
                         KeyCode::Esc => {
-                            // Return to main menu if in options
+                            // Return to main menu if in options, the mode picker, the replays or
+                            // the high scores
                             if app.menu.state == menu_types::MenuState::Options {
+                                app.menu.leave_options();
+                            } else if matches!(
+                                app.menu.state,
+                                menu_types::MenuState::ModeSelect
+                                    | menu_types::MenuState::Replays
+                                    | menu_types::MenuState::HighScores
+                            ) {
                                 app.menu.state = menu_types::MenuState::MainMenu;
                             }
                         }
-                        // Process arrow key left/right for volume control
+                        // Process arrow key left/right for volume control and the mode's scoring rule
+                        KeyCode::Left | KeyCode::Right
+                            if app.menu.state == menu_types::MenuState::ModeSelect =>
+                        {
+                            app.config.cycle_scoring_rule(app.menu.mode_selected);
+                            // Save config after changing settings
+                            app.save_config();
+                        }
+                        // Left/right flip through the high score tabs
+                        KeyCode::Left | KeyCode::Right
+                            if app.menu.state == menu_types::MenuState::HighScores =>
+                        {
+                            app.menu.switch_high_score_tab(key.code == KeyCode::Right);
+                        }
+                        // Left/right pick which stat the HUD Stats option toggles
+                        KeyCode::Left | KeyCode::Right
+                            if app.menu.state == menu_types::MenuState::Options
+                                && matches!(
+                                    app.menu.options_selected,
+                                    menu_types::OptionsOption::HudStats
+                                ) =>
+                        {
+                            app.menu.move_hud_stat_cursor(key.code == KeyCode::Right);
+                        }
                         KeyCode::Left => {
                             if app.menu.state == menu_types::MenuState::Options
                                 && matches!(
@@ -347,6 +628,44 @@ fn run_app<B: Backend>(
                     continue; // Skip the rest of the input processing for game controls
                 }
 
+                // Flip between a finished game's board and its stats summary
+                if is_game_over && key.code == KeyCode::Tab {
+                    app.show_summary = !app.show_summary;
+                    continue;
+                }
+
+                // Leave a finished game for the main menu
+                if is_game_over && key.code == KeyCode::Esc {
+                    app.menu.state = menu_types::MenuState::MainMenu;
+                    continue;
+                }
+
+                // The pause menu takes over the keys while it's open
+                if !is_game_over && app.world.resource::<GameState>().paused {
+                    match key.code {
+                        KeyCode::Up | KeyCode::Char('w') => {
+                            app.menu.pause_selected = app.menu.pause_selected.prev();
+                        }
+                        KeyCode::Down | KeyCode::Char('s') => {
+                            app.menu.pause_selected = app.menu.pause_selected.next();
+                        }
+                        KeyCode::Enter | KeyCode::Char(' ') => select_pause_option(&mut app),
+                        KeyCode::Esc | KeyCode::Char('p' | 'P') => {
+                            app.world.resource_mut::<GameState>().resume();
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Pause with P or Esc, forgetting any held keys
+                if !is_game_over && matches!(key.code, KeyCode::Esc | KeyCode::Char('p' | 'P')) {
+                    app.world.resource_mut::<GameState>().pause();
+                    app.menu.pause_selected = menu_types::PauseOption::Resume;
+                    app.world.insert_resource(AutoShift::default());
+                    continue;
+                }
+
                 // Handle audio control keys regardless of game state
                 let mut input = app.world.resource_mut::<Input>();
                 match key.code {
@@ -363,30 +682,18 @@ fn run_app<B: Backend>(
                     // Drop the current mutable borrow of input
                     drop(input);
 
-                    // Reset game state
-                    {
-                        let mut game_state = app.world.resource_mut::<GameState>();
-                        game_state.reset();
-                    }
-
-                    // Clear the board
-                    {
-                        let mut board = app.world.resource_mut::<Board>();
-                        board.clear();
-                    }
-
-                    // Spawn new tetromino
-                    systems::spawn_tetromino(&mut app.world);
+                    // Play the same mode again from a fresh board, clock and seed
+                    let mode = app.world.resource::<GameMode>().kind;
+                    app.start_game(mode);
 
-                    // Re-acquire input after other operations and reset it
+                    // Re-acquire input after other operations
                     let mut input = app.world.resource_mut::<Input>();
-                    *input = Input::default();
                     input.hard_drop_released = was_hard_drop_released;
                 } else if !is_game_over {
                     // Update input state for normal gameplay
                     let mut input = app.world.resource_mut::<Input>();
                     match key.code {
-                        KeyCode::Left | KeyCode::Char('a') => {
+                        KeyCode::Left => {
                             input.left = true;
                             input.right = false;
                         }
@@ -395,9 +702,12 @@ fn run_app<B: Backend>(
                             input.left = false;
                         }
                         KeyCode::Down | KeyCode::Char('s') => input.down = true,
-                        KeyCode::Up | KeyCode::Char('w' | ' ') => {
+                        KeyCode::Up | KeyCode::Char('w' | ' ' | 'x' | 'X') => {
                             input.rotate = true;
                         }
+                        KeyCode::Char('z' | 'Z') => input.rotate_ccw = true,
+                        KeyCode::Char('a' | 'A') => input.rotate_180 = true,
+                        KeyCode::Char('c' | 'C') => input.hold = true,
                         KeyCode::Char('e') => {
                             // Only set hard_drop to true if the key was previously released
                             if input.hard_drop_released {
@@ -412,6 +722,17 @@ fn run_app<B: Backend>(
                         }
                         _ => (),
                     }
+
+                    // Start tracking held movement keys for auto-repeat
+                    if key_release_supported {
+                        let mut auto_shift = app.world.resource_mut::<AutoShift>();
+                        match key.code {
+                            KeyCode::Left => auto_shift.press_horizontal(-1),
+                            KeyCode::Right | KeyCode::Char('d') => auto_shift.press_horizontal(1),
+                            KeyCode::Down | KeyCode::Char('s') => auto_shift.press_down(),
+                            _ => (),
+                        }
+                    }
                 }
 
                 // Update last key in game state
@@ -420,41 +741,39 @@ fn run_app<B: Backend>(
             }
         }
 
-        // Update game logic at a fixed rate
-        if last_game_tick.elapsed() >= game_tick_rate {
-            // First update time and get delta
-            let delta_seconds = {
-                let mut time = app.world.resource_mut::<Time>();
-                time.update();
-                time.delta_seconds()
-            };
-
-            debug!("Game tick at time: {:?}", Instant::now());
+        // Run as many fixed frames as the clock has banked, rendering interpolates between them
+        app.world.resource_mut::<Time>().update();
+        let mut frames = 0;
+        while app.world.resource_mut::<Time>().step() {
+            if app.replay_player.is_none() {
+                app.step_frame();
+            }
+            frames += 1;
+        }
 
-            // Process input first
-            systems::input_system(&mut app.world);
+        // A replay plays the banked frames at its own speed
+        if let Some(player) = app.replay_player.as_mut() {
+            frames = player.advance(&mut app.world, frames);
+        }
 
-            // Save config after input processing (captures audio hotkey changes)
-            app.save_config();
+        if frames > 0 {
+            debug!("Ran {frames} frames");
 
-            // Then update game state
-            systems::game_tick_system(&mut app.world, delta_seconds);
+            // Save config once the audio hotkeys have changed a setting
+            if app.audio_settings_changed() {
+                app.save_config();
+            }
 
             // Sync game state with app
             app.sync_game_state();
-
-            // Reset input state after processing
-            let mut input = app.world.resource_mut::<Input>();
-            *input = Input::default();
-
-            last_game_tick = Instant::now();
         }
 
         if app.should_quit {
             info!("Game quit by user");
 
-            // Save config before exiting
+            // Save config and suspend the game in progress before exiting
             app.save_config();
+            app.suspend_game();
 
             return Ok(());
         }
@@ -1,8 +1,11 @@
 use crate::app::App;
 use crate::components::{GameState, Particle, Position};
-use crate::menu_types::{Menu, MenuOption, MenuState, OptionsOption};
+use crate::menu_types::{Menu, MenuState, OptionsOption};
+use crate::mode::{GameModeKind, format_time};
 use crate::particles;
-use crate::sound::{AudioState, SoundEffect};
+use crate::replay::format_date;
+use crate::sound::AudioState;
+use crate::stats::StatKind;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
@@ -75,36 +78,44 @@ impl MenuRenderer {
 
     pub fn next_option(&mut self, menu: &mut Menu) {
         match menu.state {
-            MenuState::MainMenu => {
-                menu.selected_option = match menu.selected_option {
-                    MenuOption::NewGame => MenuOption::Options,
-                    MenuOption::Options => MenuOption::Quit,
-                    MenuOption::Quit => MenuOption::NewGame,
-                };
+            MenuState::MainMenu => menu.move_main_selection(true),
+            MenuState::ModeSelect => {
+                menu.mode_selected = menu.mode_selected.next();
             }
+            MenuState::Replays => menu.next_replay(),
             MenuState::Options => {
                 menu.options_selected = match menu.options_selected {
                     OptionsOption::MusicToggle => OptionsOption::SoundToggle,
                     OptionsOption::SoundToggle => OptionsOption::VolumeUp,
                     OptionsOption::VolumeUp => OptionsOption::VolumeDown,
                     OptionsOption::VolumeDown => OptionsOption::GridToggle,
-                    OptionsOption::GridToggle => OptionsOption::Back,
+                    OptionsOption::GridToggle => OptionsOption::RandomizerCycle,
+                    OptionsOption::RandomizerCycle => OptionsOption::PieceSetCycle,
+                    OptionsOption::PieceSetCycle => OptionsOption::PreviewCycle,
+                    OptionsOption::PreviewCycle => OptionsOption::DasCycle,
+                    OptionsOption::DasCycle => OptionsOption::ArrCycle,
+                    OptionsOption::ArrCycle => OptionsOption::SoftDropCycle,
+                    OptionsOption::SoftDropCycle => OptionsOption::BoardWidthCycle,
+                    OptionsOption::BoardWidthCycle => OptionsOption::BoardHeightCycle,
+                    OptionsOption::BoardHeightCycle => OptionsOption::PartialLockOutToggle,
+                    OptionsOption::PartialLockOutToggle => OptionsOption::TimingsCycle,
+                    OptionsOption::TimingsCycle => OptionsOption::FinesseTrainingToggle,
+                    OptionsOption::FinesseTrainingToggle => OptionsOption::HudStats,
+                    OptionsOption::HudStats => OptionsOption::Back,
                     OptionsOption::Back => OptionsOption::MusicToggle,
                 };
             }
-            MenuState::Game => {}
+            MenuState::HighScores | MenuState::Game => {}
         }
     }
 
     pub fn prev_option(&mut self, menu: &mut Menu) {
         match menu.state {
-            MenuState::MainMenu => {
-                menu.selected_option = match menu.selected_option {
-                    MenuOption::NewGame => MenuOption::Quit,
-                    MenuOption::Options => MenuOption::NewGame,
-                    MenuOption::Quit => MenuOption::Options,
-                };
+            MenuState::MainMenu => menu.move_main_selection(false),
+            MenuState::ModeSelect => {
+                menu.mode_selected = menu.mode_selected.prev();
             }
+            MenuState::Replays => menu.prev_replay(),
             MenuState::Options => {
                 menu.options_selected = match menu.options_selected {
                     OptionsOption::MusicToggle => OptionsOption::Back,
@@ -112,80 +123,22 @@ impl MenuRenderer {
                     OptionsOption::VolumeUp => OptionsOption::SoundToggle,
                     OptionsOption::VolumeDown => OptionsOption::VolumeUp,
                     OptionsOption::GridToggle => OptionsOption::VolumeDown,
-                    OptionsOption::Back => OptionsOption::GridToggle,
+                    OptionsOption::RandomizerCycle => OptionsOption::GridToggle,
+                    OptionsOption::PieceSetCycle => OptionsOption::RandomizerCycle,
+                    OptionsOption::PreviewCycle => OptionsOption::PieceSetCycle,
+                    OptionsOption::DasCycle => OptionsOption::PreviewCycle,
+                    OptionsOption::ArrCycle => OptionsOption::DasCycle,
+                    OptionsOption::SoftDropCycle => OptionsOption::ArrCycle,
+                    OptionsOption::BoardWidthCycle => OptionsOption::SoftDropCycle,
+                    OptionsOption::BoardHeightCycle => OptionsOption::BoardWidthCycle,
+                    OptionsOption::PartialLockOutToggle => OptionsOption::BoardHeightCycle,
+                    OptionsOption::TimingsCycle => OptionsOption::PartialLockOutToggle,
+                    OptionsOption::FinesseTrainingToggle => OptionsOption::TimingsCycle,
+                    OptionsOption::HudStats => OptionsOption::FinesseTrainingToggle,
+                    OptionsOption::Back => OptionsOption::HudStats,
                 };
             }
-            MenuState::Game => {}
-        }
-    }
-
-    pub fn select(&mut self, menu: &mut Menu, app: &mut App) -> bool {
-        match menu.state {
-            MenuState::MainMenu => match menu.selected_option {
-                MenuOption::NewGame => {
-                    menu.state = MenuState::Game;
-                    // Play a sound effect when starting the game
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        if audio_state.is_sound_enabled() {
-                            audio_state.play_sound(SoundEffect::LevelUp);
-                        }
-                    }
-                    app.reset();
-                    true
-                }
-                MenuOption::Options => {
-                    menu.state = MenuState::Options;
-                    // Play menu navigation sound
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        if audio_state.is_sound_enabled() {
-                            audio_state.play_sound(SoundEffect::Move);
-                        }
-                    }
-                    true
-                }
-                MenuOption::Quit => true,
-            },
-            MenuState::Options => match menu.options_selected {
-                OptionsOption::MusicToggle => {
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        audio_state.toggle_music();
-                    }
-                    true
-                }
-                OptionsOption::SoundToggle => {
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        // Toggle sound enabled state
-                        audio_state.toggle_sound();
-                    }
-                    true
-                }
-                OptionsOption::VolumeUp => {
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        let volume = audio_state.get_volume();
-                        audio_state.set_volume((volume + 0.1).min(1.0));
-                    }
-                    true
-                }
-                OptionsOption::VolumeDown => {
-                    if let Some(mut audio_state) = app.world.get_resource_mut::<AudioState>() {
-                        let volume = audio_state.get_volume();
-                        audio_state.set_volume((volume - 0.1).max(0.0));
-                    }
-                    true
-                }
-                OptionsOption::GridToggle => {
-                    if let Some(mut game_state) = app.world.get_resource_mut::<GameState>() {
-                        // Toggle grid visibility
-                        game_state.show_grid = !game_state.show_grid;
-                    }
-                    true
-                }
-                OptionsOption::Back => {
-                    menu.state = MenuState::MainMenu;
-                    true
-                }
-            },
-            MenuState::Game => false,
+            MenuState::HighScores | MenuState::Game => {}
         }
     }
 
@@ -282,7 +235,10 @@ impl MenuRenderer {
         render_title(f, chunks[0], &renderer.title_colors);
         match menu.state {
             MenuState::MainMenu => render_main_menu_options(f, chunks[1], menu),
+            MenuState::ModeSelect => render_mode_select(f, chunks[1], menu, app),
             MenuState::Options => render_options_menu(f, chunks[1], menu, app),
+            MenuState::Replays => render_replay_list(f, chunks[1], menu),
+            MenuState::HighScores => render_high_scores(f, chunks[1], menu, app),
             MenuState::Game => {}
         }
     }
@@ -319,20 +275,21 @@ fn render_title(f: &mut Frame, area: Rect, colors: &[Color]) {
 }
 
 fn render_main_menu_options(f: &mut Frame, area: Rect, menu: &Menu) {
-    let options = vec!["New Game", "Options", "Quit"];
     let mut lines = Vec::new();
-    for (i, option) in options.iter().enumerate() {
-        let style = if i
-            == match menu.selected_option {
-                MenuOption::NewGame => 0,
-                MenuOption::Options => 1,
-                MenuOption::Quit => 2,
-            } {
+    for option in menu.main_options() {
+        let style = if option == menu.selected_option {
             Style::default().add_modifier(Modifier::REVERSED)
         } else {
             Style::default()
         };
-        lines.push(Line::from(vec![Span::styled(option.to_string(), style)]));
+        lines.push(Line::from(vec![Span::styled(option.label(), style)]));
+    }
+    if let Some(notice) = &menu.notice {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            notice.clone(),
+            Style::default().fg(Color::Red),
+        )));
     }
     let text = Text::from(lines);
     let paragraph = Paragraph::new(text)
@@ -341,6 +298,223 @@ fn render_main_menu_options(f: &mut Frame, area: Rect, menu: &Menu) {
     f.render_widget(paragraph, area);
 }
 
+fn render_mode_select(f: &mut Frame, area: Rect, menu: &Menu, app: &App) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Select Mode",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for mode in GameModeKind::ALL {
+        let style = if mode == menu.mode_selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "{}  [{}]",
+                mode.name(),
+                app.config.scoring_rule_for(mode).name()
+            ),
+            style,
+        )]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        menu.mode_selected.description(),
+        Style::default().fg(Color::Gray),
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter: Start  ←/→: Scoring  Esc: Back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+fn render_replay_list(f: &mut Frame, area: Rect, menu: &Menu) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Replays",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    if menu.replays.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No replays yet, finish a game to record one",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    // Keep the selection in view when there are more replays than rows
+    let rows = usize::from(area.height.saturating_sub(6)).max(1);
+    let first = menu.replay_selected.saturating_sub(rows - 1);
+    for (i, entry) in menu.replays.iter().enumerate().skip(first).take(rows) {
+        let style = if i == menu.replay_selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![Span::styled(entry.label.clone(), style)]));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(message) = &menu.replay_message {
+        lines.push(Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "Enter: Watch  Esc: Back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+fn render_high_scores(f: &mut Frame, area: Rect, menu: &Menu, app: &App) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "High Scores",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    // One tab per ranked mode, the table shown is for the rules set in the options
+    let mut tabs = Vec::new();
+    for mode in GameModeKind::ALL
+        .into_iter()
+        .filter(|mode| mode.is_ranked())
+    {
+        let style = if mode == menu.high_score_mode {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        tabs.push(Span::styled(format!(" {} ", mode.name()), style));
+        tabs.push(Span::raw(" "));
+    }
+    lines.push(Line::from(tabs));
+
+    let ruleset = app.config.ruleset(menu.high_score_mode);
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} scoring, {} randomizer, {}x{}{}{}",
+            ruleset.scoring_rule.name(),
+            ruleset.randomizer.name(),
+            ruleset.board_width,
+            ruleset.board_height,
+            if ruleset.partial_lock_out {
+                ", partial lock out"
+            } else {
+                ""
+            },
+            if ruleset.finesse_training {
+                ", finesse training"
+            } else {
+                ""
+            }
+        ),
+        Style::default().fg(Color::Gray),
+    )));
+    lines.push(Line::from(""));
+
+    let entries = app.high_scores.table(menu.high_score_mode, &ruleset);
+    if entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No high scores yet under these rules",
+            Style::default().fg(Color::Gray),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:>2}  {:<4}  {:>8}  {:>5}  {:>5}  {:>8}  {:<16}",
+                "#", "Name", "Score", "Lines", "Level", "Time", "Date"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        lines.push(Line::from(format!(
+            "{:>2}  {:<4}  {:>8}  {:>5}  {:>5}  {:>8}  {}",
+            i + 1,
+            entry.name,
+            entry.score,
+            entry.lines,
+            entry.level,
+            format_time(entry.time),
+            format_date(entry.date)
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "←/→: Mode  Esc: Back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+// The stats the HUD can show, lit when shown, with the one Enter toggles underlined
+fn hud_stats_line<'a>(menu: &Menu, app: &App, selected: bool) -> Line<'a> {
+    let mut spans = Vec::new();
+    for (i, stat) in StatKind::ALL.into_iter().enumerate() {
+        let mut style = if app.config.hud_stats.contains(&stat) {
+            Style::default().fg(Color::LightGreen)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        if selected && i == menu.hud_stat_cursor {
+            style = style.add_modifier(Modifier::UNDERLINED | Modifier::BOLD);
+        }
+        spans.push(Span::styled(stat.name(), style));
+        spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+}
+
+// The line of the options menu an option is shown on
+fn options_line(option: &OptionsOption) -> usize {
+    match option {
+        OptionsOption::MusicToggle => 0,
+        OptionsOption::SoundToggle => 1,
+        OptionsOption::VolumeUp | OptionsOption::VolumeDown => 2,
+        OptionsOption::GridToggle => 3,
+        OptionsOption::RandomizerCycle => 4,
+        OptionsOption::PieceSetCycle => 5,
+        OptionsOption::PreviewCycle => 6,
+        OptionsOption::DasCycle => 7,
+        OptionsOption::ArrCycle => 8,
+        OptionsOption::SoftDropCycle => 9,
+        OptionsOption::BoardWidthCycle => 10,
+        OptionsOption::BoardHeightCycle => 11,
+        OptionsOption::PartialLockOutToggle => 12,
+        OptionsOption::TimingsCycle => 13,
+        OptionsOption::FinesseTrainingToggle => 14,
+        OptionsOption::HudStats => 15,
+        OptionsOption::Back => 16,
+    }
+}
+
 fn render_options_menu(f: &mut Frame, area: Rect, menu: &Menu, app: &App) {
     let mut options = Vec::new();
 
@@ -382,25 +556,67 @@ fn render_options_menu(f: &mut Frame, area: Rect, menu: &Menu, app: &App) {
         options.push("Grid: N/A".to_string());
     }
 
+    options.push(format!("Randomizer: {}", app.config.randomizer.name()));
+
+    options.push(format!("Piece Set: {}", app.config.piece_set.name()));
+
+    options.push(format!("Previews: {}", app.config.visible_previews()));
+
+    options.push(format!("DAS: {}ms", app.config.das_ms));
+
+    options.push(format!("ARR: {}ms", app.config.arr_ms));
+
+    options.push(format!("Soft Drop: {}x", app.config.soft_drop_factor));
+
+    let (board_width, board_height) = app.config.board_size();
+    options.push(format!("Board Width: {board_width}"));
+
+    options.push(format!("Board Height: {board_height}"));
+
+    options.push(format!(
+        "Partial Lock Out: {}",
+        if app.config.partial_lock_out {
+            "ON"
+        } else {
+            "OFF"
+        }
+    ));
+
+    options.push(format!("Timings: {}", app.config.timings_name()));
+
+    options.push(format!(
+        "Finesse Training: {}",
+        if app.config.finesse_training {
+            "ON"
+        } else {
+            "OFF"
+        }
+    ));
+
+    options.push("HUD Stats".to_string());
+
     options.push("Back".to_string());
 
     let mut lines = Vec::new();
     for (i, option) in options.iter().enumerate() {
-        let style = if i
-            == match menu.options_selected {
-                OptionsOption::MusicToggle => 0,
-                OptionsOption::SoundToggle => 1,
-                OptionsOption::VolumeUp => 2,
-                OptionsOption::VolumeDown => 2,
-                OptionsOption::GridToggle => 3,
-                OptionsOption::Back => 4,
-            } {
+        let style = if i == options_line(&menu.options_selected) {
             Style::default().add_modifier(Modifier::REVERSED)
         } else {
             Style::default()
         };
         lines.push(Line::from(vec![Span::styled(option.to_string(), style)]));
     }
+
+    let hud_stats_selected = matches!(menu.options_selected, OptionsOption::HudStats);
+    lines.insert(16, hud_stats_line(menu, app, hud_stats_selected));
+    if hud_stats_selected {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "←/→: Pick stat  Enter: Show/hide",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     let text = Text::from(lines);
     let paragraph = Paragraph::new(text)
         .alignment(Alignment::Center)
@@ -1,19 +1,45 @@
+use std::path::PathBuf;
+
+use crate::mode::{GameModeKind, format_time};
+use crate::replay::{format_date, list_replays};
+use crate::stats::StatKind;
+
 // Game state enum for main menu
 #[derive(Clone, PartialEq)]
 pub enum MenuState {
     MainMenu,
+    ModeSelect,
     Options,
+    Replays,
+    HighScores,
     Game,
 }
 
 // Menu option selection
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum MenuOption {
+    Continue,
     NewGame,
+    Replays,
+    HighScores,
     Options,
     Quit,
 }
 
+impl MenuOption {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuOption::Continue => "Continue",
+            MenuOption::NewGame => "New Game",
+            MenuOption::Replays => "Replays",
+            MenuOption::HighScores => "High Scores",
+            MenuOption::Options => "Options",
+            MenuOption::Quit => "Quit",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum OptionsOption {
     MusicToggle,
@@ -21,14 +47,91 @@ pub enum OptionsOption {
     VolumeUp,
     VolumeDown,
     GridToggle,
+    RandomizerCycle,
+    PieceSetCycle,
+    PreviewCycle,
+    DasCycle,
+    ArrCycle,
+    SoftDropCycle,
+    BoardWidthCycle,
+    BoardHeightCycle,
+    PartialLockOutToggle,
+    TimingsCycle,
+    FinesseTrainingToggle,
+    HudStats,
     Back,
 }
 
+// Entries of the pause menu shown over a paused game
+#[derive(Clone, Copy, PartialEq)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    Options,
+    QuitToMenu,
+}
+
+impl PauseOption {
+    pub const ALL: [PauseOption; 4] = [
+        PauseOption::Resume,
+        PauseOption::Restart,
+        PauseOption::Options,
+        PauseOption::QuitToMenu,
+    ];
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseOption::Resume => "Resume",
+            PauseOption::Restart => "Restart",
+            PauseOption::Options => "Options",
+            PauseOption::QuitToMenu => "Quit to Menu",
+        }
+    }
+
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            PauseOption::Resume => PauseOption::Restart,
+            PauseOption::Restart => PauseOption::Options,
+            PauseOption::Options => PauseOption::QuitToMenu,
+            PauseOption::QuitToMenu => PauseOption::Resume,
+        }
+    }
+
+    #[must_use]
+    pub fn prev(self) -> Self {
+        match self {
+            PauseOption::Resume => PauseOption::QuitToMenu,
+            PauseOption::Restart => PauseOption::Resume,
+            PauseOption::Options => PauseOption::Restart,
+            PauseOption::QuitToMenu => PauseOption::Options,
+        }
+    }
+}
+
+// A saved replay as listed in the replays menu
+#[derive(Clone)]
+pub struct ReplayEntry {
+    pub path: PathBuf,
+    pub label: String,
+}
+
 #[derive(Clone)]
 pub struct Menu {
     pub state: MenuState,
     pub selected_option: MenuOption,
     pub options_selected: OptionsOption,
+    pub mode_selected: GameModeKind,
+    pub pause_selected: PauseOption,
+    pub options_from_pause: bool, // Leaving options goes back to the paused game instead of the main menu
+    pub replays: Vec<ReplayEntry>,
+    pub replay_selected: usize,
+    pub replay_message: Option<String>, // Why the last replay picked couldn't be played
+    pub can_continue: bool,             // A suspended game is waiting to be continued
+    pub notice: Option<String>, // Shown under the main menu, e.g. why a game couldn't be continued
+    pub high_score_mode: GameModeKind, // Tab shown on the high scores screen
+    pub hud_stat_cursor: usize, // Stat picked on the HUD Stats option, into StatKind::ALL
 }
 
 impl Default for Menu {
@@ -37,6 +140,16 @@ impl Default for Menu {
             state: MenuState::MainMenu,
             selected_option: MenuOption::NewGame,
             options_selected: OptionsOption::Back,
+            mode_selected: GameModeKind::default(),
+            pause_selected: PauseOption::Resume,
+            options_from_pause: false,
+            replays: Vec::new(),
+            replay_selected: 0,
+            replay_message: None,
+            can_continue: false,
+            notice: None,
+            high_score_mode: GameModeKind::default(),
+            hud_stat_cursor: 0,
         }
     }
 }
@@ -45,4 +158,112 @@ impl Menu {
     pub fn new() -> Self {
         Self::default()
     }
+
+    // Entries of the main menu, Continue only shows while there's a suspended game
+    #[must_use]
+    pub fn main_options(&self) -> Vec<MenuOption> {
+        let mut options = vec![
+            MenuOption::NewGame,
+            MenuOption::Replays,
+            MenuOption::HighScores,
+            MenuOption::Options,
+            MenuOption::Quit,
+        ];
+        if self.can_continue {
+            options.insert(0, MenuOption::Continue);
+        }
+        options
+    }
+
+    // Moves the main menu selection by one entry, wrapping around at either end
+    pub fn move_main_selection(&mut self, forward: bool) {
+        let options = self.main_options();
+        let current = options
+            .iter()
+            .position(|&option| option == self.selected_option)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % options.len()
+        } else {
+            (current + options.len() - 1) % options.len()
+        };
+        self.selected_option = options[next];
+    }
+
+    // Options opened from the pause menu go back to the paused game
+    pub fn leave_options(&mut self) {
+        self.state = if self.options_from_pause {
+            MenuState::Game
+        } else {
+            MenuState::MainMenu
+        };
+        self.options_from_pause = false;
+    }
+
+    // Lists the saved replays, newest first, and shows them
+    pub fn open_replays(&mut self) {
+        self.replays = list_replays()
+            .into_iter()
+            .map(|(path, replay)| ReplayEntry {
+                label: format!(
+                    "{}  {:<8}  {:>8}  {:>7}",
+                    format_date(replay.recorded_at),
+                    replay.mode.name(),
+                    format_time(replay.duration()),
+                    replay.score
+                ),
+                path,
+            })
+            .collect();
+        self.replay_selected = 0;
+        self.replay_message = None;
+        self.state = MenuState::Replays;
+    }
+
+    pub fn next_replay(&mut self) {
+        if !self.replays.is_empty() {
+            self.replay_selected = (self.replay_selected + 1) % self.replays.len();
+        }
+    }
+
+    pub fn prev_replay(&mut self) {
+        if !self.replays.is_empty() {
+            self.replay_selected =
+                (self.replay_selected + self.replays.len() - 1) % self.replays.len();
+        }
+    }
+
+    // Shows the high scores, opening on the mode's tab or the first one if it isn't ranked
+    pub fn open_high_scores(&mut self, mode: GameModeKind) {
+        self.high_score_mode = if mode.is_ranked() {
+            mode
+        } else {
+            GameModeKind::default()
+        };
+        self.state = MenuState::HighScores;
+    }
+
+    // Moves to the next or previous ranked mode's tab
+    pub fn switch_high_score_tab(&mut self, forward: bool) {
+        loop {
+            self.high_score_mode = if forward {
+                self.high_score_mode.next()
+            } else {
+                self.high_score_mode.prev()
+            };
+            if self.high_score_mode.is_ranked() {
+                break;
+            }
+        }
+    }
+
+    // Moves the HUD Stats cursor to the next or previous stat, wrapping around
+    pub fn move_hud_stat_cursor(&mut self, forward: bool) {
+        let count = StatKind::ALL.len();
+        self.hud_stat_cursor = if forward {
+            (self.hud_stat_cursor + 1) % count
+        } else {
+            (self.hud_stat_cursor + count - 1) % count
+        };
+    }
 }
@@ -0,0 +1,193 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow truncation when turning seconds into whole minutes and centiseconds for display
+    clippy::cast_possible_truncation,
+    // Allow sign loss when turning seconds into display units since the clock never goes negative
+    clippy::cast_sign_loss
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::GameState;
+use crate::game::{MARATHON_LINES, SPRINT_LINES, ULTRA_TIME_LIMIT};
+use crate::scoring::ScoringRuleKind;
+
+/// The ways a game can be played
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum GameModeKind {
+    #[default]
+    Marathon,
+    Sprint,
+    Ultra,
+    Zen,
+}
+
+impl GameModeKind {
+    pub const ALL: [GameModeKind; 4] = [
+        GameModeKind::Marathon,
+        GameModeKind::Sprint,
+        GameModeKind::Ultra,
+        GameModeKind::Zen,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            GameModeKind::Marathon => "Marathon",
+            GameModeKind::Sprint => "Sprint",
+            GameModeKind::Ultra => "Ultra",
+            GameModeKind::Zen => "Zen",
+        }
+    }
+
+    // One line summary shown in the mode picker
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            GameModeKind::Marathon => "Clear 150 lines as the speed rises",
+            GameModeKind::Sprint => "Clear 40 lines as fast as you can",
+            GameModeKind::Ultra => "Score as much as you can in 2 minutes",
+            GameModeKind::Zen => "No timer, no top-out, just play",
+        }
+    }
+
+    /// Cycles to the next mode, used by the mode picker
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            GameModeKind::Marathon => GameModeKind::Sprint,
+            GameModeKind::Sprint => GameModeKind::Ultra,
+            GameModeKind::Ultra => GameModeKind::Zen,
+            GameModeKind::Zen => GameModeKind::Marathon,
+        }
+    }
+
+    #[must_use]
+    pub fn prev(self) -> Self {
+        match self {
+            GameModeKind::Marathon => GameModeKind::Zen,
+            GameModeKind::Sprint => GameModeKind::Marathon,
+            GameModeKind::Ultra => GameModeKind::Sprint,
+            GameModeKind::Zen => GameModeKind::Ultra,
+        }
+    }
+
+    // Scoring rule used until the player picks another one for the mode
+    #[must_use]
+    pub fn default_scoring_rule(self) -> ScoringRuleKind {
+        match self {
+            GameModeKind::Marathon | GameModeKind::Zen => ScoringRuleKind::Classic,
+            GameModeKind::Sprint | GameModeKind::Ultra => ScoringRuleKind::Guideline,
+        }
+    }
+
+    // Whether a blocked spawn ends the game, Zen clears the stack instead
+    #[must_use]
+    pub fn tops_out(self) -> bool {
+        self != GameModeKind::Zen
+    }
+
+    // Whether games of the mode go on the high score table, Zen never ends so it isn't
+    #[must_use]
+    pub fn is_ranked(self) -> bool {
+        self != GameModeKind::Zen
+    }
+
+    // Sprint ranks the fastest time, the other modes the highest score
+    #[must_use]
+    pub fn ranks_by_time(self) -> bool {
+        self == GameModeKind::Sprint
+    }
+}
+
+/// How a game came to an end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModeResult {
+    Completed,
+    ToppedOut,
+}
+
+/// The mode being played, with its clock and how the game ended
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct GameMode {
+    pub kind: GameModeKind,
+    pub elapsed: f32,
+    pub result: Option<ModeResult>,
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        Self::new(GameModeKind::default())
+    }
+}
+
+impl GameMode {
+    #[must_use]
+    pub fn new(kind: GameModeKind) -> Self {
+        Self {
+            kind,
+            elapsed: 0.0,
+            result: None,
+        }
+    }
+
+    // Lines that complete the mode, if it has a line goal
+    #[must_use]
+    pub fn line_goal(&self) -> Option<u32> {
+        match self.kind {
+            GameModeKind::Sprint => Some(SPRINT_LINES),
+            GameModeKind::Marathon => Some(MARATHON_LINES),
+            GameModeKind::Ultra | GameModeKind::Zen => None,
+        }
+    }
+
+    // Seconds left on the clock, if the mode has a time limit
+    #[must_use]
+    pub fn time_remaining(&self) -> Option<f32> {
+        match self.kind {
+            GameModeKind::Ultra => Some((ULTRA_TIME_LIMIT - self.elapsed).max(0.0)),
+            _ => None,
+        }
+    }
+
+    /// Advances the clock and returns true once the mode's goal has been reached
+    pub fn update(&mut self, game_state: &GameState, delta_seconds: f32) -> bool {
+        if self.result.is_some() {
+            return false;
+        }
+
+        self.elapsed += delta_seconds;
+
+        let completed = match self.kind {
+            GameModeKind::Sprint | GameModeKind::Marathon => self
+                .line_goal()
+                .is_some_and(|goal| game_state.lines_cleared >= goal),
+            GameModeKind::Ultra => self.elapsed >= ULTRA_TIME_LIMIT,
+            GameModeKind::Zen => false,
+        };
+
+        if completed {
+            // The clock stops exactly at the limit rather than a tick past it
+            if self.kind == GameModeKind::Ultra {
+                self.elapsed = ULTRA_TIME_LIMIT;
+            }
+            self.result = Some(ModeResult::Completed);
+        }
+        completed
+    }
+}
+
+/// Formats seconds as m:ss.cc for the HUD and results screen
+#[must_use]
+pub fn format_time(seconds: f32) -> String {
+    let centiseconds = (seconds.max(0.0) * 100.0) as u32;
+    format!(
+        "{}:{:02}.{:02}",
+        centiseconds / 6000,
+        centiseconds / 100 % 60,
+        centiseconds % 100
+    )
+}
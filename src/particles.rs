@@ -12,16 +12,29 @@
     clippy::items_after_statements
 )]
 
+use bevy_ecs::event::EventCursor;
 use bevy_ecs::prelude::*;
 use log::{debug, trace};
 use ratatui::style::Color;
 
-use crate::components::{Particle, Position, Tetromino};
+use crate::components::{Board, Particle, Position, Tetromino};
+use crate::events::{LinesCleared, PieceLocked};
+use crate::randomizer::GameRng;
 use crate::screenshake;
 
+// Blocks and color of a piece, from the board's piece set
+fn piece_look(world: &World, tetromino: &Tetromino) -> (Vec<(i32, i32)>, Color) {
+    let piece_set = &world.resource::<Board>().piece_set;
+    (
+        piece_set.blocks(tetromino),
+        piece_set.color(tetromino.tetromino_type),
+    )
+}
+
 pub fn spawn_lock_particles(world: &mut World, position: Position, tetromino: &Tetromino) {
-    // Clear any existing coyote time particles first
-    clear_coyote_time_particles(world);
+    let mut rng = effect_rng(world);
+    // Clear any existing lock delay particles first
+    clear_lock_delay_particles(world);
 
     debug!(
         "Spawning lock particles at position ({}, {})",
@@ -29,8 +42,7 @@ pub fn spawn_lock_particles(world: &mut World, position: Position, tetromino: &T
     );
 
     // Get tetromino blocks to spawn particles at each block position
-    let blocks = tetromino.get_blocks();
-    let color = tetromino.tetromino_type.get_color();
+    let (blocks, color) = piece_look(world, tetromino);
 
     const PARTICLES_PER_BLOCK: usize = 12;
 
@@ -43,8 +55,8 @@ pub fn spawn_lock_particles(world: &mut World, position: Position, tetromino: &T
 
         // Create multiple particles per block
         for _ in 0..PARTICLES_PER_BLOCK {
-            let angle = fastrand::f32() * std::f32::consts::PI * 2.0;
-            let speed = fastrand::f32() * 3.0 + 2.0; // Speed between 2 and 5
+            let angle = rng.f32() * std::f32::consts::PI * 2.0;
+            let speed = rng.f32() * 3.0 + 2.0; // Speed between 2 and 5
             let vx = angle.cos() * speed;
             let vy = angle.sin() * speed;
 
@@ -53,8 +65,8 @@ pub fn spawn_lock_particles(world: &mut World, position: Position, tetromino: &T
                 block_pos,
                 (vx, vy),
                 color,
-                fastrand::f32() * 1.2 + 0.3, // lifetime: 0.3 to 1.5 seconds (increased)
-                fastrand::f32() * 1.2 + 0.4, // size: 0.4 to 1.6 (increased)
+                rng.f32() * 1.2 + 0.3, // lifetime: 0.3 to 1.5 seconds (increased)
+                rng.f32() * 1.2 + 0.4, // size: 0.4 to 1.6 (increased)
             );
         }
     }
@@ -64,11 +76,11 @@ pub fn spawn_lock_particles(world: &mut World, position: Position, tetromino: &T
 }
 
 pub fn spawn_rotation_particles(world: &mut World, position: Position, tetromino: &Tetromino) {
+    let mut rng = effect_rng(world);
     trace!("Spawning rotation particles");
 
     // Get tetromino blocks to spawn particles at each block position
-    let blocks = tetromino.get_blocks();
-    let color = tetromino.tetromino_type.get_color();
+    let (blocks, color) = piece_look(world, tetromino);
 
     const PARTICLES_PER_BLOCK: usize = 3;
 
@@ -82,22 +94,23 @@ pub fn spawn_rotation_particles(world: &mut World, position: Position, tetromino
         // Create multiple particles per block
         for _ in 0..PARTICLES_PER_BLOCK {
             // Random velocity in all directions
-            let vx = (fastrand::f32() - 0.5) * 2.0;
-            let vy = (fastrand::f32() - 0.5) * 2.0;
+            let vx = (rng.f32() - 0.5) * 2.0;
+            let vy = (rng.f32() - 0.5) * 2.0;
 
             spawn_particle(
                 world,
                 block_pos,
                 (vx, vy),
                 color,
-                fastrand::f32() * 0.4 + 0.1, // lifetime: 0.1 to 0.5 seconds
-                fastrand::f32() * 0.6 + 0.2,
+                rng.f32() * 0.4 + 0.1, // lifetime: 0.1 to 0.5 seconds
+                rng.f32() * 0.6 + 0.2,
             ); // size: 0.2 to 0.8
         }
     }
 }
 
-pub fn spawn_coyote_time_particles(world: &mut World, position: Position, tetromino: &Tetromino) {
+pub fn spawn_lock_delay_particles(world: &mut World, position: Position, tetromino: &Tetromino) {
+    let mut rng = effect_rng(world);
     // Only spawn particles if we haven't already spawned them for this position
     let already_has_particles = world
         .query::<&Particle>()
@@ -109,12 +122,12 @@ pub fn spawn_coyote_time_particles(world: &mut World, position: Position, tetrom
     }
 
     debug!(
-        "Spawning coyote time particles at position ({}, {})",
+        "Spawning lock delay particles at position ({}, {})",
         position.x, position.y
     );
 
     // Get tetromino blocks to spawn particles at each block position
-    let blocks = tetromino.get_blocks();
+    let (blocks, _) = piece_look(world, tetromino);
 
     const PARTICLES_PER_BLOCK: usize = 12;
 
@@ -128,16 +141,16 @@ pub fn spawn_coyote_time_particles(world: &mut World, position: Position, tetrom
         // Create multiple particles per block
         for _ in 0..PARTICLES_PER_BLOCK {
             // Random velocity with a more scattered pattern
-            let vx = (fastrand::f32() - 0.5) * 5.0;
-            let vy = (fastrand::f32() - 0.5) * 5.0;
+            let vx = (rng.f32() - 0.5) * 5.0;
+            let vy = (rng.f32() - 0.5) * 5.0;
 
             spawn_particle(
                 world,
                 block_pos,
                 (vx, vy),
                 Color::White,
-                fastrand::f32() * 0.5 + 0.2, // lifetime: 0.2 to 0.7 seconds
-                fastrand::f32() * 0.9 + 0.3,
+                rng.f32() * 0.5 + 0.2, // lifetime: 0.2 to 0.7 seconds
+                rng.f32() * 0.9 + 0.3,
             ); // size: 0.3 to 1.2
         }
     }
@@ -147,6 +160,7 @@ pub fn spawn_coyote_time_particles(world: &mut World, position: Position, tetrom
 }
 
 pub fn spawn_perfect_clear_particles(world: &mut World, board_width: usize, board_height: usize) {
+    let mut rng = effect_rng(world);
     // Create a burst of particles across the entire bottom of the board
     for x in 0..board_width {
         let particle_pos = Position {
@@ -156,16 +170,16 @@ pub fn spawn_perfect_clear_particles(world: &mut World, board_width: usize, boar
 
         // Spawn extra particles for impressive clears
         for _ in 0..20 {
-            let vx = (fastrand::f32() - 0.5) * 10.0;
-            let vy = (fastrand::f32() - 0.8) * 10.0; // Bias upward
+            let vx = (rng.f32() - 0.5) * 10.0;
+            let vy = (rng.f32() - 0.8) * 10.0; // Bias upward
 
             spawn_particle(
                 world,
                 particle_pos,
                 (vx, vy),
                 Color::Yellow,
-                fastrand::f32() * 1.2 + 0.5, // lifetime: 0.5 to 1.7 seconds
-                fastrand::f32() * 1.5 + 0.5,
+                rng.f32() * 1.2 + 0.5, // lifetime: 0.5 to 1.7 seconds
+                rng.f32() * 1.5 + 0.5,
             ); // size: 0.5 to 2.0
         }
     }
@@ -176,6 +190,7 @@ pub fn spawn_perfect_clear_particles(world: &mut World, board_width: usize, boar
 
 /// Spawns particles for line clear effect
 pub fn spawn_line_clear_particles(world: &mut World, board_width: usize, lines: &[usize]) {
+    let mut rng = effect_rng(world);
     debug!("Spawning line clear particles for {} lines", lines.len());
 
     // Create particles along each cleared line
@@ -200,16 +215,16 @@ pub fn spawn_line_clear_particles(world: &mut World, board_width: usize, lines:
             // Create multiple particles per cell
             for _ in 0..particles_per_cell {
                 // Horizontal bias for velocity
-                let vx = (fastrand::f32() - 0.5) * 8.0;
-                let vy = (fastrand::f32() - 0.5) * 3.0; // Less vertical movement
+                let vx = (rng.f32() - 0.5) * 8.0;
+                let vy = (rng.f32() - 0.5) * 3.0; // Less vertical movement
 
                 spawn_particle(
                     world,
                     particle_pos,
                     (vx, vy),
                     color,
-                    fastrand::f32() * 0.7 + 0.3, // lifetime: 0.3 to 1.0 seconds
-                    fastrand::f32() * 0.6 + 0.3, // size: 0.3 to 0.9
+                    rng.f32() * 0.7 + 0.3, // lifetime: 0.3 to 1.0 seconds
+                    rng.f32() * 0.6 + 0.3, // size: 0.3 to 0.9
                 );
             }
         }
@@ -219,6 +234,39 @@ pub fn spawn_line_clear_particles(world: &mut World, board_width: usize, lines:
     screenshake::trigger_line_clear_shake(world, lines.len());
 }
 
+// Bursts of particles for the pieces locked and the lines cleared this frame
+pub fn particle_system(
+    world: &mut World,
+    mut locked: Local<EventCursor<PieceLocked>>,
+    mut cleared: Local<EventCursor<LinesCleared>>,
+) {
+    let locked: Vec<PieceLocked> = locked
+        .read(world.resource::<Events<PieceLocked>>())
+        .copied()
+        .collect();
+    let cleared: Vec<LinesCleared> = cleared
+        .read(world.resource::<Events<LinesCleared>>())
+        .cloned()
+        .collect();
+
+    for lock in locked {
+        spawn_lock_particles(world, lock.position, &lock.tetromino);
+    }
+
+    for clear in cleared {
+        let (width, height) = {
+            let board = world.resource::<Board>();
+            (board.width, board.height)
+        };
+        spawn_line_clear_particles(world, width, &clear.rows);
+
+        // Spawn special particles for perfect clears
+        if clear.perfect_clear {
+            spawn_perfect_clear_particles(world, width, height);
+        }
+    }
+}
+
 pub fn update_particles(world: &mut World, delta_seconds: f32) {
     // First update all particle lifetimes and collect entities to despawn
     let mut entities_to_despawn = Vec::new();
@@ -272,6 +320,11 @@ pub fn update_particles(world: &mut World, delta_seconds: f32) {
     screenshake::update_screen_shake(world, delta_seconds);
 }
 
+// Independent generator for a burst of particles, split off the game's seeded RNG
+fn effect_rng(world: &mut World) -> fastrand::Rng {
+    world.get_resource_or_insert_with(GameRng::default).fork()
+}
+
 // Helper function to spawn a single particle
 fn spawn_particle(
     world: &mut World,
@@ -283,7 +336,8 @@ fn spawn_particle(
 ) {
     // Distribute particle sizes more evenly across the whole range
     // Make sure to preserve the base size while adding more variance
-    let adjusted_size = size * (0.7 + fastrand::f32() * 0.6);
+    let jitter = world.get_resource_or_insert_with(GameRng::default).f32();
+    let adjusted_size = size * (0.7 + jitter * 0.6);
 
     world.spawn(Particle {
         position,
@@ -294,12 +348,12 @@ fn spawn_particle(
     });
 }
 
-// Helper function to clear coyote time particles
-fn clear_coyote_time_particles(world: &mut World) {
+// Helper function to clear lock delay particles
+fn clear_lock_delay_particles(world: &mut World) {
     let particles_to_remove: Vec<Entity> = world
         .query::<(Entity, &Particle)>()
         .iter(world)
-        .filter(|(_, p)| p.color == Color::White) // Coyote time particles are white
+        .filter(|(_, p)| p.color == Color::White) // Lock delay particles are white
         .map(|(e, _)| e)
         .collect();
 
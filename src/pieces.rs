@@ -0,0 +1,288 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow undocumented errors since they're all plain file and JSON errors
+    clippy::missing_errors_doc,
+    // Allow truncation when indexing pieces with a u8, sets are checked to hold no more than 256
+    clippy::cast_possible_truncation
+)]
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::components::{Tetromino, TetrominoType};
+use crate::config::Config;
+use crate::game::MAX_BOARD_WIDTH;
+
+// The built in sets, in the same format as a custom set file
+static CLASSIC: LazyLock<PieceSet> =
+    LazyLock::new(|| built_in(include_str!("../assets/pieces/classic.json")));
+static PENTOMINO: LazyLock<PieceSet> =
+    LazyLock::new(|| built_in(include_str!("../assets/pieces/pentomino.json")));
+static MINI: LazyLock<PieceSet> =
+    LazyLock::new(|| built_in(include_str!("../assets/pieces/mini.json")));
+
+fn built_in(json: &str) -> PieceSet {
+    PieceSet::from_json(json).expect("built in piece sets are valid")
+}
+
+fn default_opener() -> bool {
+    true
+}
+
+/// One piece of a set: its shape in each rotation state, how it kicks and how it's drawn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PieceDef {
+    pub name: String,
+    #[serde(with = "color_name")]
+    pub color: Color, // A color name such as "cyan", or "#rrggbb"
+    pub cells: [Vec<(i32, i32)>; 4], // Blocks for rotation states 0, R, 2 and L, relative to the pivot (y grows downwards)
+    #[serde(default)]
+    pub kicks: [Vec<(i32, i32)>; 4], // SRS offsets for each rotation state, left empty for a piece that never kicks
+    #[serde(default)]
+    pub t_spin: bool, // Spins are judged with the T-spin 3-corner rule
+    #[serde(default = "default_opener")]
+    pub opener: bool, // The TGM randomizer may deal it first, false for pieces that force an overhang
+}
+
+/// The pieces a game is played with. Pieces are told apart by their index in the set, and the
+/// first one is the long bar the I drought stat follows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PieceSet {
+    pub name: String,
+    pub pieces: Vec<PieceDef>,
+    #[serde(default)]
+    pub tgm_history: Vec<String>, // Names of the pieces the TGM randomizer's history starts out with
+}
+
+impl Default for PieceSet {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl PieceSet {
+    #[must_use]
+    pub fn classic() -> Self {
+        CLASSIC.clone()
+    }
+
+    #[must_use]
+    pub fn pentomino() -> Self {
+        PENTOMINO.clone()
+    }
+
+    #[must_use]
+    pub fn mini() -> Self {
+        MINI.clone()
+    }
+
+    /// Reads a set from its JSON, turning away sets the game couldn't play with
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let set: Self = serde_json::from_str(json)?;
+        set.validate()?;
+        Ok(set)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Checks the set has pieces, every rotation state has cells and the kicks line up
+    pub fn validate(&self) -> io::Result<()> {
+        let invalid = |reason: String| Err(io::Error::new(io::ErrorKind::InvalidData, reason));
+
+        if self.pieces.is_empty() || self.pieces.len() > usize::from(u8::MAX) + 1 {
+            return invalid(format!(
+                "a set holds 1 to 256 pieces, not {}",
+                self.pieces.len()
+            ));
+        }
+        for piece in &self.pieces {
+            if piece.cells.iter().any(Vec::is_empty) {
+                return invalid(format!("{} has a rotation state with no cells", piece.name));
+            }
+            let kicks = piece.kicks[0].len();
+            if piece.kicks.iter().any(|state| state.len() != kicks) {
+                return invalid(format!(
+                    "{} needs the same number of kicks in every rotation state",
+                    piece.name
+                ));
+            }
+        }
+        if let Some(name) = self
+            .tgm_history
+            .iter()
+            .find(|name| !self.pieces.iter().any(|piece| piece.name == **name))
+        {
+            return invalid(format!(
+                "the TGM history names {name}, which isn't in the set"
+            ));
+        }
+        if self.min_board_width() > MAX_BOARD_WIDTH {
+            return invalid(format!(
+                "a piece needs a board wider than {MAX_BOARD_WIDTH} columns to spawn"
+            ));
+        }
+        Ok(())
+    }
+
+    /// The narrowest board every piece of the set spawns on, centered the way pieces are dealt.
+    /// Sets needing more than the widest board the layout draws get `usize::MAX`.
+    #[must_use]
+    pub fn min_board_width(&self) -> usize {
+        (1..=MAX_BOARD_WIDTH)
+            .find(|&width: &usize| {
+                let spawn_x = i32::try_from((width - 1) / 2).unwrap_or(i32::MAX);
+                self.pieces
+                    .iter()
+                    .flat_map(|piece| &piece.cells[0])
+                    .all(|(dx, _)| usize::try_from(spawn_x + dx).is_ok_and(|x| x < width))
+            })
+            .unwrap_or(usize::MAX)
+    }
+
+    // Every piece of the set, in order
+    pub fn types(&self) -> impl Iterator<Item = TetrominoType> + use<> {
+        (0..self.pieces.len()).map(|index| TetrominoType(index as u8))
+    }
+
+    #[must_use]
+    pub fn contains(&self, tetromino_type: TetrominoType) -> bool {
+        usize::from(tetromino_type.0) < self.pieces.len()
+    }
+
+    #[must_use]
+    pub fn piece(&self, tetromino_type: TetrominoType) -> &PieceDef {
+        &self.pieces[usize::from(tetromino_type.0)]
+    }
+
+    #[must_use]
+    pub fn name(&self, tetromino_type: TetrominoType) -> &str {
+        &self.piece(tetromino_type).name
+    }
+
+    #[must_use]
+    pub fn color(&self, tetromino_type: TetrominoType) -> Color {
+        self.piece(tetromino_type).color
+    }
+
+    /// Blocks of a piece in its current rotation, relative to its position
+    #[must_use]
+    pub fn blocks(&self, tetromino: &Tetromino) -> Vec<(i32, i32)> {
+        self.piece(tetromino.tetromino_type).cells[tetromino.rotation % 4].clone()
+    }
+
+    /// Returns the kick translations to try, in order, when rotating `from` into `to`.
+    /// Each is the offset of the old state less the offset of the new one.
+    #[must_use]
+    pub fn kick_tests(&self, from: &Tetromino, to: &Tetromino) -> Vec<(i32, i32)> {
+        let kicks = &self.piece(from.tetromino_type).kicks;
+        if kicks[0].is_empty() {
+            return vec![(0, 0)];
+        }
+
+        kicks[from.rotation % 4]
+            .iter()
+            .zip(&kicks[to.rotation % 4])
+            .map(|((from_x, from_y), (to_x, to_y))| (from_x - to_x, from_y - to_y))
+            .collect()
+    }
+
+    // The pieces the TGM randomizer may open with
+    #[must_use]
+    pub fn openers(&self) -> Vec<TetrominoType> {
+        self.types()
+            .filter(|&tetromino_type| self.piece(tetromino_type).opener)
+            .collect()
+    }
+
+    // The pieces the TGM randomizer's history starts out with
+    #[must_use]
+    pub fn tgm_history(&self) -> Vec<TetrominoType> {
+        self.tgm_history
+            .iter()
+            .filter_map(|name| self.types().find(|&piece| self.name(piece) == name))
+            .collect()
+    }
+}
+
+/// The piece sets a game can be played with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PieceSetKind {
+    #[default]
+    Classic,
+    Pentomino,
+    Mini,
+    Custom, // Read from pieces.json next to the config file
+}
+
+impl PieceSetKind {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceSetKind::Classic => "Classic",
+            PieceSetKind::Pentomino => "Pentomino",
+            PieceSetKind::Mini => "Mini",
+            PieceSetKind::Custom => "Custom",
+        }
+    }
+
+    /// Cycles to the next piece set, used by the options menu
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            PieceSetKind::Classic => PieceSetKind::Pentomino,
+            PieceSetKind::Pentomino => PieceSetKind::Mini,
+            PieceSetKind::Mini => PieceSetKind::Custom,
+            PieceSetKind::Custom => PieceSetKind::Classic,
+        }
+    }
+
+    /// The set's pieces, only the custom set can fail to load
+    pub fn load(self) -> io::Result<PieceSet> {
+        match self {
+            PieceSetKind::Classic => Ok(PieceSet::classic()),
+            PieceSetKind::Pentomino => Ok(PieceSet::pentomino()),
+            PieceSetKind::Mini => Ok(PieceSet::mini()),
+            PieceSetKind::Custom => PieceSet::load(&custom_piece_set_path()?),
+        }
+    }
+
+    // The narrowest board the set plays on. The custom set isn't read for it, its file is only
+    // loaded as a game starts, which widens the board then if need be.
+    #[must_use]
+    pub fn min_board_width(self) -> usize {
+        match self {
+            PieceSetKind::Classic => CLASSIC.min_board_width(),
+            PieceSetKind::Pentomino => PENTOMINO.min_board_width(),
+            PieceSetKind::Mini => MINI.min_board_width(),
+            PieceSetKind::Custom => 1,
+        }
+    }
+}
+
+// Where the custom piece set is read from, next to the config file
+fn custom_piece_set_path() -> io::Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join("pieces.json"))
+}
+
+// Colors are saved by name, the way ratatui prints and parses them
+mod color_name {
+    use ratatui::style::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // serde's `with` hands over a reference
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(color)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
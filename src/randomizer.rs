@@ -0,0 +1,341 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::TetrominoType;
+use crate::pieces::PieceSet;
+
+// Number of rolls the TGM generator makes to avoid a recently dealt piece
+const TGM_HISTORY_ROLLS: usize = 6;
+// Number of recently dealt pieces the TGM generator tries to avoid
+const TGM_HISTORY_LENGTH: usize = 4;
+// Mixed into a game's seed for its GameRng, so it doesn't draw the same numbers as the pieces
+const GAME_RNG_SEED_MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A source of upcoming pieces
+pub trait Randomizer: Send + Sync {
+    /// Draws the next piece to be dealt
+    fn next_piece(&mut self) -> TetrominoType;
+
+    /// Captures where the generator is, so a suspended game deals on from the same point
+    fn save_state(&self) -> GeneratorState;
+
+    /// Picks up from a state captured by `save_state`
+    fn load_state(&mut self, state: &GeneratorState);
+}
+
+/// A generator's RNG along with whatever pieces it remembers, its bag or its history
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GeneratorState {
+    pub rng: u64,
+    #[serde(default)]
+    pub pieces: Vec<TetrominoType>,
+    #[serde(default)]
+    pub first_piece: bool,
+}
+
+/// Memoryless generator, every piece is an independent uniform draw
+pub struct RandomGenerator {
+    rng: fastrand::Rng,
+    pieces: Vec<TetrominoType>,
+}
+
+impl RandomGenerator {
+    #[must_use]
+    pub fn new(seed: u64, piece_set: &PieceSet) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            pieces: piece_set.types().collect(),
+        }
+    }
+}
+
+impl Randomizer for RandomGenerator {
+    fn next_piece(&mut self) -> TetrominoType {
+        self.pieces[self.rng.usize(..self.pieces.len())]
+    }
+
+    fn save_state(&self) -> GeneratorState {
+        GeneratorState {
+            rng: self.rng.get_seed(),
+            ..GeneratorState::default()
+        }
+    }
+
+    fn load_state(&mut self, state: &GeneratorState) {
+        self.rng.seed(state.rng);
+    }
+}
+
+/// Bag generator, deals a shuffled bag holding `copies` of every piece before refilling
+pub struct BagGenerator {
+    rng: fastrand::Rng,
+    copies: usize,
+    pieces: Vec<TetrominoType>,
+    bag: Vec<TetrominoType>,
+}
+
+impl BagGenerator {
+    #[must_use]
+    pub fn new(seed: u64, copies: usize, piece_set: &PieceSet) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            copies: copies.max(1),
+            pieces: piece_set.types().collect(),
+            bag: Vec::new(),
+        }
+    }
+
+    fn refill(&mut self) {
+        for _ in 0..self.copies {
+            self.bag.extend_from_slice(&self.pieces);
+        }
+        self.rng.shuffle(&mut self.bag);
+    }
+}
+
+impl Randomizer for BagGenerator {
+    fn next_piece(&mut self) -> TetrominoType {
+        if self.bag.is_empty() {
+            self.refill();
+        }
+
+        // The bag is shuffled, so dealing from the back is as good as from the front
+        self.bag.pop().unwrap_or(self.pieces[0])
+    }
+
+    fn save_state(&self) -> GeneratorState {
+        GeneratorState {
+            rng: self.rng.get_seed(),
+            pieces: self.bag.clone(),
+            first_piece: false,
+        }
+    }
+
+    fn load_state(&mut self, state: &GeneratorState) {
+        self.rng.seed(state.rng);
+        self.bag.clone_from(&state.pieces);
+    }
+}
+
+/// TGM style generator, rerolls a few times to avoid the last four pieces dealt
+pub struct TgmGenerator {
+    rng: fastrand::Rng,
+    pieces: Vec<TetrominoType>,
+    openers: Vec<TetrominoType>,
+    history: Vec<TetrominoType>, // The last pieces dealt, oldest first
+    first_piece: bool,
+}
+
+impl TgmGenerator {
+    #[must_use]
+    pub fn new(seed: u64, piece_set: &PieceSet) -> Self {
+        let pieces: Vec<TetrominoType> = piece_set.types().collect();
+        let openers = piece_set.openers();
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            // A set where every piece forces an overhang opens with any of them
+            openers: if openers.is_empty() {
+                pieces.clone()
+            } else {
+                openers
+            },
+            pieces,
+            // The classic history starts out full of snakes so they're unlikely early on
+            history: piece_set.tgm_history(),
+            first_piece: true,
+        }
+    }
+
+    fn roll(&mut self) -> TetrominoType {
+        self.pieces[self.rng.usize(..self.pieces.len())]
+    }
+}
+
+impl Randomizer for TgmGenerator {
+    fn next_piece(&mut self) -> TetrominoType {
+        let piece = if self.first_piece {
+            // Never open with a piece that forces an overhang
+            self.first_piece = false;
+            self.openers[self.rng.usize(..self.openers.len())]
+        } else {
+            let mut piece = self.roll();
+            for _ in 1..TGM_HISTORY_ROLLS {
+                if !self.history.contains(&piece) {
+                    break;
+                }
+                piece = self.roll();
+            }
+            piece
+        };
+
+        self.history.push(piece);
+        if self.history.len() > TGM_HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+        piece
+    }
+
+    fn save_state(&self) -> GeneratorState {
+        GeneratorState {
+            rng: self.rng.get_seed(),
+            pieces: self.history.clone(),
+            first_piece: self.first_piece,
+        }
+    }
+
+    fn load_state(&mut self, state: &GeneratorState) {
+        self.rng.seed(state.rng);
+        self.history.clone_from(&state.pieces);
+        self.history.truncate(TGM_HISTORY_LENGTH);
+        self.first_piece = state.first_piece;
+    }
+}
+
+/// The randomizers a game can be configured with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RandomizerKind {
+    #[default]
+    SevenBag,
+    FourteenBag,
+    Tgm,
+    Random,
+}
+
+impl RandomizerKind {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            RandomizerKind::SevenBag => "7-Bag",
+            RandomizerKind::FourteenBag => "14-Bag",
+            RandomizerKind::Tgm => "TGM",
+            RandomizerKind::Random => "Random",
+        }
+    }
+
+    /// Cycles to the next randomizer, used by the options menu
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            RandomizerKind::SevenBag => RandomizerKind::FourteenBag,
+            RandomizerKind::FourteenBag => RandomizerKind::Tgm,
+            RandomizerKind::Tgm => RandomizerKind::Random,
+            RandomizerKind::Random => RandomizerKind::SevenBag,
+        }
+    }
+
+    /// A generator dealing from the piece set, a "7-bag" holds one of each of its pieces
+    #[must_use]
+    pub fn create(self, seed: u64, piece_set: &PieceSet) -> Box<dyn Randomizer> {
+        match self {
+            RandomizerKind::SevenBag => Box::new(BagGenerator::new(seed, 1, piece_set)),
+            RandomizerKind::FourteenBag => Box::new(BagGenerator::new(seed, 2, piece_set)),
+            RandomizerKind::Tgm => Box::new(TgmGenerator::new(seed, piece_set)),
+            RandomizerKind::Random => Box::new(RandomGenerator::new(seed, piece_set)),
+        }
+    }
+}
+
+/// Everything needed to rebuild a game's randomizer mid-game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RandomizerState {
+    pub kind: RandomizerKind,
+    pub generator: GeneratorState,
+}
+
+/// The randomizer pieces are dealt from in the current game
+#[derive(Resource)]
+pub struct PieceRandomizer {
+    kind: RandomizerKind,
+    generator: Box<dyn Randomizer>,
+}
+
+impl PieceRandomizer {
+    #[must_use]
+    pub fn new(kind: RandomizerKind, seed: u64, piece_set: &PieceSet) -> Self {
+        Self {
+            kind,
+            generator: kind.create(seed, piece_set),
+        }
+    }
+
+    #[must_use]
+    pub fn from_state(state: &RandomizerState, piece_set: &PieceSet) -> Self {
+        let mut randomizer = Self::new(state.kind, 0, piece_set);
+        randomizer.generator.load_state(&state.generator);
+        randomizer
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> RandomizerKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn state(&self) -> RandomizerState {
+        RandomizerState {
+            kind: self.kind,
+            generator: self.generator.save_state(),
+        }
+    }
+
+    /// Creates a randomizer dealing classic pieces, with a fresh seed from the global RNG
+    #[must_use]
+    pub fn from_entropy(kind: RandomizerKind) -> Self {
+        Self::new(kind, fastrand::u64(..), &PieceSet::classic())
+    }
+
+    pub fn next_piece(&mut self) -> TetrominoType {
+        self.generator.next_piece()
+    }
+}
+
+impl Default for PieceRandomizer {
+    fn default() -> Self {
+        Self::from_entropy(RandomizerKind::default())
+    }
+}
+
+/// Per-game RNG for everything random besides the pieces, seeded with the game so it replays exactly
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    rng: fastrand::Rng,
+}
+
+impl GameRng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    // Where the generator is, GameRng::new picks up from it again
+    #[must_use]
+    pub fn state(&self) -> u64 {
+        self.rng.get_seed()
+    }
+
+    pub fn f32(&mut self) -> f32 {
+        self.rng.f32()
+    }
+
+    /// Splits off an independent generator, for code that can't hold on to the resource
+    pub fn fork(&mut self) -> fastrand::Rng {
+        self.rng.fork()
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(fastrand::u64(..))
+    }
+}
+
+/// Seeds a new game's pieces and its other randomness from a single seed. The pieces take the
+/// seed as it is and the `GameRng` a seed derived from it, so the two streams differ.
+pub fn seed_game(world: &mut World, kind: RandomizerKind, piece_set: &PieceSet, seed: u64) {
+    world.insert_resource(PieceRandomizer::new(kind, seed, piece_set));
+    world.insert_resource(GameRng::new(seed ^ GAME_RNG_SEED_MIX));
+}
@@ -0,0 +1,457 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow precision loss when turning frame counts into seconds for display
+    clippy::cast_precision_loss,
+    // Allow truncation and sign loss when banking whole frames from the playback speed
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    // Allow undocumented errors since they're all plain file and JSON errors
+    clippy::missing_errors_doc
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::start_game;
+use crate::components::{AutoShift, GameState, Handling, Input};
+use crate::config::{Config, Ruleset};
+use crate::game::{FIXED_TIMESTEP, REPLAY_SEEK_SECONDS, REPLAY_SPEEDS};
+use crate::mode::GameModeKind;
+use crate::systems::step_frame;
+
+// Bumped whenever the file layout or the simulation changes in a way old replays can't follow
+pub const REPLAY_VERSION: u32 = 1;
+
+// Index of normal speed in REPLAY_SPEEDS
+const NORMAL_SPEED: usize = 2;
+
+// Bits of the keys pressed on a frame, in the order they're packed
+const KEY_LEFT: u8 = 1;
+const KEY_RIGHT: u8 = 1 << 1;
+const KEY_DOWN: u8 = 1 << 2;
+const KEY_ROTATE: u8 = 1 << 3;
+const KEY_ROTATE_CCW: u8 = 1 << 4;
+const KEY_ROTATE_180: u8 = 1 << 5;
+const KEY_HARD_DROP: u8 = 1 << 6;
+const KEY_HOLD: u8 = 1 << 7;
+
+fn pack_keys(input: &Input) -> u8 {
+    [
+        (input.left, KEY_LEFT),
+        (input.right, KEY_RIGHT),
+        (input.down, KEY_DOWN),
+        (input.rotate, KEY_ROTATE),
+        (input.rotate_ccw, KEY_ROTATE_CCW),
+        (input.rotate_180, KEY_ROTATE_180),
+        (input.hard_drop, KEY_HARD_DROP),
+        (input.hold, KEY_HOLD),
+    ]
+    .into_iter()
+    .filter(|&(pressed, _)| pressed)
+    .fold(0, |keys, (_, bit)| keys | bit)
+}
+
+fn unpack_keys(keys: u8, input: &mut Input) {
+    input.left = keys & KEY_LEFT != 0;
+    input.right = keys & KEY_RIGHT != 0;
+    input.down = keys & KEY_DOWN != 0;
+    input.rotate = keys & KEY_ROTATE != 0;
+    input.rotate_ccw = keys & KEY_ROTATE_CCW != 0;
+    input.rotate_180 = keys & KEY_ROTATE_180 != 0;
+    input.hard_drop = keys & KEY_HARD_DROP != 0;
+    input.hold = keys & KEY_HOLD != 0;
+}
+
+/// Whether play was held up by the pause menu, its countdown or a too small terminal
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Freeze {
+    pub paused: bool,
+    pub resume_countdown: f32,
+    pub resized: bool,
+}
+
+impl Freeze {
+    fn read(game_state: &GameState) -> Self {
+        Self {
+            paused: game_state.paused,
+            resume_countdown: game_state.resume_countdown,
+            resized: game_state.was_paused_for_resize,
+        }
+    }
+
+    fn apply(self, game_state: &mut GameState) {
+        game_state.paused = self.paused;
+        game_state.resume_countdown = self.resume_countdown;
+        game_state.was_paused_for_resize = self.resized;
+    }
+}
+
+/// What the player did before a frame: keys pressed, plus any state changed outside the simulation
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub frame: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub keys: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_shift: Option<AutoShift>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freeze: Option<Freeze>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handling: Option<Handling>,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde passes the field by reference
+fn is_zero(keys: &u8) -> bool {
+    *keys == 0
+}
+
+impl ReplayEvent {
+    fn is_empty(&self) -> bool {
+        self.keys == 0
+            && self.auto_shift.is_none()
+            && self.freeze.is_none()
+            && self.handling.is_none()
+    }
+}
+
+/// A recorded game: how it was set up and the frame-stamped inputs that played it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub recorded_at: u64, // Unix seconds
+    pub seed: u64,
+    pub mode: GameModeKind,
+    pub ruleset: Ruleset,
+    pub handling: Handling,
+    pub frames: u64,
+    pub score: u32,
+    pub lines: u32,
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    // Length of the recording in seconds
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        self.frames as f32 * FIXED_TIMESTEP
+    }
+
+    // Writes the replay to the replay directory, returning the file it went to
+    pub fn save(&self) -> io::Result<PathBuf> {
+        self.save_in(&replay_dir()?)
+    }
+
+    // Writes the replay to a directory. Games of a mode ending within the same second get a
+    // numbered file each rather than overwriting one another.
+    pub fn save_in(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+
+        let name = format!("{}-{}", self.recorded_at, self.mode.name().to_lowercase());
+        for attempt in 1.. {
+            let path = if attempt == 1 {
+                dir.join(format!("{name}.json"))
+            } else {
+                dir.join(format!("{name}-{attempt}.json"))
+            };
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    file.write_all(serde_json::to_string(self)?.as_bytes())?;
+                    return Ok(path);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err),
+            }
+        }
+        unreachable!("a free file name is always found")
+    }
+
+    // Reads a replay, rejecting ones recorded by an incompatible version
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let replay: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("replay version {} is not supported", replay.version),
+            ));
+        }
+        Ok(replay)
+    }
+}
+
+// Where replays are kept, next to the config file
+pub fn replay_dir() -> io::Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join("replays"))
+}
+
+/// Every readable replay on disk, newest first
+#[must_use]
+pub fn list_replays() -> Vec<(PathBuf, Replay)> {
+    let Ok(entries) = replay_dir().and_then(fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut replays: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Replay::load(&path).ok().map(|replay| (path, replay)))
+        .collect();
+    replays.sort_by_key(|(_, replay)| std::cmp::Reverse(replay.recorded_at));
+    replays
+}
+
+/// Formats Unix seconds as a UTC date and time, YYYY-MM-DD HH:MM
+#[must_use]
+pub fn format_date(unix_seconds: u64) -> String {
+    let days = unix_seconds / 86_400;
+    let minutes = unix_seconds % 86_400 / 60;
+
+    // Civil date from days since 1970-01-01, counting in 400 year eras that start in March
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+// Seconds since the Unix epoch, the date saved with replays and high scores
+#[must_use]
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Records a game as it's played, one frame at a time
+#[derive(Serialize, Deserialize)]
+pub struct ReplayRecorder {
+    replay: Replay,
+    // State as the last frame left it, anything different before the next one came from outside
+    auto_shift: AutoShift,
+    freeze: Freeze,
+    handling: Handling,
+}
+
+impl ReplayRecorder {
+    #[must_use]
+    pub fn new(seed: u64, mode: GameModeKind, ruleset: Ruleset, handling: Handling) -> Self {
+        Self {
+            replay: Replay {
+                version: REPLAY_VERSION,
+                recorded_at: unix_now(),
+                seed,
+                mode,
+                ruleset,
+                handling,
+                frames: 0,
+                score: 0,
+                lines: 0,
+                events: Vec::new(),
+            },
+            auto_shift: AutoShift::default(),
+            freeze: Freeze::default(),
+            handling,
+        }
+    }
+
+    /// Logs whatever the player changed since the last frame, then runs the frame
+    pub fn step(&mut self, world: &mut World) {
+        let keys = pack_keys(world.resource::<Input>());
+        let auto_shift = world
+            .get_resource::<AutoShift>()
+            .cloned()
+            .unwrap_or_default();
+        let freeze = Freeze::read(world.resource::<GameState>());
+        let handling = world
+            .get_resource::<Handling>()
+            .copied()
+            .unwrap_or_default();
+
+        let event = ReplayEvent {
+            frame: self.replay.frames,
+            keys,
+            auto_shift: (auto_shift != self.auto_shift).then_some(auto_shift),
+            freeze: (freeze != self.freeze).then_some(freeze),
+            handling: (handling != self.handling).then_some(handling),
+        };
+        if !event.is_empty() {
+            self.replay.events.push(event);
+        }
+        self.handling = handling;
+
+        step_frame(world);
+        self.replay.frames += 1;
+
+        self.auto_shift = world
+            .get_resource::<AutoShift>()
+            .cloned()
+            .unwrap_or_default();
+        self.freeze = Freeze::read(world.resource::<GameState>());
+    }
+
+    /// Wraps up the recording with how the game stood at the end
+    #[must_use]
+    pub fn finish(mut self, world: &World) -> Replay {
+        let game_state = world.resource::<GameState>();
+        self.replay.score = game_state.score;
+        self.replay.lines = game_state.lines_cleared;
+        self.replay
+    }
+}
+
+/// Plays a replay back through the game systems, with pause, speed control, stepping and seeking
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub paused: bool,
+    speed_index: usize,
+    frame: u64,
+    cursor: usize,  // Next event to apply
+    freeze: Freeze, // Pause state as recorded, reapplied every frame
+    pending: f32,   // Frames banked towards the next step at the current speed
+}
+
+impl ReplayPlayer {
+    /// Sets the world up as the recorded game started
+    pub fn new(replay: Replay, world: &mut World) -> Self {
+        let mut player = Self {
+            replay,
+            paused: false,
+            speed_index: NORMAL_SPEED,
+            frame: 0,
+            cursor: 0,
+            freeze: Freeze::default(),
+            pending: 0.0,
+        };
+        player.restart(world);
+        player
+    }
+
+    fn restart(&mut self, world: &mut World) {
+        let replay = &self.replay;
+        start_game(world, replay.mode, &replay.ruleset, replay.seed);
+        world.insert_resource(replay.handling);
+
+        self.frame = 0;
+        self.cursor = 0;
+        self.freeze = Freeze::default();
+        self.pending = 0.0;
+    }
+
+    #[must_use]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frames
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(REPLAY_SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.pending = 0.0;
+    }
+
+    /// Runs the next recorded frame, returning false once the recording is over
+    pub fn step(&mut self, world: &mut World) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        while let Some(event) = self
+            .replay
+            .events
+            .get(self.cursor)
+            .filter(|event| event.frame == self.frame)
+        {
+            unpack_keys(event.keys, &mut world.resource_mut::<Input>());
+            if let Some(auto_shift) = &event.auto_shift {
+                world.insert_resource(auto_shift.clone());
+            }
+            if let Some(freeze) = event.freeze {
+                self.freeze = freeze;
+            }
+            if let Some(handling) = event.handling {
+                world.insert_resource(handling);
+            }
+            self.cursor += 1;
+        }
+
+        // The terminal being watched on may be a different size, so the recorded freeze wins
+        self.freeze.apply(&mut world.resource_mut::<GameState>());
+        step_frame(world);
+        self.frame += 1;
+        self.freeze = Freeze::read(world.resource::<GameState>());
+        true
+    }
+
+    /// Plays the share of `frames` real frames the current speed allows, returning how many ran
+    pub fn advance(&mut self, world: &mut World, frames: u32) -> u32 {
+        if self.paused || self.is_finished() {
+            return 0;
+        }
+
+        self.pending += frames as f32 * self.speed();
+        let mut played = 0;
+        while self.pending >= 1.0 && self.step(world) {
+            self.pending -= 1.0;
+            played += 1;
+        }
+        played
+    }
+
+    /// Jumps to a frame, replaying from the start when it lies behind the current one
+    pub fn seek(&mut self, world: &mut World, frame: u64) {
+        if frame < self.frame {
+            self.restart(world);
+        }
+        while self.frame < frame && self.step(world) {}
+    }
+
+    // Seeks a few seconds forwards or backwards
+    pub fn skip(&mut self, world: &mut World, forward: bool) {
+        let frames = (REPLAY_SEEK_SECONDS / FIXED_TIMESTEP).round() as u64;
+        let target = if forward {
+            self.frame + frames
+        } else {
+            self.frame.saturating_sub(frames)
+        };
+        self.seek(world, target.min(self.replay.frames));
+    }
+}
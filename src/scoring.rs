@@ -0,0 +1,281 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow truncation when casting the float bonuses back to points, scores are far below u32::MAX
+    clippy::cast_possible_truncation,
+    // Allow sign loss when casting the float bonuses back to points since they're never negative
+    clippy::cast_sign_loss,
+    // Allow precision loss when casting points to f32 for the classic multipliers
+    clippy::cast_precision_loss
+)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::{GameState, TSpin};
+use crate::game::{
+    BACK_TO_BACK_MULTIPLIER, COMBO_MULTIPLIER, HARD_DROP_POINTS, LEVEL_SCORE_THRESHOLDS,
+    LINES_PER_LEVEL, MAX_LEVEL, PERFECT_CLEAR_BONUS, POINTS_DOUBLE, POINTS_SINGLE, POINTS_TETRIS,
+    POINTS_TRIPLE, SOFT_DROP_POINTS, STARTING_LEVEL, TSPIN_DOUBLE, TSPIN_MINI_DOUBLE,
+    TSPIN_MINI_SINGLE, TSPIN_MINI_ZERO, TSPIN_SINGLE, TSPIN_TRIPLE, TSPIN_ZERO,
+};
+
+// Guideline line clear values, indexed by lines cleared (multiplied by level)
+const GUIDELINE_LINE_POINTS: [u32; 5] = [0, 100, 300, 500, 800];
+const GUIDELINE_TSPIN_POINTS: [u32; 4] = [400, 800, 1200, 1600];
+const GUIDELINE_TSPIN_MINI_POINTS: [u32; 3] = [100, 200, 400];
+const GUIDELINE_COMBO_POINTS: u32 = 50; // Per combo step, per level
+const GUIDELINE_PERFECT_CLEAR_POINTS: [u32; 5] = [0, 800, 1200, 1800, 2000];
+const GUIDELINE_B2B_PERFECT_CLEAR_TETRIS: u32 = 3200;
+
+// NES line clear values, indexed by lines cleared (multiplied by level)
+const NES_LINE_POINTS: [u32; 5] = [0, 40, 100, 300, 1200];
+
+/// Turns locked pieces into points and decides when the level goes up
+pub trait ScoringRule: Send + Sync {
+    /// Scores a piece that just locked, along with any drop points earned on the way down.
+    /// The level is recomputed afterwards with `update_level`
+    fn score_lock(
+        &self,
+        state: &mut GameState,
+        lines_cleared: usize,
+        t_spin: TSpin,
+        is_perfect_clear: bool,
+    );
+
+    /// Recomputes the level, by default one level every `LINES_PER_LEVEL` lines
+    fn update_level(&self, state: &mut GameState) {
+        let level = state.lines_cleared / LINES_PER_LEVEL + STARTING_LEVEL;
+        state.level = level.min(MAX_LEVEL);
+    }
+}
+
+// Bookkeeping every rule shares: clear counters, the line total and the combo chain
+fn record_lock(state: &mut GameState, lines_cleared: usize, t_spin: TSpin, is_perfect_clear: bool) {
+    if t_spin != TSpin::None {
+        state.t_spin_count += 1;
+    }
+
+    if lines_cleared == 0 {
+        state.combo_count = 0;
+        return;
+    }
+
+    state.combo_count += 1;
+    if lines_cleared == 4 {
+        state.tetris_count += 1;
+    }
+    if is_perfect_clear {
+        state.perfect_clear_count += 1;
+    }
+    state.lines_cleared += u32::try_from(lines_cleared).unwrap_or(u32::MAX);
+}
+
+/// The original fallingblocks scoring, with score thresholds that can raise the level early
+pub struct ClassicScoring;
+
+impl ScoringRule for ClassicScoring {
+    fn score_lock(
+        &self,
+        state: &mut GameState,
+        lines_cleared: usize,
+        t_spin: TSpin,
+        is_perfect_clear: bool,
+    ) {
+        let was_difficult = state.last_clear_was_difficult && state.back_to_back;
+        record_lock(state, lines_cleared, t_spin, is_perfect_clear);
+
+        if lines_cleared == 0 {
+            // A T-spin still scores without clearing lines, and leaves back-to-back alone
+            let t_spin_points = match t_spin {
+                TSpin::Full => TSPIN_ZERO,
+                TSpin::Mini => TSPIN_MINI_ZERO,
+                TSpin::None => 0,
+            };
+            state.score += t_spin_points * state.level;
+            return;
+        }
+
+        // Determine base points based on clear type
+        let mut base_points = match (t_spin, lines_cleared) {
+            (TSpin::Full, 1) => TSPIN_SINGLE,
+            (TSpin::Full, 2) => TSPIN_DOUBLE,
+            (TSpin::Full, 3) => TSPIN_TRIPLE,
+            // A Mini can only clear as many lines as the T is tall when lying flat
+            (TSpin::Mini, 1) => TSPIN_MINI_SINGLE,
+            (TSpin::Mini, _) => TSPIN_MINI_DOUBLE,
+            (TSpin::None, 1) => POINTS_SINGLE,
+            (TSpin::None, 2) => POINTS_DOUBLE,
+            (TSpin::None, 3) => POINTS_TRIPLE,
+            (TSpin::None, 4) => POINTS_TETRIS,
+            _ => 0,
+        };
+
+        // Apply back-to-back bonus if applicable
+        let is_difficult_clear = lines_cleared == 4 || t_spin != TSpin::None;
+        if is_difficult_clear && was_difficult {
+            base_points = (base_points as f32 * BACK_TO_BACK_MULTIPLIER) as u32;
+        }
+        state.back_to_back = is_difficult_clear;
+        state.last_clear_was_difficult = is_difficult_clear;
+
+        // Each consecutive clear adds half the clear's value again
+        let combo_bonus = if state.combo_count > 1 {
+            ((state.combo_count - 1) as f32 * COMBO_MULTIPLIER * base_points as f32) as u32
+        } else {
+            0
+        };
+
+        let perfect_clear_bonus = if is_perfect_clear {
+            PERFECT_CLEAR_BONUS
+        } else {
+            0
+        };
+
+        // Drop points are banked until the next line clear
+        let drop_bonus = (state.soft_drop_distance * SOFT_DROP_POINTS)
+            + (state.hard_drop_distance * HARD_DROP_POINTS);
+        state.soft_drop_distance = 0;
+        state.hard_drop_distance = 0;
+
+        state.score += (base_points * state.level) + combo_bonus + perfect_clear_bonus + drop_bonus;
+    }
+
+    fn update_level(&self, state: &mut GameState) {
+        // Traditional level progression based on lines cleared
+        let lines_level = (state.lines_cleared / LINES_PER_LEVEL) + STARTING_LEVEL;
+
+        // Level progression based on score thresholds
+        let mut score_level = STARTING_LEVEL;
+        for &(threshold, level) in LEVEL_SCORE_THRESHOLDS {
+            if state.score >= threshold {
+                score_level = level;
+            } else {
+                break;
+            }
+        }
+
+        // Take the maximum of the two approaches, but cap at MAX_LEVEL
+        state.level = lines_level.max(score_level).min(MAX_LEVEL);
+    }
+}
+
+/// The Tetris Guideline table, with T-spin minis, 50 × combo × level and back-to-back ×1.5
+pub struct GuidelineScoring;
+
+impl ScoringRule for GuidelineScoring {
+    fn score_lock(
+        &self,
+        state: &mut GameState,
+        lines_cleared: usize,
+        t_spin: TSpin,
+        is_perfect_clear: bool,
+    ) {
+        let was_back_to_back = state.back_to_back;
+        record_lock(state, lines_cleared, t_spin, is_perfect_clear);
+
+        let lines = lines_cleared.min(4);
+        let action_points = match t_spin {
+            TSpin::Full => GUIDELINE_TSPIN_POINTS[lines.min(3)],
+            TSpin::Mini => GUIDELINE_TSPIN_MINI_POINTS[lines.min(2)],
+            TSpin::None => GUIDELINE_LINE_POINTS[lines],
+        };
+        let mut points = action_points * state.level;
+
+        // A T-spin that clears nothing neither builds nor breaks back-to-back
+        if lines > 0 {
+            let is_difficult_clear = lines == 4 || t_spin != TSpin::None;
+            let continues_chain = is_difficult_clear && was_back_to_back;
+            if continues_chain {
+                points = points * 3 / 2;
+            }
+            state.back_to_back = is_difficult_clear;
+            state.last_clear_was_difficult = is_difficult_clear;
+
+            // The first clear of a chain is combo 0
+            points += GUIDELINE_COMBO_POINTS * (state.combo_count - 1) * state.level;
+
+            if is_perfect_clear {
+                let bonus = if lines == 4 && continues_chain {
+                    GUIDELINE_B2B_PERFECT_CLEAR_TETRIS
+                } else {
+                    GUIDELINE_PERFECT_CLEAR_POINTS[lines]
+                };
+                points += bonus * state.level;
+            }
+        }
+
+        // Drop points are paid as soon as the piece locks
+        points += state.soft_drop_distance * SOFT_DROP_POINTS
+            + state.hard_drop_distance * HARD_DROP_POINTS;
+        state.soft_drop_distance = 0;
+        state.hard_drop_distance = 0;
+
+        state.score += points;
+    }
+}
+
+/// NES scoring, line clears only with no T-spins, combos or back-to-back
+pub struct NesScoring;
+
+impl ScoringRule for NesScoring {
+    fn score_lock(
+        &self,
+        state: &mut GameState,
+        lines_cleared: usize,
+        t_spin: TSpin,
+        is_perfect_clear: bool,
+    ) {
+        record_lock(state, lines_cleared, t_spin, is_perfect_clear);
+        state.back_to_back = false;
+        state.last_clear_was_difficult = false;
+
+        // Level 1 here is the NES's level 0, so the level is already the (level + 1) multiplier
+        let mut points = NES_LINE_POINTS[lines_cleared.min(4)] * state.level;
+
+        // Soft drops pay a point a cell, hard drops didn't exist
+        points += state.soft_drop_distance * SOFT_DROP_POINTS;
+        state.soft_drop_distance = 0;
+        state.hard_drop_distance = 0;
+
+        state.score += points;
+    }
+}
+
+/// The scoring rules a game can be played with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScoringRuleKind {
+    #[default]
+    Classic,
+    Guideline,
+    Nes,
+}
+
+impl ScoringRuleKind {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoringRuleKind::Classic => "Classic",
+            ScoringRuleKind::Guideline => "Guideline",
+            ScoringRuleKind::Nes => "NES",
+        }
+    }
+
+    /// Cycles to the next rule, used by the options menu
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            ScoringRuleKind::Classic => ScoringRuleKind::Guideline,
+            ScoringRuleKind::Guideline => ScoringRuleKind::Nes,
+            ScoringRuleKind::Nes => ScoringRuleKind::Classic,
+        }
+    }
+
+    // The rules keep no state of their own, so one shared instance of each is enough
+    #[must_use]
+    pub fn rule(self) -> &'static dyn ScoringRule {
+        match self {
+            ScoringRuleKind::Classic => &ClassicScoring,
+            ScoringRuleKind::Guideline => &GuidelineScoring,
+            ScoringRuleKind::Nes => &NesScoring,
+        }
+    }
+}
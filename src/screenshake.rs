@@ -14,6 +14,7 @@ use bevy_ecs::prelude::*;
 use log::trace;
 
 use crate::components::ScreenShake;
+use crate::randomizer::GameRng;
 
 /// Triggers a screen shake effect with the specified intensity and duration
 pub fn trigger_screen_shake(world: &mut World, intensity: f32, duration: f32) {
@@ -49,6 +50,7 @@ pub fn trigger_line_clear_shake(world: &mut World, lines_cleared: usize) {
 
 /// Updates the screen shake state based on elapsed time
 pub fn update_screen_shake(world: &mut World, delta_seconds: f32) {
+    let mut rng = world.get_resource_or_insert_with(GameRng::default).fork();
     let mut screen_shake = world.resource_mut::<ScreenShake>();
     if screen_shake.duration > 0.0 {
         screen_shake.duration -= delta_seconds;
@@ -68,14 +70,14 @@ pub fn update_screen_shake(world: &mut World, delta_seconds: f32) {
             if screen_shake.horizontal_bias {
                 // For line clear: more horizontal movement, less vertical
                 screen_shake.current_offset = (
-                    (fastrand::i16(0..=max_offset) - max_offset / 2),
-                    (fastrand::i16(0..=(max_offset / 3)) - max_offset / 6),
+                    (rng.i16(0..=max_offset) - max_offset / 2),
+                    (rng.i16(0..=(max_offset / 3)) - max_offset / 6),
                 );
             } else {
                 // Regular screen shake: equal in both directions
                 screen_shake.current_offset = (
-                    (fastrand::i16(0..=max_offset) - max_offset / 2),
-                    (fastrand::i16(0..=max_offset) - max_offset / 2),
+                    (rng.i16(0..=max_offset) - max_offset / 2),
+                    (rng.i16(0..=max_offset) - max_offset / 2),
                 );
             }
         }
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use bevy_ecs::system::Resource;
+use bevy_ecs::prelude::{EventReader, Res, Resource};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, SizedSample};
 use crossbeam_channel::{Receiver, Sender, bounded};
@@ -7,6 +7,9 @@ use fundsp::hacker32::*;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use crate::components::TSpin;
+use crate::events::{HoldUsed, LevelUp, LinesCleared, PieceLocked, TopOut};
+
 // Sound effects types that can be played
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)] // Some variants may not be used yet but will be in the future
@@ -21,6 +24,7 @@ pub enum SoundEffect {
     Rotate,
     SoftDrop,
     HardDrop,
+    Hold,
     Tetris,
     TSpin,
     PerfectClear,
@@ -130,6 +134,53 @@ impl Default for AudioState {
     }
 }
 
+// Plays the sounds of the game events sent this frame, worlds without audio just let them go
+#[allow(clippy::needless_pass_by_value)]
+pub fn sound_system(
+    audio_state: Option<Res<AudioState>>,
+    mut locked: EventReader<PieceLocked>,
+    mut cleared: EventReader<LinesCleared>,
+    mut holds: EventReader<HoldUsed>,
+    mut level_ups: EventReader<LevelUp>,
+    mut top_outs: EventReader<TopOut>,
+) {
+    let play = |effect| {
+        if let Some(audio_state) = &audio_state {
+            audio_state.play_sound(effect);
+        }
+    };
+
+    // A T-spin that clears nothing still gets its sound
+    for lock in locked.read() {
+        if lock.lines_cleared == 0 && lock.t_spin != TSpin::None {
+            play(SoundEffect::TSpin);
+        }
+    }
+
+    // The sound of a clear depends on what kind it was
+    for clear in cleared.read() {
+        play(if clear.perfect_clear {
+            SoundEffect::PerfectClear
+        } else if clear.lines == 4 {
+            SoundEffect::Tetris
+        } else if clear.t_spin != TSpin::None {
+            SoundEffect::TSpin
+        } else {
+            SoundEffect::LineClear
+        });
+    }
+
+    for _ in holds.read() {
+        play(SoundEffect::Hold);
+    }
+    for _ in level_ups.read() {
+        play(SoundEffect::LevelUp);
+    }
+    for _ in top_outs.read() {
+        play(SoundEffect::GameOver);
+    }
+}
+
 fn run_audio_thread(receiver: Receiver<AudioCommand>) -> Result<()> {
     let mut retry_count = 0;
     const MAX_RETRIES: u32 = 3;
@@ -498,6 +549,18 @@ pub fn generate_sound_sample(effect: SoundEffect, t: f64) -> (f32, f32) {
             let sample = (noise + tone) * amp;
             (sample * 0.8, sample * 1.2) // Slightly right panned
         }
+        SoundEffect::Hold => {
+            // Hold - quick two-note blip
+            let (freq, amp) = if t < 0.04 {
+                (330.0, 0.3)
+            } else if t < 0.08 {
+                (494.0, 0.3)
+            } else {
+                (0.0, 0.0)
+            };
+            let sample = (t * freq * std::f32::consts::TAU).sin() * amp;
+            (sample, sample) // Center panned
+        }
         SoundEffect::LineClear => {
             // Line clear - rising sweep
             let freq = 300.0 + 500.0 * (t * 5.0).min(1.0);
@@ -680,7 +743,7 @@ fn create_sound_effect(effect: SoundEffect) -> Box<dyn AudioUnit> {
         SoundEffect::GameOver => create_game_over(),
         SoundEffect::LevelUp => create_level_up(),
         SoundEffect::Move => create_move_click(),
-        SoundEffect::Rotate => create_rotate_click(),
+        SoundEffect::Rotate | SoundEffect::Hold => create_rotate_click(),
         SoundEffect::SoftDrop => create_soft_drop(),
         SoundEffect::HardDrop => create_hard_drop(),
         SoundEffect::Tetris => create_tetris(),
@@ -0,0 +1,201 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow truncation when counting the keys of one frame, there are only a handful
+    clippy::cast_possible_truncation,
+    // Allow precision loss when turning counts into rates, they're only shown to two decimals
+    clippy::cast_precision_loss
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Input, TSpin, TetrominoType};
+use crate::events::PieceLocked;
+use crate::game::{
+    ATTACK_BACK_TO_BACK, ATTACK_COMBO, ATTACK_LINES, ATTACK_PERFECT_CLEAR, ATTACK_TSPIN_LINES,
+    ATTACK_TSPIN_MINI_LINES,
+};
+use crate::mode::format_time;
+
+/// The numbers the HUD can show while playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatKind {
+    Pps,
+    Apm,
+    Kpp,
+    Pieces,
+    IDrought,
+    Holes,
+    Time,
+}
+
+impl StatKind {
+    pub const ALL: [StatKind; 7] = [
+        StatKind::Pps,
+        StatKind::Apm,
+        StatKind::Kpp,
+        StatKind::Pieces,
+        StatKind::IDrought,
+        StatKind::Holes,
+        StatKind::Time,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            StatKind::Pps => "PPS",
+            StatKind::Apm => "APM",
+            StatKind::Kpp => "KPP",
+            StatKind::Pieces => "Pieces",
+            StatKind::IDrought => "I Drought",
+            StatKind::Holes => "Holes",
+            StatKind::Time => "Time",
+        }
+    }
+}
+
+/// Performance numbers for the game being played, kept up by the game systems
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+    pub pieces: u32,
+    pub piece_counts: Vec<u32>, // Pieces placed of each type, in piece set order
+    pub keys: u32,              // Key presses that reached the game, auto-repeats excluded
+    pub attack: u32,            // Garbage lines the clears would have sent
+    pub i_drought: u32,         // Pieces placed since the last I
+    pub max_i_drought: u32,
+    pub holes_created: u32,
+    back_to_back: bool, // Attack keeps its own chains, whatever the scoring rule does
+    combo: u32,
+}
+
+impl GameStats {
+    // Counts the game keys pressed this frame
+    pub fn record_keys(&mut self, input: &Input) {
+        let presses = [
+            input.left,
+            input.right,
+            input.down,
+            input.rotate,
+            input.rotate_ccw,
+            input.rotate_180,
+            input.hard_drop,
+            input.hold,
+        ];
+        self.keys += presses.into_iter().filter(|&pressed| pressed).count() as u32;
+    }
+
+    /// Records a locked piece, its clear and the holes it left behind
+    pub fn record_lock(
+        &mut self,
+        tetromino_type: TetrominoType,
+        lines_cleared: usize,
+        t_spin: TSpin,
+        is_perfect_clear: bool,
+        new_holes: u32,
+    ) {
+        self.pieces += 1;
+        let index = usize::from(tetromino_type.0);
+        if self.piece_counts.len() <= index {
+            self.piece_counts.resize(index + 1, 0);
+        }
+        self.piece_counts[index] += 1;
+
+        // The first piece of a set is its long bar
+        if tetromino_type == TetrominoType(0) {
+            self.i_drought = 0;
+        } else {
+            self.i_drought += 1;
+            self.max_i_drought = self.max_i_drought.max(self.i_drought);
+        }
+
+        self.holes_created += new_holes;
+        self.attack += self.attack_for(lines_cleared, t_spin, is_perfect_clear);
+    }
+
+    // Lines a clear sends: its base value, back-to-back, combo and perfect clear bonuses
+    fn attack_for(&mut self, lines_cleared: usize, t_spin: TSpin, is_perfect_clear: bool) -> u32 {
+        if lines_cleared == 0 {
+            self.combo = 0;
+            return 0;
+        }
+
+        let lines = lines_cleared.min(4);
+        let mut attack = match t_spin {
+            TSpin::Full => ATTACK_TSPIN_LINES[lines.min(3)],
+            TSpin::Mini => ATTACK_TSPIN_MINI_LINES[lines.min(2)],
+            TSpin::None => ATTACK_LINES[lines],
+        };
+
+        let is_difficult_clear = lines == 4 || t_spin != TSpin::None;
+        if is_difficult_clear && self.back_to_back {
+            attack += ATTACK_BACK_TO_BACK;
+        }
+        self.back_to_back = is_difficult_clear;
+
+        attack += ATTACK_COMBO[(self.combo as usize).min(ATTACK_COMBO.len() - 1)];
+        self.combo += 1;
+
+        if is_perfect_clear {
+            attack += ATTACK_PERFECT_CLEAR;
+        }
+        attack
+    }
+
+    #[must_use]
+    pub fn pieces_per_second(&self, elapsed: f32) -> f32 {
+        if elapsed > 0.0 {
+            self.pieces as f32 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    #[must_use]
+    pub fn attack_per_minute(&self, elapsed: f32) -> f32 {
+        if elapsed > 0.0 {
+            self.attack as f32 * 60.0 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    #[must_use]
+    pub fn keys_per_piece(&self) -> f32 {
+        if self.pieces > 0 {
+            self.keys as f32 / self.pieces as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// A stat as shown in the HUD and the summary, given the seconds played
+    #[must_use]
+    pub fn value(&self, kind: StatKind, elapsed: f32) -> String {
+        match kind {
+            StatKind::Pps => format!("{:.2}", self.pieces_per_second(elapsed)),
+            StatKind::Apm => format!("{:.1}", self.attack_per_minute(elapsed)),
+            StatKind::Kpp => format!("{:.2}", self.keys_per_piece()),
+            StatKind::Pieces => self.pieces.to_string(),
+            StatKind::IDrought => format!("{} (max {})", self.i_drought, self.max_i_drought),
+            StatKind::Holes => self.holes_created.to_string(),
+            StatKind::Time => format_time(elapsed),
+        }
+    }
+}
+
+// Records every piece locked this frame
+pub fn stats_system(mut locked: EventReader<PieceLocked>, stats: Option<ResMut<GameStats>>) {
+    let Some(mut stats) = stats else {
+        locked.clear();
+        return;
+    };
+    for lock in locked.read() {
+        stats.record_lock(
+            lock.tetromino.tetromino_type,
+            lock.lines_cleared,
+            lock.t_spin,
+            lock.perfect_clear,
+            lock.new_holes,
+        );
+    }
+}
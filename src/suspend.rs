@@ -0,0 +1,228 @@
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    // Allow undocumented errors since they're all plain file and JSON errors
+    clippy::missing_errors_doc
+)]
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::components::{
+    AutoShift, Board, GameState, Ghost, InitialInputs, Input, LastAction, LockDelay, Phase,
+    Position, ScreenShake, Tetromino, TetrominoType, Timings,
+};
+use crate::config::Config;
+use crate::finesse::Finesse;
+use crate::game::{MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH};
+use crate::mode::GameMode;
+use crate::randomizer::{GameRng, PieceRandomizer, RandomizerState};
+use crate::replay::ReplayRecorder;
+use crate::stats::GameStats;
+
+// Bumped whenever the saved layout changes, older suspend files are turned away
+pub const SUSPEND_VERSION: u32 = 2;
+
+/// The piece in play when the game was suspended
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivePiece {
+    pub tetromino: Tetromino,
+    pub position: Position,
+    pub last_action: LastAction,
+}
+
+/// A game in progress, saved on quit so it can be continued next time
+#[derive(Serialize, Deserialize)]
+pub struct SuspendedGame {
+    pub version: u32,
+    pub mode: GameMode,
+    pub game_state: GameState,
+    pub board: Board,
+    pub piece: Option<ActivePiece>,
+    pub lock_delay: LockDelay,
+    #[serde(default)]
+    pub timings: Timings,
+    #[serde(default)]
+    pub phase: Phase, // A game saved mid line clear or entry delay picks up where it was
+    #[serde(default)]
+    pub initial_inputs: InitialInputs, // Rotation and hold already pressed for the next piece
+    #[serde(default)]
+    pub screen_shake: ScreenShake, // Still shaking from the last lock when the game was quit
+    pub randomizer: RandomizerState,
+    pub rng: u64,
+    #[serde(default)]
+    pub stats: GameStats,
+    #[serde(default)]
+    pub finesse: Finesse,
+    #[serde(default)]
+    pub recorder: Option<ReplayRecorder>, // The replay so far, carried on once the game continues
+}
+
+impl SuspendedGame {
+    /// Captures the game in the world, taking over its replay recording
+    #[must_use]
+    pub fn capture(world: &mut World, recorder: Option<ReplayRecorder>) -> Self {
+        let piece = world
+            .query::<(&Tetromino, &Position, &LastAction)>()
+            .iter(world)
+            .next()
+            .map(|(tetromino, position, last_action)| ActivePiece {
+                tetromino: *tetromino,
+                position: *position,
+                last_action: *last_action,
+            });
+
+        Self {
+            version: SUSPEND_VERSION,
+            mode: world.resource::<GameMode>().clone(),
+            game_state: world.resource::<GameState>().clone(),
+            board: world.resource::<Board>().clone(),
+            piece,
+            lock_delay: world.resource::<LockDelay>().clone(),
+            timings: world.get_resource::<Timings>().copied().unwrap_or_default(),
+            phase: world.get_resource::<Phase>().cloned().unwrap_or_default(),
+            initial_inputs: world
+                .get_resource::<InitialInputs>()
+                .copied()
+                .unwrap_or_default(),
+            screen_shake: world
+                .get_resource::<ScreenShake>()
+                .cloned()
+                .unwrap_or_default(),
+            randomizer: world.resource::<PieceRandomizer>().state(),
+            rng: world
+                .get_resource::<GameRng>()
+                .map_or_else(|| fastrand::u64(..), GameRng::state),
+            stats: world
+                .get_resource::<GameStats>()
+                .cloned()
+                .unwrap_or_default(),
+            finesse: world.get_resource::<Finesse>().cloned().unwrap_or_default(),
+            recorder,
+        }
+    }
+
+    /// Puts the game back into the world, paused so the player can get ready, and hands back
+    /// its replay recording
+    pub fn restore(self, world: &mut World) -> Option<ReplayRecorder> {
+        world.clear_entities();
+
+        let mut game_state = self.game_state;
+        game_state.pause();
+        game_state.was_paused_for_resize = false;
+        world.insert_resource(game_state);
+        world.insert_resource(self.mode);
+        world.insert_resource(PieceRandomizer::from_state(
+            &self.randomizer,
+            &self.board.piece_set,
+        ));
+        world.insert_resource(self.board);
+        world.insert_resource(self.lock_delay);
+        world.insert_resource(self.timings);
+        world.insert_resource(self.phase);
+        world.insert_resource(self.initial_inputs);
+        world.insert_resource(self.screen_shake);
+        world.insert_resource(GameRng::new(self.rng));
+        world.insert_resource(self.stats);
+        world.insert_resource(self.finesse);
+
+        // Keys held when the game was saved are long since let go
+        world.insert_resource(Input::default());
+        world.insert_resource(AutoShift::default());
+
+        if let Some(piece) = self.piece {
+            world.spawn((
+                piece.tetromino,
+                piece.position,
+                Ghost {
+                    position: piece.position,
+                },
+                piece.last_action,
+            ));
+        }
+
+        self.recorder
+    }
+
+    // Reads a suspended game, turning away other versions and anything that doesn't add up
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = value.get("version").and_then(serde_json::Value::as_u64);
+        if version != Some(u64::from(SUSPEND_VERSION)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "it was saved by a different version of the game",
+            ));
+        }
+
+        let game: Self = serde_json::from_value(value)?;
+        if !game.is_consistent() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the saved board is damaged",
+            ));
+        }
+        Ok(game)
+    }
+
+    // Whether the board and pieces are shaped the way the game systems expect, with every
+    // piece on the board, in the queue or in the randomizer one its piece set has
+    fn is_consistent(&self) -> bool {
+        let board = &self.board;
+        let columns_fit = |columns: &[Vec<_>], rows: usize| {
+            columns.len() == board.width && columns.iter().all(|column| column.len() == rows)
+        };
+        let in_set = |piece: &TetrominoType| board.piece_set.contains(*piece);
+
+        (MIN_BOARD_WIDTH..=MAX_BOARD_WIDTH).contains(&board.width)
+            && (MIN_BOARD_HEIGHT..=MAX_BOARD_HEIGHT).contains(&board.height)
+            && columns_fit(&board.cells, board.height)
+            && columns_fit(&board.hidden, board.hidden_rows)
+            && board.piece_set.validate().is_ok()
+            && board
+                .cells
+                .iter()
+                .chain(&board.hidden)
+                .flatten()
+                .flatten()
+                .all(in_set)
+            && self.game_state.next_queue.iter().all(in_set)
+            && self.game_state.held_tetromino.iter().all(in_set)
+            && self.randomizer.generator.pieces.iter().all(in_set)
+            && self.piece.as_ref().is_none_or(|piece| {
+                piece.tetromino.rotation < 4 && in_set(&piece.tetromino.tetromino_type)
+            })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = suspend_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn load() -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(suspend_path()?)?)
+    }
+
+    // Whether there is a suspended game to offer on the main menu
+    #[must_use]
+    pub fn exists() -> bool {
+        suspend_path().is_ok_and(|path| path.exists())
+    }
+
+    // Removes the suspend file once its game has been continued or turned away
+    pub fn discard() {
+        if let Ok(path) = suspend_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+// Where the suspended game is kept, next to the config file
+fn suspend_path() -> io::Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join("suspend.json"))
+}
@@ -13,19 +13,121 @@
 )]
 
 use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::{ExecutorKind, ScheduleLabel};
 use log::{debug, info, trace};
 
+use crate::Time;
 use crate::components::{
-    Board, CoyoteTime, GameState, Ghost, Input, Particle, Position, ScreenShake, Tetromino,
-    TetrominoType,
+    AutoShift, Board, GameState, Ghost, Handling, InitialInputs, Input, LastAction, LockDelay,
+    Phase, Position, TSpin, Tetromino, TetrominoType, Timings, TopOut,
 };
-use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
+use crate::events::{self, HoldUsed, LevelUp, LinesCleared, PieceLocked};
+use crate::finesse::Finesse;
+use crate::game::{FIXED_TIMESTEP, FRAMES_PER_SECOND, MAX_GRAVITY, MAX_PREVIEW_COUNT};
+use crate::mode::{GameMode, ModeResult};
 use crate::particles;
-use crate::sound::{AudioState, SoundEffect};
+use crate::randomizer::{GameRng, PieceRandomizer};
+use crate::sound::{self, AudioState, SoundEffect};
+use crate::stats::{self, GameStats};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use std::time::{Duration, Instant};
 
 pub fn spawn_tetromino(world: &mut World) {
+    // Deal the front of the next queue, drawing one first if the queue is empty
+    let mut tetromino_type = deal_piece(world);
+
+    // A freshly dealt piece may always be swapped into the hold slot
+    world.resource_mut::<GameState>().hold_used = false;
+
+    // Rotation and hold pressed while waiting for this piece take effect as it spawns
+    let initial = world.remove_resource::<InitialInputs>().unwrap_or_default();
+    if initial.hold {
+        debug!("Initial hold, swapping the dealt piece with the hold slot");
+        let held = tetromino_type;
+        let previously_held = world
+            .resource_mut::<GameState>()
+            .held_tetromino
+            .replace(held);
+        tetromino_type = previously_held.unwrap_or_else(|| deal_piece(world));
+        world.resource_mut::<GameState>().hold_used = true;
+        events::send(world, HoldUsed);
+    }
+
+    // The piece only spawns turned if it fits that way
+    let mut tetromino = Tetromino::new(tetromino_type);
+    if initial.rotation > 0 {
+        let mut rotated = tetromino;
+        rotated.rotation = initial.rotation % 4;
+        let board = world.resource::<Board>();
+        if board.is_valid_position(spawn_position(board, &rotated), &rotated) {
+            debug!(
+                "Initial rotation, spawning turned {} times",
+                rotated.rotation
+            );
+            tetromino = rotated;
+            play_sound(world, SoundEffect::Rotate);
+        }
+    }
+
+    spawn_piece(world, tetromino);
+}
+
+// Takes the piece at the front of the next queue and tops the queue back up, so the previews
+// always have pieces to show
+fn deal_piece(world: &mut World) -> TetrominoType {
+    let next_tetromino = world.resource_mut::<GameState>().next_queue.pop_front();
+    let tetromino_type = next_tetromino.unwrap_or_else(|| draw_piece(world));
+    fill_next_queue(world);
+    tetromino_type
+}
+
+// Plays a sound effect, when the world has audio
+fn play_sound(world: &World, effect: SoundEffect) {
+    if let Some(audio_state) = world.get_resource::<AudioState>() {
+        audio_state.play_sound(effect);
+    }
+}
+
+// Draws pieces from the randomizer until the next queue is full
+fn fill_next_queue(world: &mut World) {
+    while world.resource::<GameState>().next_queue.len() < MAX_PREVIEW_COUNT {
+        let piece = draw_piece(world);
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_back(piece);
+    }
+}
+
+// Draws a piece from the game's randomizer, falling back to the default one if none is configured
+fn draw_piece(world: &mut World) -> TetrominoType {
+    world
+        .get_resource_or_insert_with(PieceRandomizer::default)
+        .next_piece()
+}
+
+// Spawns a specific tetromino type at the top of the board, replacing the active one
+fn spawn_tetromino_of_type(world: &mut World, tetromino_type: TetrominoType) {
+    spawn_piece(world, Tetromino::new(tetromino_type));
+}
+
+// Start position centered in the hidden buffer, with the lowest block just above the visible field
+fn spawn_position(board: &Board, tetromino: &Tetromino) -> Position {
+    let bottom_offset = board
+        .piece_set
+        .blocks(tetromino)
+        .iter()
+        .map(|&(_, y)| y)
+        .max()
+        .unwrap_or(0);
+    Position {
+        x: i32::try_from((board.width - 1) / 2).unwrap_or(0),
+        y: -1 - bottom_offset,
+    }
+}
+
+// Spawns a piece, in whatever rotation it comes in, replacing the active one
+fn spawn_piece(world: &mut World, tetromino: Tetromino) {
     // First, despawn any existing tetromino entities to avoid multiple tetrominos on screen
     let entities_to_despawn: Vec<Entity> = world
         .query::<(Entity, &Tetromino)>()
@@ -50,43 +152,63 @@ pub fn spawn_tetromino(world: &mut World) {
         input.hard_drop_released = was_hard_drop_released;
     }
 
-    // Get the next tetromino from game state or generate a random one if none exists
-    let tetromino_type = {
-        let mut game_state = world.resource_mut::<GameState>();
-        game_state
-            .next_tetromino
-            .take()
-            .unwrap_or_else(TetrominoType::random)
-    };
+    let board = world.resource::<Board>();
+    let position = spawn_position(board, &tetromino);
 
-    // Generate the next tetromino for the future
-    {
-        let mut game_state = world.resource_mut::<GameState>();
-        game_state.next_tetromino = Some(TetrominoType::random());
+    // Game over if we can't spawn a new tetromino
+    if !board.is_valid_position(position, &tetromino) && top_out(world, TopOut::BlockOut) {
+        return;
     }
 
-    let tetromino = Tetromino::new(tetromino_type);
-
-    // Start position at the top center of the board
-    let position = Position {
-        x: i32::try_from(BOARD_WIDTH / 2).unwrap_or(0),
-        y: 0,
+    // The piece drops into view straight away if there is room, and at 20G it arrives
+    // already resting on the stack
+    let rows = if world.resource::<GameState>().gravity() >= MAX_GRAVITY {
+        world.resource::<Board>().height as u32
+    } else {
+        1
     };
+    let position = world.resource::<Board>().fall(position, &tetromino, rows);
 
-    // Check if spawn position is valid
-    let board = world.resource::<Board>();
-    if !board.is_valid_position(position, &tetromino) {
-        // Game over if we can't spawn a new tetromino
-        let mut game_state = world.resource_mut::<GameState>();
-        game_state.game_over = true;
-        return;
+    // The new piece gets a fresh lock delay with all of its resets, and its keys are counted afresh
+    if let Some(mut lock_delay) = world.get_resource_mut::<LockDelay>() {
+        lock_delay.start_piece(position.y);
+    }
+    if let Some(mut finesse) = world.get_resource_mut::<Finesse>() {
+        finesse.start_piece();
     }
 
     // Create the ghost piece at the same initial position
     let ghost = Ghost { position };
 
     // Spawn the tetromino entity with a ghost
-    world.spawn((tetromino, position, ghost));
+    world.spawn((tetromino, position, ghost, LastAction::default()));
+}
+
+// Ends the game for the given reason and returns true, or for modes without a top-out clears the
+// stack and returns false so play carries on
+fn top_out(world: &mut World, reason: TopOut) -> bool {
+    let tops_out = world
+        .get_resource::<GameMode>()
+        .is_none_or(|mode| mode.kind.tops_out());
+
+    if !tops_out {
+        info!(
+            "{} in a mode without top-out, clearing the stack",
+            reason.description()
+        );
+        world.resource_mut::<Board>().clear();
+        return false;
+    }
+
+    info!("Topped out: {}", reason.description());
+    let mut game_state = world.resource_mut::<GameState>();
+    game_state.game_over = true;
+    game_state.top_out = Some(reason);
+    if let Some(mut mode) = world.get_resource_mut::<GameMode>() {
+        mode.result = Some(ModeResult::ToppedOut);
+    }
+    events::send(world, events::TopOut);
+    true
 }
 
 // Helper function to check if a tetromino can continue falling
@@ -103,44 +225,48 @@ fn can_continue_falling(world: &mut World, position: &Position, tetromino: &Tetr
 pub fn input_system(world: &mut World) {
     // Clone resources to avoid borrowing issues
     let input = world.resource::<Input>().clone();
-    let screen_shake = world.resource::<ScreenShake>().clone();
 
     // Log input state
     debug!(
-        "Input state: left={}, right={}, down={}, rotate={}, hard_drop={}, hard_drop_released={}",
+        "Input state: left={}, right={}, down={}, rotate={}, rotate_ccw={}, rotate_180={}, hard_drop={}, hard_drop_released={}",
         input.left,
         input.right,
         input.down,
         input.rotate,
+        input.rotate_ccw,
+        input.rotate_180,
         input.hard_drop,
         input.hard_drop_released
     );
 
-    // Skip inputs if game is paused for resize
+    // Skip inputs while the game is paused
     let game_state = world.resource::<GameState>();
-    if game_state.was_paused_for_resize {
-        debug!("Game paused for resize, skipping input");
+    if game_state.is_frozen() {
+        debug!("Game paused, skipping input");
         return;
     }
 
     // Track when this move occurred
     {
+        let frame = world.get_resource::<Time>().map_or(0, Time::frame);
         let mut game_state = world.resource_mut::<GameState>();
-        game_state.last_move = std::time::Instant::now();
+        game_state.last_move = frame;
     }
 
-    // Check if screen shake is active
-    if screen_shake.is_active {
-        // If screen shake is active, ignore inputs
-        debug!("Screen shake active, ignoring inputs");
-        return;
+    if let Some(mut stats) = world.get_resource_mut::<GameStats>() {
+        stats.record_keys(&input);
     }
 
-    // Get coyote time status
-    let coyote_time_active = {
-        let game_state = world.resource::<GameState>();
-        game_state.coyote_time_active
-    };
+    // Between pieces, rotation and hold are saved for the next piece to spawn with
+    if world
+        .get_resource::<Phase>()
+        .is_some_and(Phase::is_between_pieces)
+    {
+        world
+            .get_resource_or_insert_with(InitialInputs::default)
+            .record(&input);
+        return;
+    }
 
     // First, check if there's an active tetromino
     let has_active_tetromino;
@@ -161,6 +287,18 @@ pub fn input_system(world: &mut World) {
         return;
     }
 
+    // Handle hold separately since it replaces the active tetromino
+    if input.hold {
+        debug!("Hold input detected, swapping with hold slot");
+        handle_hold(world);
+        return;
+    }
+
+    // The moves and rotations below count towards the piece's finesse
+    if let Some(mut finesse) = world.get_resource_mut::<Finesse>() {
+        finesse.record_input(&input);
+    }
+
     // Get the active tetromino
     let mut entity_id = None;
     let mut tetromino_clone = None;
@@ -176,7 +314,7 @@ pub fn input_system(world: &mut World) {
     }
 
     // If no tetromino found, exit early
-    let (Some(entity), Some(tetromino), Some(position)) =
+    let (Some(entity), Some(tetromino), Some(mut position)) =
         (entity_id, tetromino_clone, position_clone)
     else {
         return;
@@ -185,150 +323,242 @@ pub fn input_system(world: &mut World) {
     // Handle horizontal movement
     if input.left || input.right {
         let dx = if input.left { -1 } else { 1 };
-        let new_position = Position {
-            x: position.x + dx,
-            y: position.y,
-        };
+        if let Some(new_position) = shift_tetromino(world, entity, &tetromino, position, dx) {
+            position = new_position;
+        }
+    }
 
-        // Check if the move is valid
-        let can_move = {
+    // Handle soft drop, a piece that can't fall any further is left to the lock delay
+    if input.down {
+        position = soft_drop_tetromino(world, entity, &tetromino, position).unwrap_or(position);
+    }
+
+    // Handle rotation, all three directions share the same kick handling
+    if input.rotate || input.rotate_ccw || input.rotate_180 {
+        let mut new_tetromino = tetromino;
+        if input.rotate {
+            new_tetromino.rotate();
+        } else if input.rotate_ccw {
+            new_tetromino.rotate_counter_clockwise();
+        } else {
+            new_tetromino.rotate_180();
+        }
+
+        // Try the SRS wall kicks in order until one fits
+        let kick = {
             let board = world.resource::<Board>();
-            board.is_valid_position(new_position, &tetromino)
+            board.find_kick(position, &tetromino, &new_tetromino)
         };
 
-        if can_move {
-            // Also check if piece can still move down
+        if let Some((kicked_position, kick_index)) = kick {
+            debug!("Rotation succeeded with kick test {kick_index}");
+
+            // Check if piece can still move down after rotation
             let can_move_down = {
                 let down_pos = Position {
-                    x: new_position.x,
-                    y: new_position.y + 1,
+                    x: kicked_position.x,
+                    y: kicked_position.y + 1,
                 };
                 let board = world.resource::<Board>();
-                board.is_valid_position(down_pos, &tetromino)
+                board.is_valid_position(down_pos, &new_tetromino)
             };
 
-            // Update position
-            world.entity_mut(entity).insert(new_position);
+            // Update tetromino and its (possibly kicked) position, remembering the kick for T-spins
+            world.entity_mut(entity).insert((
+                new_tetromino,
+                kicked_position,
+                LastAction::Rotate { kick: kick_index },
+            ));
 
-            // Update ghost position
-            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
-                if let Some(mut ghost) = entity_mut.get_mut::<Ghost>() {
-                    ghost.position.x += dx;
-                }
+            // Add rotation effect
+            let roll = world.get_resource_or_insert_with(GameRng::default).f32();
+            if roll < 0.3 {
+                // Only 30% chance to spawn particles for rotation
+                particles::spawn_rotation_particles(world, kicked_position, &new_tetromino);
             }
 
-            // Only spawn coyote time particles if we can't move down
-            if coyote_time_active && !can_move_down {
-                debug!(
-                    "Spawning coyote time particles due to horizontal movement during coyote time"
-                );
-                particles::spawn_coyote_time_particles(world, new_position, &tetromino);
+            // A successful rotation on the ground restarts the lock delay
+            let lock_delay_active = {
+                let mut lock_delay = world.resource_mut::<LockDelay>();
+                lock_delay.register_move();
+                lock_delay.active
+            };
+
+            // Only spawn lock delay particles if we can't move down
+            if lock_delay_active && !can_move_down {
+                debug!("Spawning lock delay particles due to rotation during lock delay");
+                particles::spawn_lock_delay_particles(world, kicked_position, &new_tetromino);
             }
 
-            // Play movement sound effect
-            let audio_state = world.resource::<AudioState>();
-            audio_state.play_sound(SoundEffect::Move);
+            // Play rotation sound effect
+            play_sound(world, SoundEffect::Rotate);
         }
     }
+}
 
-    // Handle soft drop
-    if input.down {
-        let new_position = Position {
-            x: position.x,
-            y: position.y + 1,
-        };
+// Moves the active tetromino one cell sideways, returning its new position if the move fit
+fn shift_tetromino(
+    world: &mut World,
+    entity: Entity,
+    tetromino: &Tetromino,
+    position: Position,
+    dx: i32,
+) -> Option<Position> {
+    let new_position = Position {
+        x: position.x + dx,
+        y: position.y,
+    };
 
-        // Check if the move is valid
-        let can_move_down = {
-            let board = world.resource::<Board>();
-            board.is_valid_position(new_position, &tetromino)
+    // Check if the move is valid
+    let can_move = {
+        let board = world.resource::<Board>();
+        board.is_valid_position(new_position, tetromino)
+    };
+
+    if !can_move {
+        return None;
+    }
+
+    // Also check if piece can still move down
+    let can_move_down = {
+        let down_pos = Position {
+            x: new_position.x,
+            y: new_position.y + 1,
         };
+        let board = world.resource::<Board>();
+        board.is_valid_position(down_pos, tetromino)
+    };
 
-        if can_move_down {
-            // Update position
-            world.entity_mut(entity).insert(new_position);
+    // Update position
+    world
+        .entity_mut(entity)
+        .insert((new_position, LastAction::Move));
 
-            // Track soft drop distance for scoring
-            let mut game_state = world.resource_mut::<GameState>();
-            game_state.soft_drop_distance += 1;
-
-            // Reset drop timer to avoid immediate auto-drop
-            game_state.drop_timer = 0.0;
-
-            // Clear any existing coyote time state since we can move down
-            if game_state.coyote_time_active {
-                game_state.coyote_time_active = false;
-                game_state.coyote_time_timer = 0.0;
-
-                // Also reset the coyote time resource
-                let mut coyote_time = world.resource_mut::<CoyoteTime>();
-                coyote_time.active = false;
-                coyote_time.timer = 0.0;
-
-                // Clear any existing coyote time particles
-                let particles_to_remove: Vec<Entity> = world
-                    .query::<(Entity, &Particle)>()
-                    .iter(world)
-                    .filter(|(_, p)| p.color == ratatui::style::Color::White)
-                    .map(|(e, _)| e)
-                    .collect();
-
-                for entity in particles_to_remove {
-                    world.despawn(entity);
-                }
-            }
+    // Update ghost position
+    if let Ok(mut entity_mut) = world.get_entity_mut(entity)
+        && let Some(mut ghost) = entity_mut.get_mut::<Ghost>()
+    {
+        ghost.position.x += dx;
+    }
 
-            // Play soft drop sound effect
-            let audio_state = world.resource::<AudioState>();
-            audio_state.play_sound(SoundEffect::SoftDrop);
-        } else {
-            // When we can't move down during soft drop, we should lock immediately
-            // instead of activating coyote time
-            handle_piece_lock(world, entity, position, &tetromino);
-            return; // Exit early to prevent any other movement processing
-        }
+    // A successful move on the ground restarts the lock delay
+    let lock_delay_active = {
+        let mut lock_delay = world.resource_mut::<LockDelay>();
+        lock_delay.register_move();
+        lock_delay.active
+    };
+
+    // Only spawn lock delay particles if we can't move down
+    if lock_delay_active && !can_move_down {
+        debug!("Spawning lock delay particles due to horizontal movement during lock delay");
+        particles::spawn_lock_delay_particles(world, new_position, tetromino);
     }
 
-    // Handle rotation
-    if input.rotate {
-        let mut new_tetromino = tetromino;
-        new_tetromino.rotate();
+    // Play movement sound effect
+    play_sound(world, SoundEffect::Move);
 
-        // Check if the rotation is valid
-        let can_rotate = {
-            let board = world.resource::<Board>();
-            board.is_valid_position(position, &new_tetromino)
-        };
+    Some(new_position)
+}
 
-        if can_rotate {
-            // Check if piece can still move down after rotation
-            let can_move_down = {
-                let down_pos = Position {
-                    x: position.x,
-                    y: position.y + 1,
-                };
-                let board = world.resource::<Board>();
-                board.is_valid_position(down_pos, &new_tetromino)
-            };
+// Moves the active tetromino one cell down for a soft drop, returning its new position if it fit
+fn soft_drop_tetromino(
+    world: &mut World,
+    entity: Entity,
+    tetromino: &Tetromino,
+    position: Position,
+) -> Option<Position> {
+    let new_position = Position {
+        x: position.x,
+        y: position.y + 1,
+    };
 
-            // Update tetromino
-            world.entity_mut(entity).insert(new_tetromino);
+    // Check if the move is valid
+    let can_move_down = {
+        let board = world.resource::<Board>();
+        board.is_valid_position(new_position, tetromino)
+    };
 
-            // Add rotation effect
-            if fastrand::f32() < 0.3 {
-                // Only 30% chance to spawn particles for rotation
-                particles::spawn_rotation_particles(world, position, &new_tetromino);
-            }
+    if !can_move_down {
+        return None;
+    }
 
-            // Only spawn coyote time particles if we can't move down
-            if coyote_time_active && !can_move_down {
-                debug!("Spawning coyote time particles due to rotation during coyote time");
-                particles::spawn_coyote_time_particles(world, position, &new_tetromino);
-            }
+    // Update position
+    world
+        .entity_mut(entity)
+        .insert((new_position, LastAction::Move));
 
-            // Play rotation sound effect
-            let audio_state = world.resource::<AudioState>();
-            audio_state.play_sound(SoundEffect::Rotate);
+    // Track soft drop distance for scoring
+    let mut game_state = world.resource_mut::<GameState>();
+    game_state.soft_drop_distance += 1;
+
+    // Restart gravity to avoid an immediate auto-drop
+    game_state.gravity_progress = 0.0;
+
+    // Play soft drop sound effect
+    play_sound(world, SoundEffect::SoftDrop);
+
+    Some(new_position)
+}
+
+// Repeats held movement keys: sideways after DAS at the ARR rate, and soft drop at the soft drop factor
+pub fn auto_shift_system(world: &mut World, delta_seconds: f32) {
+    let Some(auto_shift) = world.get_resource::<AutoShift>() else {
+        return;
+    };
+    if auto_shift.direction == 0 && !auto_shift.down_held {
+        return;
+    }
+
+    // Held keys are ignored under the same conditions as presses
+    {
+        let game_state = world.resource::<GameState>();
+        if game_state.game_over || game_state.is_frozen() {
+            return;
+        }
+    }
+    let handling = world
+        .get_resource::<Handling>()
+        .copied()
+        .unwrap_or_default();
+    let das = handling.das_ms as f32 / 1000.0;
+    let arr = handling.arr_ms as f32 / 1000.0;
+    let soft_drop_interval =
+        world.resource::<GameState>().get_drop_delay() / handling.soft_drop_factor.max(1) as f32;
+
+    let (direction, horizontal_steps, soft_drop_steps) = {
+        let mut auto_shift = world.resource_mut::<AutoShift>();
+        let horizontal_steps = auto_shift.horizontal_steps(delta_seconds, das, arr);
+        let soft_drop_steps = auto_shift.soft_drop_steps(delta_seconds, soft_drop_interval);
+        (auto_shift.direction, horizontal_steps, soft_drop_steps)
+    };
+
+    let Some((entity, tetromino, mut position)) = world
+        .query::<(Entity, &Tetromino, &Position)>()
+        .iter(world)
+        .next()
+        .map(|(entity, tetromino, position)| (entity, *tetromino, *position))
+    else {
+        return;
+    };
+
+    // A piece can never travel further than the board, which also bounds an ARR of zero
+    let (board_width, board_height) = {
+        let board = world.resource::<Board>();
+        (board.width as u32, board.height as u32)
+    };
+
+    for _ in 0..horizontal_steps.min(board_width) {
+        match shift_tetromino(world, entity, &tetromino, position, direction) {
+            Some(new_position) => position = new_position,
+            None => break,
+        }
+    }
+
+    // Once the piece lands, locking is left to the lock delay
+    for _ in 0..soft_drop_steps.min(board_height) {
+        match soft_drop_tetromino(world, entity, &tetromino, position) {
+            Some(new_position) => position = new_position,
+            None => break,
         }
     }
 }
@@ -390,12 +620,6 @@ fn handle_hard_drop(world: &mut World) {
 
     debug!("Hard drop distance: {}", hard_drop_distance);
 
-    // If there's nowhere to drop, just return
-    if hard_drop_distance == 0 {
-        debug!("Hard drop distance is 0, nothing to do");
-        return;
-    }
-
     // Update the game state with the hard drop distance for scoring
     {
         let mut game_state = world.resource_mut::<GameState>();
@@ -411,33 +635,74 @@ fn handle_hard_drop(world: &mut World) {
 
     debug!("Final position: {:?}", final_position);
 
-    // Lock the tetromino at the final position
-    {
-        let mut board = world.resource_mut::<Board>();
-        board.lock_tetromino(final_position, &tetromino);
-        debug!("Locked tetromino at final position");
+    // Falling any distance counts as a move, so a T that drops after rotating isn't a T-spin
+    if hard_drop_distance > 0 {
+        world
+            .entity_mut(entity)
+            .insert((final_position, LastAction::Move));
     }
 
-    // Spawn particles for the locked tetromino
-    particles::spawn_lock_particles(world, final_position, &tetromino);
-    debug!("Spawned lock particles");
-
-    // Remove the tetromino and spawn a new one
-    world.despawn(entity);
-    debug!("Despawned old tetromino");
-    spawn_tetromino(world);
-    debug!("Spawned new tetromino");
+    // Lock the tetromino at the final position, clearing lines and spawning the next one
+    handle_piece_lock(world, entity, final_position, &tetromino);
+    debug!("Locked tetromino at final position");
 
     // Play hard drop sound effect
-    let audio_state = world.resource::<AudioState>();
-    audio_state.play_sound(SoundEffect::HardDrop);
+    play_sound(world, SoundEffect::HardDrop);
     debug!("Played hard drop sound effect");
 }
 
+// Swaps the active tetromino with the hold slot, at most once per locked piece
+fn handle_hold(world: &mut World) {
+    if world.resource::<GameState>().hold_used {
+        debug!("Hold already used for this piece, ignoring");
+        return;
+    }
+
+    // Get the active tetromino
+    let active = {
+        let mut query = world.query::<(Entity, &Tetromino)>();
+        query
+            .iter(world)
+            .next()
+            .map(|(entity, tetromino)| (entity, tetromino.tetromino_type))
+    };
+
+    let Some((entity, active_type)) = active else {
+        debug!("No tetromino found, exiting hold");
+        return;
+    };
+
+    // Put the active piece into the hold slot and take whatever was there before
+    let previously_held = {
+        let mut game_state = world.resource_mut::<GameState>();
+        let previously_held = game_state.held_tetromino.replace(active_type);
+
+        // The swapped-in piece starts fresh, spawning it also restarts the lock delay
+        game_state.gravity_progress = 0.0;
+
+        previously_held
+    };
+
+    world.despawn(entity);
+
+    // An empty hold slot pulls the next piece from the preview instead
+    match previously_held {
+        Some(held_type) => spawn_tetromino_of_type(world, held_type),
+        None => spawn_tetromino(world),
+    }
+
+    // Block further swaps until the new piece locks
+    world.resource_mut::<GameState>().hold_used = true;
+
+    events::send(world, HoldUsed);
+}
+
 /// Process audio controls (music toggle, volume adjustments)
 fn process_audio_controls(world: &mut World) {
     let input = world.resource::<Input>().clone();
-    let mut audio_state = world.resource_mut::<AudioState>();
+    let Some(mut audio_state) = world.get_resource_mut::<AudioState>() else {
+        return;
+    };
 
     // Handle music toggle
     if input.toggle_music {
@@ -502,216 +767,297 @@ fn update_ghost_positions(world: &mut World) {
     }
 }
 
-pub fn game_tick_system(world: &mut World, delta_seconds: f32) {
-    // Process audio controls first
-    process_audio_controls(world);
-
-    // Log delta seconds for debugging
-    trace!("Game tick with delta: {delta_seconds}");
+/// The stages of a frame, run in this order
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Input,
+    Movement,
+    Gravity,
+    Lock,
+    LineClear,
+    Scoring,
+    Effects, // Sound, particles and stats, following the game events of the frame
+}
 
-    // Update particles first, regardless of game state
-    particles::update_particles(world, delta_seconds);
+/// The schedule `step_frame` runs
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FrameSchedule;
+
+/// Builds the schedule of a frame, with the game systems in their sets
+#[must_use]
+pub fn frame_schedule() -> Schedule {
+    let mut schedule = Schedule::new(FrameSchedule);
+    // Systems run one after another in a fixed order, so replays play out the same every time
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    schedule.configure_sets(
+        (
+            GameSet::Input,
+            GameSet::Movement,
+            GameSet::Gravity,
+            GameSet::Lock,
+            GameSet::LineClear,
+            GameSet::Scoring,
+            GameSet::Effects,
+        )
+            .chain(),
+    );
 
-    // Check if game is over
-    let game_over = {
-        let game_state = world.resource::<GameState>();
-        game_state.game_over
-    };
+    schedule.add_systems(
+        (events::update_events, input_system, process_audio_controls)
+            .chain()
+            .in_set(GameSet::Input),
+    );
+    schedule.add_systems(
+        (|world: &mut World| auto_shift_system(world, FIXED_TIMESTEP)).in_set(GameSet::Movement),
+    );
+    schedule.add_systems(
+        (
+            (|world: &mut World| particles::update_particles(world, FIXED_TIMESTEP))
+                .run_if(game_unfrozen),
+            (
+                update_ghost_positions,
+                (|world: &mut World| gravity_system(world, FIXED_TIMESTEP)).run_if(piece_in_play),
+            )
+                .chain()
+                .run_if(game_running),
+        )
+            .chain()
+            .in_set(GameSet::Gravity),
+    );
+    schedule.add_systems(
+        (|world: &mut World| update_lock_delay(world, FIXED_TIMESTEP))
+            .run_if(game_running)
+            .in_set(GameSet::Lock),
+    );
+    // The countdown after resuming runs out once the frozen frame's systems have been skipped
+    schedule.add_systems(
+        (
+            (|world: &mut World| update_phase(world, FIXED_TIMESTEP)).run_if(game_running),
+            |world: &mut World| update_resume_countdown(world, FIXED_TIMESTEP),
+        )
+            .chain()
+            .in_set(GameSet::LineClear),
+    );
+    schedule.add_systems(
+        (|world: &mut World| game_mode_system(world, FIXED_TIMESTEP)).in_set(GameSet::Scoring),
+    );
+    schedule.add_systems(
+        (
+            sound::sound_system,
+            particles::particle_system,
+            stats::stats_system,
+            update_music_for_level.run_if(game_running),
+        )
+            .chain()
+            .in_set(GameSet::Effects),
+    );
+    schedule
+}
 
-    if game_over {
-        return;
-    }
+// Nothing moves while paused, and after resuming the countdown runs out first
+#[allow(clippy::needless_pass_by_value)]
+fn game_unfrozen(game_state: Res<GameState>) -> bool {
+    !game_state.is_frozen()
+}
 
-    // Update ghost positions
-    update_ghost_positions(world);
+#[allow(clippy::needless_pass_by_value)]
+fn game_running(game_state: Res<GameState>) -> bool {
+    !game_state.game_over && !game_state.is_frozen()
+}
 
-    // Update music based on current level
-    update_music_for_level(world);
+// Between pieces the board stays still while the line clear and entry delays run out
+#[allow(clippy::needless_pass_by_value)]
+fn piece_in_play(phase: Option<Res<Phase>>) -> bool {
+    phase.is_none_or(|phase| !phase.is_between_pieces())
+}
 
-    // Update coyote time
-    let coyote_time_expired = {
-        // First get the current state
-        let is_active = {
-            let game_state = world.resource::<GameState>();
-            game_state.coyote_time_active
-        };
+/// Runs one fixed-length frame of the simulation with the input latched since the last one
+pub fn step_frame(world: &mut World) {
+    // A world gets the frame schedule and the game event queues the first time it's stepped
+    let scheduled = world
+        .get_resource::<Schedules>()
+        .is_some_and(|schedules| schedules.contains(FrameSchedule));
+    if !scheduled {
+        events::register(world);
+        world.add_schedule(frame_schedule());
+    }
+    world.run_schedule(FrameSchedule);
 
-        if is_active {
-            // Update coyote time first
-            let mut coyote_time = world.resource_mut::<CoyoteTime>();
-            coyote_time.active = true;
-            coyote_time.timer += delta_seconds;
-            let timer_value = coyote_time.timer;
-            let should_expire = timer_value >= crate::game::COYOTE_TIME_DURATION;
-
-            if should_expire {
-                coyote_time.active = false;
-                coyote_time.timer = 0.0;
-            }
+    // Each key press is only seen by one frame
+    *world.resource_mut::<Input>() = Input::default();
+}
 
-            // Then update game state
-            let mut game_state = world.resource_mut::<GameState>();
-            game_state.coyote_time_timer = timer_value;
-
-            if should_expire {
-                debug!("Coyote time expired");
-                game_state.coyote_time_active = false;
-                game_state.coyote_time_timer = 0.0;
-                true
-            } else {
-                false
-            }
-        } else {
-            // Reset both states
-            {
-                let mut coyote_time = world.resource_mut::<CoyoteTime>();
-                coyote_time.active = false;
-                coyote_time.timer = 0.0;
-            }
-            false
-        }
+// Runs the game mode's clock and ends the game once its goal is reached
+pub fn game_mode_system(world: &mut World, delta_seconds: f32) {
+    let (game_over, paused) = {
+        let game_state = world.resource::<GameState>();
+        (game_state.game_over, game_state.is_frozen())
     };
+    if game_over || paused {
+        return;
+    }
 
-    // If coyote time just expired, handle tetromino locking
-    if coyote_time_expired {
-        let mut tetromino_entity = None;
-        let mut tetromino_data = None;
-        let mut position_data = None;
-
-        // Collect the active tetromino data
-        {
-            let mut entities = world.query::<(Entity, &Tetromino, &Position)>();
-            if let Some((entity, tetromino, position)) = entities.iter(world).next() {
-                tetromino_entity = Some(entity);
-                tetromino_data = Some(*tetromino);
-                position_data = Some(*position);
-            }
+    let completed = world.resource_scope(|world, mut mode: Mut<GameMode>| {
+        mode.update(world.resource::<GameState>(), delta_seconds)
+    });
+
+    if completed {
+        info!("Game mode completed");
+        world.resource_mut::<GameState>().game_over = true;
+
+        // Clear away the active piece so the results are shown over a still board
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, With<Tetromino>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.despawn(entity);
         }
 
-        if let (Some(entity), Some(tetromino), Some(position)) =
-            (tetromino_entity, tetromino_data, position_data)
-        {
-            // Before locking, check once more if the piece can continue falling
-            if can_continue_falling(world, &position, &tetromino) {
-                // If the piece can move down, update its position and continue
-                debug!("Piece can continue falling after coyote time expired");
-                let new_position = Position {
-                    x: position.x,
-                    y: position.y + 1,
-                };
-                world.entity_mut(entity).insert(new_position);
-
-                // Reset drop timer to give player a moment before next drop
-                let mut game_state = world.resource_mut::<GameState>();
-                game_state.drop_timer = 0.0;
-            } else {
-                // Handle locking the piece
-                handle_piece_lock(world, entity, position, &tetromino);
-            }
-        }
-        return;
+        play_sound(world, SoundEffect::LevelUp);
     }
+}
 
-    // Update drop timer
-    let should_drop = {
+// Runs down the countdown after resuming, nothing moves until it's over
+fn update_resume_countdown(world: &mut World, delta_seconds: f32) {
+    let mut game_state = world.resource_mut::<GameState>();
+    if !game_state.paused && !game_state.was_paused_for_resize {
+        game_state.resume_countdown = (game_state.resume_countdown - delta_seconds).max(0.0);
+    }
+}
+
+// Banks the tick's gravity and falls the active piece every whole row banked
+fn gravity_system(world: &mut World, delta_seconds: f32) {
+    // Bank this tick's gravity, every whole row banked is fallen straight away
+    let rows = {
         let mut game_state = world.resource_mut::<GameState>();
+        let gravity = game_state.gravity();
+        game_state.gravity_progress += gravity * delta_seconds * FRAMES_PER_SECOND;
+        let rows = game_state.gravity_progress.floor();
+        game_state.gravity_progress -= rows;
 
-        // Don't update drop timer if coyote time is active
-        if game_state.coyote_time_active {
-            false
-        } else {
-            // Add the elapsed time to our drop timer
-            game_state.drop_timer += delta_seconds;
+        trace!("Gravity: {gravity}G, falling {rows} rows");
+        rows as u32
+    };
 
-            // Get the drop delay based on level
-            let drop_delay = game_state.get_drop_delay();
+    // Handle automatic falling
+    if rows == 0 {
+        return;
+    }
+    let active = world
+        .query::<(Entity, &Tetromino, &Position)>()
+        .iter(world)
+        .next()
+        .map(|(entity, tetromino, position)| (entity, *tetromino, *position));
 
-            // Debug log
-            trace!(
-                "Drop timer: {}, Drop delay: {}",
-                game_state.drop_timer, drop_delay
-            );
+    // No active tetromino, spawn one
+    let Some((entity, tetromino, position)) = active else {
+        debug!("No active tetromino, spawning a new one");
+        spawn_tetromino(world);
+        return;
+    };
 
-            // Check if it's time to drop the tetromino
-            let should_drop = game_state.drop_timer >= drop_delay;
+    // A piece can never fall further than the board, which also bounds 20G
+    let new_position = {
+        let board = world.resource::<Board>();
+        board.fall(position, &tetromino, rows.min(board.height as u32))
+    };
 
-            // Reset timer if dropping
-            if should_drop {
-                game_state.drop_timer = 0.0;
-                debug!("Dropping tetromino!");
-            }
+    if new_position == position {
+        // Resting pieces don't bank gravity for when they slide off a ledge
+        world.resource_mut::<GameState>().gravity_progress = 0.0;
+    } else {
+        debug!("Moving tetromino down {} rows", new_position.y - position.y);
+        world
+            .entity_mut(entity)
+            .insert((new_position, LastAction::Move));
+    }
+}
 
-            should_drop
-        }
+// Advances the lock delay of the active tetromino and locks it when the delay expires
+fn update_lock_delay(world: &mut World, delta_seconds: f32) {
+    let Some((entity, tetromino, position)) = world
+        .query::<(Entity, &Tetromino, &Position)>()
+        .iter(world)
+        .next()
+        .map(|(entity, tetromino, position)| (entity, *tetromino, *position))
+    else {
+        return;
     };
 
-    // Handle automatic falling
-    if should_drop {
-        // Collect the active tetromino data
-        let mut tetromino_entity = None;
-        let mut tetromino_data = None;
-        let mut position_data = None;
-
-        {
-            let mut entities = world.query::<(Entity, &Tetromino, &Position)>();
+    let grounded = !can_continue_falling(world, &position, &tetromino);
 
-            // Log how many entities we have for debugging
-            let count = entities.iter(world).count();
-            debug!("Found {count} tetromino entities");
+    let (should_lock, just_landed) = {
+        let mut lock_delay = world.resource_mut::<LockDelay>();
+        lock_delay.reach_row(position.y);
 
-            if let Some((entity, tetromino, position)) = entities.iter(world).next() {
-                tetromino_entity = Some(entity);
-                tetromino_data = Some(*tetromino);
-                position_data = Some(*position);
-            }
-        }
+        let was_active = lock_delay.active;
+        let should_lock = lock_delay.tick(grounded, delta_seconds);
+        (should_lock, !was_active && lock_delay.active)
+    };
 
-        // No active tetromino, spawn one
-        if tetromino_entity.is_none() {
-            debug!("No active tetromino, spawning a new one");
-            spawn_tetromino(world);
-            return;
-        }
+    world.insert_resource(if grounded {
+        Phase::Locking
+    } else {
+        Phase::Falling
+    });
 
-        let entity = tetromino_entity.unwrap();
-        let tetromino = tetromino_data.unwrap();
-        let position = position_data.unwrap();
+    if just_landed {
+        debug!("Tetromino landed, lock delay started");
 
-        trace!(
-            "Moving tetromino at position ({}, {})",
-            position.x, position.y
-        );
+        // Spawn initial lock delay particles to give visual feedback
+        particles::spawn_lock_delay_particles(world, position, &tetromino);
+    }
 
-        // Try to move tetromino down
-        let new_position = Position {
-            x: position.x,
-            y: position.y + 1,
-        };
+    if should_lock {
+        debug!("Lock delay expired");
+        handle_piece_lock(world, entity, position, &tetromino);
+    }
+}
 
-        let can_move_down = {
-            let board = world.resource::<Board>();
-            board.is_valid_position(new_position, &tetromino)
-        };
+// The phase timings of the game, the defaults when it has none
+fn timings(world: &World) -> Timings {
+    world.get_resource::<Timings>().copied().unwrap_or_default()
+}
 
-        if can_move_down {
-            // Update position
-            debug!("Moving tetromino down");
-            world.entity_mut(entity).insert(new_position);
-        } else {
-            // Instead of locking immediately, activate coyote time if not already active
-            let mut game_state = world.resource_mut::<GameState>();
-            if !game_state.coyote_time_active {
-                debug!("Activating coyote time");
-                game_state.coyote_time_active = true;
-                game_state.coyote_time_timer = 0.0;
+// Runs the clock of the line clear or entry delay, moving on to the next phase once it runs out
+fn update_phase(world: &mut World, delta_seconds: f32) {
+    let timings = timings(world);
+    let Some(mut phase) = world.get_resource_mut::<Phase>() else {
+        return;
+    };
 
-                // Spawn initial coyote time particles to give visual feedback
-                particles::spawn_coyote_time_particles(world, position, &tetromino);
+    match &mut *phase {
+        Phase::Falling | Phase::Locking => {}
+        Phase::LineClear { timer, .. } => {
+            *timer += delta_seconds;
+            if *timer >= timings.line_clear_delay() {
+                debug!("Line clear delay over, removing the cleared rows");
+                world.resource_mut::<Board>().clear_lines_with_indices();
+                start_entry_delay(world);
+            }
+        }
+        Phase::EntryDelay { timer } => {
+            *timer += delta_seconds;
+            if *timer >= timings.entry_delay() {
+                debug!("Entry delay over, spawning the next piece");
+                world.insert_resource(Phase::Falling);
+                spawn_tetromino(world);
             }
         }
     }
 }
 
+// Waits out the entry delay before the next piece, or spawns it straight away without one
+fn start_entry_delay(world: &mut World) {
+    if timings(world).entry_delay() > 0.0 {
+        world.insert_resource(Phase::EntryDelay { timer: 0.0 });
+    } else {
+        world.insert_resource(Phase::Falling);
+        spawn_tetromino(world);
+    }
+}
+
 // Function to update background music based on the current level
 fn update_music_for_level(world: &mut World) {
     // Music system not implemented yet
@@ -721,75 +1067,132 @@ fn update_music_for_level(world: &mut World) {
 fn handle_piece_lock(world: &mut World, entity: Entity, position: Position, tetromino: &Tetromino) {
     info!("Locking tetromino in place");
 
+    // Finesse training sends a piece placed with a fault back to the top to be tried again
+    let board = world.resource::<Board>().clone();
+    let retry = world
+        .get_resource_mut::<Finesse>()
+        .is_some_and(|mut finesse| finesse.judge(&board, tetromino, position) && finesse.training);
+    if retry {
+        debug!("Finesse fault, retrying the piece");
+
+        // The retry starts out like a freshly dealt piece, nothing from the failed try carries over
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.hard_drop_distance = 0;
+            game_state.soft_drop_distance = 0;
+            game_state.hold_used = false;
+        }
+        world.remove_resource::<InitialInputs>();
+        world.insert_resource(Phase::Falling);
+        spawn_tetromino_of_type(world, tetromino.tetromino_type);
+        return;
+    }
+
     // Check for T-spin before locking
-    let is_t_spin = {
+    let last_action = world.get::<LastAction>(entity).copied().unwrap_or_default();
+    let t_spin = {
         let board = world.resource::<Board>();
-        GameState::is_t_spin(board, position, tetromino)
+        GameState::detect_t_spin(board, position, tetromino, last_action)
     };
 
-    // First lock the tetromino
-    {
-        let mut board = world.resource_mut::<Board>();
-        board.lock_tetromino(position, tetromino);
-    }
-
-    // Then spawn the lock particles
-    particles::spawn_lock_particles(world, position, tetromino);
+    // Rows above the visible field are negative, so a piece locking entirely above it locks out
+    let rows: Vec<i32> = board
+        .piece_set
+        .blocks(tetromino)
+        .iter()
+        .map(|&(_, y)| position.y + y)
+        .collect();
+    let locked_out = rows.iter().all(|&row| row < 0);
+    let partly_above = rows.iter().any(|&row| row < 0);
 
-    // Then clear lines and check for perfect clear
-    let (lines_cleared, is_perfect_clear, cleared_line_indices) = {
+    // First lock the tetromino, noting the holes beforehand to see how many it leaves
+    let holes_before = {
         let mut board = world.resource_mut::<Board>();
+        let holes_before = board.holes();
+        board.lock_tetromino(position, tetromino);
+        holes_before
+    };
 
-        // Clear completed lines and get their indices
-        let (lines_cleared, cleared_line_indices) = board.clear_lines_with_indices();
-
-        // Check for perfect clear
-        let is_perfect_clear = if lines_cleared > 0 {
-            GameState::is_perfect_clear(&board)
-        } else {
-            false
-        };
+    // Then clear lines on a copy of the board to score them. The full rows stay on the board
+    // itself while they flash for the line clear delay, and are removed straight away without one
+    let mut cleared_board = world.resource::<Board>().clone();
+    let (lines_cleared, cleared_line_indices) = cleared_board.clear_lines_with_indices();
+    let is_perfect_clear = lines_cleared > 0 && GameState::is_perfect_clear(&cleared_board);
+    let delay_clear = lines_cleared > 0 && timings(world).line_clear_delay() > 0.0;
+    if !delay_clear {
+        world.resource_mut::<Board>().clear_lines_with_indices();
+    }
 
-        (lines_cleared, is_perfect_clear, cleared_line_indices)
-    };
+    let new_holes = cleared_board.holes().saturating_sub(holes_before);
 
-    // Update score if needed
+    // Score the piece, an empty clear still scores a T-spin zero and resets the combo
     if lines_cleared > 0 {
         info!(
-            "Cleared {lines_cleared} lines (T-spin: {is_t_spin}, Perfect clear: {is_perfect_clear})"
+            "Cleared {lines_cleared} lines (T-spin: {t_spin:?}, Perfect clear: {is_perfect_clear})"
         );
-
+    } else if t_spin != TSpin::None {
+        info!("T-spin without lines ({t_spin:?})");
+    }
+    let (level_before, level) = {
         let mut game_state = world.resource_mut::<GameState>();
-        game_state.update_score(lines_cleared, is_t_spin, is_perfect_clear);
-
-        // Spawn line clear particles
-        particles::spawn_line_clear_particles(world, BOARD_WIDTH, &cleared_line_indices);
-
-        // Spawn special particles for perfect clears
-        if is_perfect_clear {
-            particles::spawn_perfect_clear_particles(world, BOARD_WIDTH, BOARD_HEIGHT);
-        }
+        let level_before = game_state.level;
+        game_state.update_score(lines_cleared, t_spin, is_perfect_clear);
+        (level_before, game_state.level)
+    };
 
-        // Play appropriate sound effect based on the type of clear
-        let audio_state = world.resource::<AudioState>();
-        if is_perfect_clear {
-            audio_state.play_sound(SoundEffect::PerfectClear);
-        } else if lines_cleared == 4 {
-            audio_state.play_sound(SoundEffect::Tetris);
-        } else if is_t_spin {
-            audio_state.play_sound(SoundEffect::TSpin);
-        } else {
-            audio_state.play_sound(SoundEffect::LineClear);
-        }
-    } else {
-        // Reset combo counter if no lines were cleared
-        let mut game_state = world.resource_mut::<GameState>();
-        game_state.combo_count = 0;
+    // Sound, particles and stats follow the lock through its events
+    events::send(
+        world,
+        PieceLocked {
+            tetromino: *tetromino,
+            position,
+            lines_cleared,
+            t_spin,
+            perfect_clear: is_perfect_clear,
+            new_holes,
+        },
+    );
+    if lines_cleared > 0 {
+        events::send(
+            world,
+            LinesCleared {
+                lines: lines_cleared,
+                rows: cleared_line_indices.clone(),
+                t_spin,
+                perfect_clear: is_perfect_clear,
+            },
+        );
+    }
+    if level > level_before {
+        events::send(world, LevelUp);
     }
 
     // Remove the old tetromino entity
     world.despawn(entity);
 
-    // Spawn a new tetromino
-    spawn_tetromino(world);
+    // Lock out is checked before the next piece spawns, partial lock out only when enabled and
+    // the piece's blocks are still above the field after its line clears
+    let partial_lock_out = world.resource::<GameState>().partial_lock_out
+        && partly_above
+        && !cleared_board.is_buffer_empty();
+    let reason = if locked_out {
+        Some(TopOut::LockOut)
+    } else if partial_lock_out {
+        Some(TopOut::PartialLockOut)
+    } else {
+        None
+    };
+    if reason.is_some_and(|reason| top_out(world, reason)) {
+        return;
+    }
+
+    // The next piece comes once the cleared rows have flashed and the entry delay is over
+    if delay_clear {
+        world.insert_resource(Phase::LineClear {
+            rows: cleared_line_indices,
+            timer: 0.0,
+        });
+    } else {
+        start_entry_delay(world);
+    }
 }
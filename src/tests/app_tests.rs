@@ -5,6 +5,7 @@ mod tests {
     use crate::app::App;
     use crate::components::{Board, GameState, Position, Tetromino, TetrominoType};
     use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
+    use crate::sound::AudioState;
 
     #[test]
     fn test_app_creation() {
@@ -96,4 +97,13 @@ mod tests {
         assert_eq!(game_state.combo_count, 0);
         assert!(!game_state.back_to_back);
     }
+
+    #[test]
+    fn test_config_is_only_saved_when_audio_hotkeys_change_it() {
+        let mut app = App::new();
+        assert!(!app.audio_settings_changed());
+
+        app.world.resource_mut::<AudioState>().toggle_music();
+        assert!(app.audio_settings_changed());
+    }
 }
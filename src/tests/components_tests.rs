@@ -35,6 +35,7 @@ mod position_tests {
 #[cfg(test)]
 mod tetromino_tests {
     use crate::components::{Tetromino, TetrominoType};
+    use crate::pieces::PieceSet;
 
     #[test]
     fn test_tetromino_creation() {
@@ -62,24 +63,26 @@ mod tetromino_tests {
 
     #[test]
     fn test_tetromino_get_blocks() {
+        let classic = PieceSet::classic();
+
         // Test I tetromino in default rotation
         let i_tetromino = Tetromino::new(TetrominoType::I);
-        let i_blocks = i_tetromino.get_blocks();
+        let i_blocks = classic.blocks(&i_tetromino);
 
         // I tetromino should have 4 blocks
         assert_eq!(i_blocks.len(), 4);
 
         // O tetromino should always have the same shape regardless of rotation
         let o_tetromino = Tetromino::new(TetrominoType::O);
-        let o_blocks_r0 = o_tetromino.get_blocks();
+        let o_spawn_blocks = classic.blocks(&o_tetromino);
 
         let mut rotated_o = o_tetromino;
         rotated_o.rotation = 1; // 90 degrees
-        let o_blocks_r90 = rotated_o.get_blocks();
+        let o_turned_blocks = classic.blocks(&rotated_o);
 
         // O tetromino should have 4 blocks
-        assert_eq!(o_blocks_r0.len(), 4);
-        assert_eq!(o_blocks_r90.len(), 4);
+        assert_eq!(o_spawn_blocks.len(), 4);
+        assert_eq!(o_turned_blocks.len(), 4);
     }
 
     #[test]
@@ -104,17 +107,93 @@ mod tetromino_tests {
         assert_eq!(i_tetromino.rotation, 0); // Back to 0 degrees
     }
 
+    #[test]
+    fn test_tetromino_rotate_counter_clockwise_and_180() {
+        let mut t_tetromino = Tetromino::new(TetrominoType::T);
+
+        // Counter-clockwise walks the rotation states backwards
+        t_tetromino.rotate_counter_clockwise();
+        assert_eq!(t_tetromino.rotation, 3);
+        t_tetromino.rotate_counter_clockwise();
+        assert_eq!(t_tetromino.rotation, 2);
+
+        // 180 degrees flips to the opposite state
+        t_tetromino.rotate_180();
+        assert_eq!(t_tetromino.rotation, 0);
+        t_tetromino.rotate_180();
+        assert_eq!(t_tetromino.rotation, 2);
+
+        // A clockwise turn undoes a counter-clockwise one
+        t_tetromino.rotate_counter_clockwise();
+        t_tetromino.rotate();
+        assert_eq!(t_tetromino.rotation, 2);
+    }
+
+    #[test]
+    fn test_spawn_states_are_flat() {
+        // Guideline spawn states lie flat across two rows at most
+        let classic = PieceSet::classic();
+        for t in [
+            TetrominoType::I,
+            TetrominoType::J,
+            TetrominoType::L,
+            TetrominoType::O,
+            TetrominoType::S,
+            TetrominoType::T,
+            TetrominoType::Z,
+        ] {
+            let blocks = classic.blocks(&Tetromino::new(t));
+            let min_y = blocks.iter().map(|&(_, y)| y).min().unwrap();
+            let max_y = blocks.iter().map(|&(_, y)| y).max().unwrap();
+            assert!(max_y - min_y <= 1, "{t:?} should spawn flat");
+        }
+
+        // The T spawns pointing up, with its center on the pivot
+        let t_blocks = classic.blocks(&Tetromino::new(TetrominoType::T));
+        assert!(t_blocks.contains(&(0, -1)));
+        assert!(t_blocks.contains(&(0, 0)));
+    }
+
+    #[test]
+    fn test_o_rotation_does_not_wobble() {
+        // Applying the O offset to a rotated O leaves its cells exactly in place
+        let classic = PieceSet::classic();
+        let mut o_tetromino = Tetromino::new(TetrominoType::O);
+        for _ in 0..4 {
+            let mut original = classic.blocks(&o_tetromino);
+            let mut rotated = o_tetromino;
+            rotated.rotate();
+
+            let kicks = classic.kick_tests(&o_tetromino, &rotated);
+            assert_eq!(kicks.len(), 1, "The O piece has a single kick test");
+
+            let (dx, dy) = kicks[0];
+            let mut moved: Vec<_> = classic
+                .blocks(&rotated)
+                .into_iter()
+                .map(|(x, y)| (x + dx, y + dy))
+                .collect();
+
+            original.sort_unstable();
+            moved.sort_unstable();
+            assert_eq!(original, moved);
+
+            o_tetromino = rotated;
+        }
+    }
+
     #[test]
     fn test_tetromino_color() {
         // Each tetromino type should have a distinct color
+        let classic = PieceSet::classic();
         let colors = [
-            TetrominoType::I.get_color(),
-            TetrominoType::J.get_color(),
-            TetrominoType::L.get_color(),
-            TetrominoType::O.get_color(),
-            TetrominoType::S.get_color(),
-            TetrominoType::T.get_color(),
-            TetrominoType::Z.get_color(),
+            classic.color(TetrominoType::I),
+            classic.color(TetrominoType::J),
+            classic.color(TetrominoType::L),
+            classic.color(TetrominoType::O),
+            classic.color(TetrominoType::S),
+            classic.color(TetrominoType::T),
+            classic.color(TetrominoType::Z),
         ];
 
         // This tests that colors are implemented, but doesn't check specific values
@@ -179,7 +258,12 @@ mod board_tests {
             x: BOARD_WIDTH as i32,
             y: 5,
         };
-        let out_bottom = Position { x: 5, y: -1 };
+        // Rows above the visible field are fine down to the top of the hidden buffer
+        assert!(board.is_valid_position(Position { x: 5, y: -1 }, &test_tetromino));
+        let out_bottom = Position {
+            x: 5,
+            y: board.top() - 1,
+        };
         let out_top = Position {
             x: 5,
             y: BOARD_HEIGHT as i32,
@@ -195,6 +279,74 @@ mod board_tests {
         assert!(!board.is_valid_position(valid_pos, &test_tetromino));
     }
 
+    #[test]
+    fn test_find_kick_open_board() {
+        let board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+
+        // JLSTZ pieces rotate in place when nothing is in the way
+        let t_tetromino = Tetromino::new(TetrominoType::T);
+        let mut rotated = t_tetromino;
+        rotated.rotate();
+        let position = Position { x: 4, y: 5 };
+        assert_eq!(
+            board.find_kick(position, &t_tetromino, &rotated),
+            Some((position, 0))
+        );
+
+        // The I piece offset shifts it one column right going from spawn to R
+        let i_tetromino = Tetromino::new(TetrominoType::I);
+        let mut rotated = i_tetromino;
+        rotated.rotate();
+        assert_eq!(
+            board.find_kick(position, &i_tetromino, &rotated),
+            Some((Position { x: 5, y: 5 }, 0))
+        );
+    }
+
+    #[test]
+    fn test_find_kick_off_wall() {
+        let board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+
+        // A T pointing right, flush against the left wall
+        let mut t_tetromino = Tetromino::new(TetrominoType::T);
+        t_tetromino.rotate();
+        let position = Position { x: 0, y: 5 };
+        assert!(board.is_valid_position(position, &t_tetromino));
+
+        // Rotating to the flat state would poke through the wall, so the second test kicks right
+        let mut rotated = t_tetromino;
+        rotated.rotate();
+        assert!(!board.is_valid_position(position, &rotated));
+        assert_eq!(
+            board.find_kick(position, &t_tetromino, &rotated),
+            Some((Position { x: 1, y: 5 }, 1))
+        );
+    }
+
+    #[test]
+    fn test_find_kick_blocked() {
+        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+
+        // Fill everything except a vertical I-shaped well
+        for x in 0..board.width {
+            for y in 0..board.height {
+                if x != 4 {
+                    board.cells[x][y] = Some(TetrominoType::O);
+                }
+            }
+        }
+
+        let mut i_tetromino = Tetromino::new(TetrominoType::I);
+        i_tetromino.rotation = 1;
+        let position = Position { x: 4, y: 10 };
+        assert!(board.is_valid_position(position, &i_tetromino));
+
+        // No kick test can fit a horizontal I into the well
+        let mut rotated = i_tetromino;
+        rotated.rotate();
+        assert_eq!(board.find_kick(position, &i_tetromino, &rotated), None);
+    }
+
     #[test]
     fn test_board_clear_lines() {
         let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
@@ -236,12 +388,70 @@ mod board_tests {
             assert_eq!(board.cells[x][1], None, "Cell at ({x}, 1) should be None");
         }
     }
+
+    #[test]
+    fn test_board_clear_adjacent_lines_collapses_stack() {
+        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+        let bottom = BOARD_HEIGHT - 1;
+
+        // Two full rows at the bottom with a lone block above them
+        for x in 0..board.width {
+            board.cells[x][bottom] = Some(TetrominoType::I);
+            board.cells[x][bottom - 1] = Some(TetrominoType::O);
+        }
+        board.cells[3][bottom - 2] = Some(TetrominoType::T);
+
+        // A block waiting in the hidden buffer comes down with the rest of the stack
+        board.set_cell(7, -1, Some(TetrominoType::S));
+
+        let (lines_cleared, cleared_indices) = board.clear_lines_with_indices();
+
+        assert_eq!(lines_cleared, 2);
+        assert_eq!(cleared_indices, vec![bottom - 1, bottom]);
+        assert_eq!(board.cells[3][bottom], Some(TetrominoType::T));
+        assert_eq!(board.cells[7][1], Some(TetrominoType::S));
+        assert_eq!(board.cell(7, -1), None);
+        let remaining = board.cells.iter().flatten().filter(|c| c.is_some()).count();
+        assert_eq!(remaining, 2);
+    }
+
+    #[test]
+    fn test_board_hidden_buffer() {
+        let mut board = Board::new(6, 12);
+        let piece = Tetromino::new(TetrominoType::O);
+
+        // Pieces can sit anywhere in the buffer but not above it, the O piece's top row is -1
+        assert_eq!(board.top(), -20);
+        assert!(board.is_valid_position(
+            Position {
+                x: 0,
+                y: board.top() + 1
+            },
+            &piece
+        ));
+        assert!(!board.is_valid_position(
+            Position {
+                x: 0,
+                y: board.top()
+            },
+            &piece
+        ));
+
+        // Blocks in the buffer collide and count against a perfect clear
+        board.lock_tetromino(Position { x: 0, y: -5 }, &piece);
+        assert_eq!(board.cell(0, -5), Some(TetrominoType::O));
+        assert!(!board.is_valid_position(Position { x: 0, y: -5 }, &piece));
+        assert!(!board.is_empty());
+
+        board.clear();
+        assert!(board.is_empty());
+    }
 }
 
 #[cfg(test)]
 mod game_state_tests {
     use crate::components::{GameState, TetrominoType};
-    use crate::game::{LINES_PER_LEVEL, STARTING_LEVEL};
+    use crate::game::{FRAMES_PER_SECOND, LINES_PER_LEVEL, MAX_GRAVITY, MAX_LEVEL, STARTING_LEVEL};
 
     #[test]
     fn test_game_state_default() {
@@ -252,7 +462,7 @@ mod game_state_tests {
         assert_eq!(game_state.level, STARTING_LEVEL);
         assert_eq!(game_state.lines_cleared, 0);
         assert!(!game_state.game_over);
-        assert_eq!(game_state.next_tetromino, None);
+        assert!(game_state.next_queue.is_empty());
     }
 
     #[test]
@@ -264,7 +474,7 @@ mod game_state_tests {
         game_state.level = 5;
         game_state.lines_cleared = 40;
         game_state.game_over = true;
-        game_state.next_tetromino = Some(TetrominoType::I);
+        game_state.next_queue.push_back(TetrominoType::I);
 
         // Reset
         game_state.reset();
@@ -274,7 +484,7 @@ mod game_state_tests {
         assert_eq!(game_state.level, STARTING_LEVEL);
         assert_eq!(game_state.lines_cleared, 0);
         assert!(!game_state.game_over);
-        assert_eq!(game_state.next_tetromino, None);
+        assert!(game_state.next_queue.is_empty());
     }
 
     #[test]
@@ -291,24 +501,314 @@ mod game_state_tests {
         assert_eq!(game_state.level, STARTING_LEVEL);
     }
 
+    #[test]
+    fn test_gravity_follows_guideline_curve() {
+        let at_level = |level| GameState {
+            level,
+            ..GameState::default()
+        };
+
+        // Level 1 falls a row a second
+        assert!((at_level(1).get_drop_delay() - 1.0).abs() < 1e-4);
+
+        // Level 10: 0.737^9 seconds per row
+        let expected = 1.0 / (0.737_f32.powi(9) * FRAMES_PER_SECOND);
+        assert!((at_level(10).gravity() - expected).abs() < 1e-4);
+
+        // Every level is faster than the one before until gravity tops out at 20G
+        for level in 1..MAX_LEVEL {
+            assert!(at_level(level + 1).gravity() >= at_level(level).gravity());
+        }
+        assert!(at_level(15).gravity() > 1.0);
+        assert!((at_level(MAX_LEVEL).gravity() - MAX_GRAVITY).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn test_game_state_next_tetromino() {
         let mut game_state = GameState::default();
 
-        // Initially next_tetromino should be None
-        assert_eq!(game_state.next_tetromino, None);
+        // Initially the queue is empty
+        assert_eq!(game_state.next_queue.front().copied(), None);
 
-        // Set a specific tetromino type
-        game_state.next_tetromino = Some(TetrominoType::Z);
+        // Queue up specific tetromino types
+        game_state.next_queue.push_back(TetrominoType::Z);
+        game_state.next_queue.push_back(TetrominoType::L);
 
-        // Verify it was set correctly
-        assert_eq!(game_state.next_tetromino, Some(TetrominoType::Z));
+        // The front of the queue is dealt next
+        assert_eq!(
+            game_state.next_queue.front().copied(),
+            Some(TetrominoType::Z)
+        );
 
         // Take the next tetromino (simulating spawn_tetromino behavior)
-        let next = game_state.next_tetromino.take();
+        let next = game_state.next_queue.pop_front();
 
-        // Verify we get the expected type and it's now None in the game state
+        // Verify we get the expected type and the queue moved up
         assert_eq!(next, Some(TetrominoType::Z));
-        assert_eq!(game_state.next_tetromino, None);
+        assert_eq!(
+            game_state.next_queue.front().copied(),
+            Some(TetrominoType::L)
+        );
+    }
+}
+
+#[cfg(test)]
+mod auto_shift_tests {
+    use crate::components::AutoShift;
+
+    const DAS: f32 = 0.15;
+    const ARR: f32 = 0.05;
+
+    #[test]
+    fn test_no_steps_before_das_charges() {
+        let mut auto_shift = AutoShift::default();
+        auto_shift.press_horizontal(1);
+
+        assert_eq!(auto_shift.horizontal_steps(0.1, DAS, ARR), 0);
+        assert_eq!(auto_shift.horizontal_steps(0.04, DAS, ARR), 0);
+    }
+
+    #[test]
+    fn test_shifts_at_arr_after_das() {
+        let mut auto_shift = AutoShift::default();
+        auto_shift.press_horizontal(-1);
+
+        // Charging DAS shifts once, the leftover time counts towards the next repeat
+        assert_eq!(auto_shift.horizontal_steps(0.16, DAS, ARR), 1);
+        assert_eq!(auto_shift.horizontal_steps(0.045, DAS, ARR), 1);
+        assert_eq!(auto_shift.horizontal_steps(0.1, DAS, ARR), 2);
+    }
+
+    #[test]
+    fn test_zero_arr_shifts_to_wall() {
+        let mut auto_shift = AutoShift::default();
+        auto_shift.press_horizontal(1);
+
+        assert_eq!(auto_shift.horizontal_steps(0.2, DAS, 0.0), u32::MAX);
+    }
+
+    #[test]
+    fn test_release_falls_back_to_opposite_direction() {
+        let mut auto_shift = AutoShift::default();
+        auto_shift.press_horizontal(-1);
+        auto_shift.press_horizontal(1);
+        assert_eq!(auto_shift.direction, 1);
+
+        // Releasing the newer key recharges DAS for the one still held
+        auto_shift.horizontal_steps(0.2, DAS, ARR);
+        auto_shift.release_horizontal(1);
+        assert_eq!(auto_shift.direction, -1);
+        assert_eq!(auto_shift.horizontal_steps(0.1, DAS, ARR), 0);
+
+        auto_shift.release_horizontal(-1);
+        assert_eq!(auto_shift.direction, 0);
+        assert_eq!(auto_shift.horizontal_steps(1.0, DAS, ARR), 0);
+    }
+
+    #[test]
+    fn test_soft_drop_steps_only_while_held() {
+        let mut auto_shift = AutoShift::default();
+        assert_eq!(auto_shift.soft_drop_steps(1.0, 0.05), 0);
+
+        auto_shift.press_down();
+        assert_eq!(auto_shift.soft_drop_steps(0.12, 0.05), 2);
+        assert_eq!(auto_shift.soft_drop_steps(0.035, 0.05), 1);
+
+        auto_shift.release_down();
+        assert_eq!(auto_shift.soft_drop_steps(1.0, 0.05), 0);
+    }
+}
+
+#[cfg(test)]
+mod lock_delay_tests {
+    use crate::components::{LockDelay, Timings};
+    use crate::game::MAX_LOCK_RESETS;
+
+    #[test]
+    fn test_locks_after_duration_on_ground() {
+        let mut lock_delay = LockDelay::default();
+        lock_delay.start_piece(0);
+
+        assert!(!lock_delay.tick(true, Timings::default().lock_delay() * 0.6));
+        assert!(lock_delay.active);
+        assert!(lock_delay.tick(true, Timings::default().lock_delay() * 0.6));
+    }
+
+    #[test]
+    fn test_timer_stops_in_the_air() {
+        let mut lock_delay = LockDelay::default();
+        lock_delay.start_piece(0);
+
+        lock_delay.tick(true, Timings::default().lock_delay() * 0.9);
+        assert!(!lock_delay.tick(false, 0.1));
+        assert!(!lock_delay.active);
+        assert!(!lock_delay.tick(true, Timings::default().lock_delay() * 0.5));
+    }
+
+    #[test]
+    fn test_moves_reset_timer_until_limit() {
+        let mut lock_delay = LockDelay::default();
+        lock_delay.start_piece(0);
+
+        for _ in 0..MAX_LOCK_RESETS - 1 {
+            assert!(!lock_delay.tick(true, Timings::default().lock_delay() * 0.9));
+            lock_delay.register_move();
+        }
+        assert_eq!(lock_delay.resets, MAX_LOCK_RESETS - 1);
+
+        // The last reset is used up, after that the piece locks as soon as it's on the ground
+        assert!(!lock_delay.tick(true, Timings::default().lock_delay() * 0.9));
+        lock_delay.register_move();
+        assert_eq!(lock_delay.resets, MAX_LOCK_RESETS);
+        assert!(lock_delay.tick(true, 0.0));
+    }
+
+    #[test]
+    fn test_moves_in_the_air_do_not_use_resets() {
+        let mut lock_delay = LockDelay::default();
+        lock_delay.start_piece(0);
+
+        lock_delay.register_move();
+        assert_eq!(lock_delay.resets, 0);
+    }
+
+    #[test]
+    fn test_new_lowest_row_restores_resets() {
+        let mut lock_delay = LockDelay::default();
+        lock_delay.start_piece(2);
+        lock_delay.tick(true, 0.1);
+        lock_delay.register_move();
+        lock_delay.register_move();
+        assert_eq!(lock_delay.resets, 2);
+
+        // Climbing up via a kick doesn't give resets back
+        lock_delay.reach_row(1);
+        assert_eq!(lock_delay.resets, 2);
+
+        lock_delay.reach_row(3);
+        assert_eq!(lock_delay.resets, 0);
+        assert_eq!(lock_delay.lowest_row, 3);
+    }
+
+    #[test]
+    fn test_remaining_fraction_shrinks() {
+        let mut lock_delay = LockDelay::default();
+        assert!((lock_delay.remaining_fraction() - 1.0).abs() < f32::EPSILON);
+
+        lock_delay.tick(true, Timings::default().lock_delay() / 2.0);
+        assert!((lock_delay.remaining_fraction() - 0.5).abs() < 0.01);
+    }
+}
+
+#[cfg(test)]
+mod t_spin_tests {
+    use crate::components::{
+        Board, GameState, LastAction, Position, TSpin, Tetromino, TetrominoType,
+    };
+    use crate::game::{BOARD_HEIGHT, BOARD_WIDTH, TSPIN_DOUBLE, TSPIN_MINI_ZERO, TSPIN_ZERO};
+
+    // Two bottom rows with a T-spin double slot at column 4 and an overhang at (3, 17)
+    fn t_spin_double_board() -> Board {
+        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+        for x in 0..BOARD_WIDTH {
+            if x != 4 {
+                board.cells[x][19] = Some(TetrominoType::I);
+            }
+            if !(3..=5).contains(&x) {
+                board.cells[x][18] = Some(TetrominoType::I);
+            }
+        }
+        board.cells[3][17] = Some(TetrominoType::J);
+        board
+    }
+
+    // A T pointing down, sitting in the slot
+    fn t_in_slot() -> (Position, Tetromino) {
+        let mut tetromino = Tetromino::new(TetrominoType::T);
+        tetromino.rotate_180();
+        (Position { x: 4, y: 18 }, tetromino)
+    }
+
+    #[test]
+    fn test_rotation_into_slot_is_full_t_spin() {
+        let board = t_spin_double_board();
+        let (position, tetromino) = t_in_slot();
+
+        let t_spin =
+            GameState::detect_t_spin(&board, position, &tetromino, LastAction::Rotate { kick: 0 });
+        assert_eq!(t_spin, TSpin::Full);
+    }
+
+    #[test]
+    fn test_dropping_into_slot_is_not_t_spin() {
+        let board = t_spin_double_board();
+        let (position, tetromino) = t_in_slot();
+
+        assert_eq!(
+            GameState::detect_t_spin(&board, position, &tetromino, LastAction::Move),
+            TSpin::None
+        );
+        assert_eq!(
+            GameState::detect_t_spin(&board, position, &tetromino, LastAction::Spawn),
+            TSpin::None
+        );
+    }
+
+    #[test]
+    fn test_only_t_pieces_spin() {
+        let board = t_spin_double_board();
+        let tetromino = Tetromino::new(TetrominoType::L);
+
+        let t_spin = GameState::detect_t_spin(
+            &board,
+            Position { x: 4, y: 18 },
+            &tetromino,
+            LastAction::Rotate { kick: 0 },
+        );
+        assert_eq!(t_spin, TSpin::None);
+    }
+
+    #[test]
+    fn test_back_corners_give_mini_unless_last_kick() {
+        // A flat T on the floor has both back corners under the floor, one front corner is filled
+        let mut board = Board::new(BOARD_WIDTH, BOARD_HEIGHT);
+        board.cells[3][18] = Some(TetrominoType::O);
+        let tetromino = Tetromino::new(TetrominoType::T);
+        let position = Position { x: 4, y: 19 };
+
+        assert_eq!(
+            GameState::detect_t_spin(&board, position, &tetromino, LastAction::Rotate { kick: 1 }),
+            TSpin::Mini
+        );
+        assert_eq!(
+            GameState::detect_t_spin(&board, position, &tetromino, LastAction::Rotate { kick: 4 }),
+            TSpin::Full
+        );
+    }
+
+    #[test]
+    fn test_t_spin_zero_scores_without_lines() {
+        let mut game_state = GameState::default();
+        game_state.update_score(0, TSpin::Full, false);
+        assert_eq!(game_state.score, TSPIN_ZERO * game_state.level);
+        assert_eq!(game_state.t_spin_count, 1);
+
+        let mut game_state = GameState::default();
+        game_state.update_score(0, TSpin::Mini, false);
+        assert_eq!(game_state.score, TSPIN_MINI_ZERO * game_state.level);
+
+        let mut game_state = GameState::default();
+        game_state.update_score(0, TSpin::None, false);
+        assert_eq!(game_state.score, 0);
+        assert_eq!(game_state.t_spin_count, 0);
+    }
+
+    #[test]
+    fn test_t_spin_double_scores_and_starts_back_to_back() {
+        let mut game_state = GameState::default();
+        game_state.update_score(2, TSpin::Full, false);
+
+        assert_eq!(game_state.score, TSPIN_DOUBLE * game_state.level);
+        assert!(game_state.back_to_back);
     }
 }
@@ -0,0 +1,132 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{Board, GameState, Ghost, ScreenShake, Tetromino, TetrominoType};
+    use crate::env::{Action, GameEnv, Observation};
+    use crate::sound::AudioState;
+
+    // Plays a fixed cycle of actions from a seed, returning every observation and reward
+    fn play(seed: u64, steps: usize) -> Vec<(Observation, f32, bool)> {
+        let mut env = GameEnv::default();
+        env.reset(seed);
+        (0..steps)
+            .map(|step| env.step(Action::ALL[step * 7 % Action::ALL.len()]))
+            .collect()
+    }
+
+    #[test]
+    fn test_action_indices_round_trip() {
+        for (index, action) in Action::ALL.into_iter().enumerate() {
+            assert_eq!(action.index(), index);
+            assert_eq!(Action::from_index(index), Some(action));
+        }
+        assert_eq!(Action::from_index(Action::ALL.len()), None);
+    }
+
+    #[test]
+    fn test_reset_observes_a_fresh_game() {
+        let mut env = GameEnv::default();
+        let observation = env.reset(11);
+
+        assert_eq!(
+            observation.board.len(),
+            observation.width * observation.height
+        );
+        assert!(observation.board.iter().all(|&occupied| !occupied));
+        assert!(observation.active.is_some());
+        assert!(!observation.queue.is_empty());
+        assert_eq!(observation.hold, None);
+        assert!(env.world().get_resource::<AudioState>().is_none());
+
+        let (observation, _, done) = env.step(Action::Hold);
+        assert!(!done);
+        assert!(observation.hold.is_some());
+        assert!(observation.hold_used);
+    }
+
+    #[test]
+    fn test_same_seed_and_actions_play_the_same() {
+        assert_eq!(play(4, 600), play(4, 600));
+    }
+
+    #[test]
+    fn test_reward_is_the_score_a_step_made() {
+        let mut env = GameEnv::default();
+        env.reset(2);
+
+        // Fill the bottom row, leaving the cells the active piece lands in
+        {
+            let world = env.world_mut();
+            let (tetromino, landing) = world
+                .query::<(&Tetromino, &Ghost)>()
+                .iter(world)
+                .next()
+                .map(|(tetromino, ghost)| (*tetromino, ghost.position))
+                .unwrap();
+            let mut board = world.resource_mut::<Board>();
+            let bottom = board.height - 1;
+            let bottom_row = i32::try_from(bottom).unwrap();
+            let landing_cells: Vec<(i32, i32)> = board
+                .piece_set
+                .blocks(&tetromino)
+                .iter()
+                .map(|&(x, y)| (landing.x + x, landing.y + y))
+                .collect();
+            for x in 0..board.width {
+                if !landing_cells.contains(&(i32::try_from(x).unwrap(), bottom_row)) {
+                    board.cells[x][bottom] = Some(TetrominoType::Z);
+                }
+            }
+        }
+
+        let (observation, reward, done) = env.step(Action::HardDrop);
+        assert!(!done);
+        assert!(reward > 0.0);
+        let score = env.world().resource::<GameState>().score;
+        assert!((f64::from(reward) - f64::from(score)).abs() < f64::EPSILON);
+        assert_eq!(env.world().resource::<GameState>().lines_cleared, 1);
+        assert!(observation.active.is_some());
+    }
+
+    #[test]
+    fn test_actions_right_after_a_lock_take_effect() {
+        let mut env = GameEnv::default();
+        env.reset(5);
+
+        // The next piece is in play while the lock's screen shake is still running
+        let mut observation = env.step(Action::HardDrop).0;
+        while observation.active.is_none() {
+            observation = env.step(Action::Noop).0;
+        }
+        assert!(env.world().resource::<ScreenShake>().is_active);
+
+        let spawned = observation.active.unwrap();
+        let moved = env.step(Action::Left).0.active.unwrap();
+        assert_eq!(moved.x, spawned.x - 1);
+        let turned = env.step(Action::RotateCw).0.active.unwrap();
+        assert_eq!(turned.rotation, (spawned.rotation + 1) % 4);
+    }
+
+    #[test]
+    fn test_hard_drops_play_until_the_game_is_done() {
+        let mut env = GameEnv::default();
+        env.reset(2);
+
+        let mut done = false;
+        for _ in 0..10_000 {
+            done = env.step(Action::HardDrop).2;
+            if done {
+                break;
+            }
+        }
+        assert!(done);
+        assert!(env.is_done());
+
+        // A finished game stays as it ended
+        let (observation, reward, done) = env.step(Action::Left);
+        assert_eq!(observation, env.observation());
+        assert!(reward.abs() < f32::EPSILON);
+        assert!(done);
+    }
+}
@@ -0,0 +1,133 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::{
+        Board, GameState, Input, LockDelay, Phase, Position, Tetromino, TetrominoType,
+    };
+    use crate::config::Config;
+    use crate::finesse::{Finesse, FinesseFault, minimal_inputs};
+    use crate::mode::GameModeKind;
+    use crate::sound::AudioState;
+    use crate::systems::step_frame;
+    use bevy_ecs::prelude::*;
+
+    fn needed(tetromino_type: TetrominoType, rotation: usize, x: i32) -> Option<u32> {
+        let mut tetromino = Tetromino::new(tetromino_type);
+        tetromino.rotation = rotation;
+        minimal_inputs(&Board::new(10, 20), &tetromino, Position { x, y: 18 })
+    }
+
+    #[test]
+    fn test_minimal_inputs() {
+        // A T stays put for free, taps one column, holds to either wall and taps back off it
+        assert_eq!(needed(TetrominoType::T, 0, 4), Some(0));
+        assert_eq!(needed(TetrominoType::T, 0, 3), Some(1));
+        assert_eq!(needed(TetrominoType::T, 0, 1), Some(1));
+        assert_eq!(needed(TetrominoType::T, 0, 2), Some(2));
+        assert_eq!(needed(TetrominoType::T, 0, 8), Some(1));
+        assert_eq!(needed(TetrominoType::T, 2, 4), Some(1));
+        assert_eq!(needed(TetrominoType::T, 1, 8), Some(2));
+
+        // Rotations that look alike count as the same spot, an O never needs to rotate
+        assert_eq!(needed(TetrominoType::O, 2, 5), Some(0));
+        assert_eq!(needed(TetrominoType::I, 1, 0), Some(2));
+        assert_eq!(
+            needed(TetrominoType::S, 2, 4),
+            needed(TetrominoType::S, 0, 4)
+        );
+    }
+
+    fn new_game(training: bool) -> World {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        let mut ruleset = Config::default().ruleset(GameModeKind::Marathon);
+        ruleset.finesse_training = training;
+        start_game(&mut world, GameModeKind::Marathon, &ruleset, 9);
+        world
+    }
+
+    fn piece(world: &mut World) -> (TetrominoType, usize, Position) {
+        let (tetromino, position) = world.query::<(&Tetromino, &Position)>().single(world);
+        (tetromino.tetromino_type, tetromino.rotation, *position)
+    }
+
+    // Left then right again, two keys for a piece that needed none, then a hard drop
+    fn waste_keys_and_drop(world: &mut World) {
+        for frame in 0..3 {
+            {
+                let mut input = world.resource_mut::<Input>();
+                match frame {
+                    0 => input.left = true,
+                    1 => input.right = true,
+                    _ => input.hard_drop = true,
+                }
+            }
+            step_frame(world);
+        }
+    }
+
+    #[test]
+    fn test_wasted_keys_are_faults() {
+        let mut world = new_game(false);
+        let (tetromino_type, _, _) = piece(&mut world);
+        waste_keys_and_drop(&mut world);
+
+        let finesse = world.resource::<Finesse>();
+        assert_eq!(finesse.faults, 2);
+        assert_eq!(finesse.pieces, 1);
+        assert_eq!(finesse.clean_pieces, 0);
+        assert_eq!(
+            finesse.last_fault,
+            Some(FinesseFault {
+                tetromino_type,
+                used: 2,
+                needed: 0
+            })
+        );
+        assert!(!world.resource::<Board>().is_empty());
+    }
+
+    #[test]
+    fn test_soft_dropped_pieces_are_not_judged() {
+        let mut world = new_game(false);
+        world.resource_mut::<Input>().down = true;
+        step_frame(&mut world);
+        waste_keys_and_drop(&mut world);
+
+        let finesse = world.resource::<Finesse>();
+        assert_eq!((finesse.faults, finesse.pieces), (0, 0));
+    }
+
+    #[test]
+    fn test_training_retries_a_faulty_piece() {
+        let mut world = new_game(true);
+        let spawned = piece(&mut world);
+        let queue = world.resource::<GameState>().next_queue.clone();
+        waste_keys_and_drop(&mut world);
+
+        // The piece goes back to the top rather than locking, and the queue doesn't move on
+        assert_eq!(world.resource::<Finesse>().faults, 2);
+        assert!(world.resource::<Board>().is_empty());
+        assert_eq!(piece(&mut world), spawned);
+        assert_eq!(world.resource::<GameState>().next_queue, queue);
+
+        // A piece taken out of the hold is retried with the hold and lock delay fresh again
+        world.resource_mut::<Input>().hold = true;
+        step_frame(&mut world);
+        let (held, _, _) = piece(&mut world);
+        assert!(world.resource::<GameState>().hold_used);
+        waste_keys_and_drop(&mut world);
+
+        assert_eq!(world.resource::<Finesse>().faults, 4);
+        assert_eq!(piece(&mut world).0, held);
+        let game_state = world.resource::<GameState>();
+        assert!(!game_state.hold_used);
+        assert_eq!(game_state.held_tetromino, Some(spawned.0));
+        let lock_delay = world.resource::<LockDelay>();
+        assert!(!lock_delay.active);
+        assert_eq!(lock_delay.resets, 0);
+        assert_eq!(*world.resource::<Phase>(), Phase::Falling);
+    }
+}
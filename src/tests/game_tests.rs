@@ -2,6 +2,7 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::components::Timings;
     use crate::game::*;
 
     #[test]
@@ -51,8 +52,10 @@ mod tests {
     }
 
     #[test]
-    fn test_coyote_time() {
-        // Verify coyote time is positive
-        assert!(COYOTE_TIME_DURATION > 0.0);
+    fn test_lock_delay() {
+        // Guideline lock delay is half a second with 15 move resets
+        assert_eq!(DEFAULT_LOCK_DELAY_MS, 500);
+        assert!((Timings::default().lock_delay() - 0.5).abs() < f32::EPSILON);
+        assert_eq!(MAX_LOCK_RESETS, 15);
     }
 }
@@ -1,9 +1,10 @@
 use crate::Time;
 use crate::components::{
-    Board, CoyoteTime, GameState, Ghost, Input, Position, ScreenShake, Tetromino,
+    Board, GameState, Ghost, Input, LockDelay, Position, ScreenShake, Tetromino,
 };
+use crate::mode::GameMode;
 use crate::sound::AudioState;
-use crate::systems::{game_tick_system, input_system, spawn_tetromino};
+use crate::systems::{input_system, spawn_tetromino, step_frame};
 use bevy_ecs::prelude::*;
 
 #[test]
@@ -16,9 +17,10 @@ fn test_hard_drop() {
     world.insert_resource(Board::new(10, 20)); // Standard 10x20 board
     world.insert_resource(Input::default());
     world.insert_resource(ScreenShake::default());
-    world.insert_resource(CoyoteTime::default());
+    world.insert_resource(LockDelay::default());
     world.insert_resource(AudioState::new());
     world.insert_resource(Time::new());
+    world.insert_resource(GameMode::default());
 
     // Set the hard_drop_released flag to true initially
     {
@@ -43,8 +45,8 @@ fn test_hard_drop() {
         *query.iter(&world).next().unwrap()
     };
 
-    // Run one game tick to update the ghost position
-    game_tick_system(&mut world, 0.016);
+    // Run one frame to update the ghost position
+    step_frame(&mut world);
 
     // Verify the ghost is properly positioned
     let ghost_position = {
@@ -106,15 +108,16 @@ fn test_ghost_position_update() {
     world.insert_resource(Board::new(10, 20)); // Standard 10x20 board
     world.insert_resource(Input::default());
     world.insert_resource(ScreenShake::default());
-    world.insert_resource(CoyoteTime::default());
+    world.insert_resource(LockDelay::default());
     world.insert_resource(AudioState::new());
     world.insert_resource(Time::new());
+    world.insert_resource(GameMode::default());
 
     // Spawn a tetromino
     spawn_tetromino(&mut world);
 
-    // Run game tick to update ghost position
-    game_tick_system(&mut world, 0.016);
+    // Run a frame to update the ghost position
+    step_frame(&mut world);
 
     // Get the tetromino and ghost positions
     let (tetromino_pos, ghost_pos) = {
@@ -144,8 +147,8 @@ fn test_ghost_position_update() {
         world.entity_mut(entity).insert(new_position);
     }
 
-    // Run game tick again to update ghost position
-    game_tick_system(&mut world, 0.016);
+    // Run another frame to update the ghost position
+    step_frame(&mut world);
 
     // Get the updated tetromino and ghost positions
     let (updated_tetromino_pos, updated_ghost_pos) = {
@@ -0,0 +1,109 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::GameState;
+    use crate::config::Config;
+    use crate::game::MAX_HIGH_SCORES;
+    use crate::highscores::{HighScore, HighScores};
+    use crate::mode::{GameMode, GameModeKind, ModeResult};
+    use crate::sound::AudioState;
+    use bevy_ecs::prelude::*;
+
+    fn entry(name: &str, score: u32, time: f32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+            lines: 40,
+            level: 5,
+            time,
+            date: 0,
+        }
+    }
+
+    fn finished_game(mode: GameModeKind, score: u32) -> World {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        start_game(&mut world, mode, &Config::default().ruleset(mode), 7);
+        let mut game_state = world.resource_mut::<GameState>();
+        game_state.score = score;
+        game_state.game_over = true;
+        world
+    }
+
+    #[test]
+    fn test_tables_keep_the_best_ten_per_mode_and_ruleset() {
+        let mut high_scores = HighScores::default();
+        let ruleset = Config::default().ruleset(GameModeKind::Marathon);
+
+        for score in 1..=12 {
+            high_scores.insert(
+                GameModeKind::Marathon,
+                &ruleset,
+                entry("AAA", score * 100, 0.0),
+            );
+        }
+        let table = high_scores.table(GameModeKind::Marathon, &ruleset);
+        assert_eq!(table.len(), MAX_HIGH_SCORES);
+        assert_eq!(table[0].score, 1200);
+        assert_eq!(table[MAX_HIGH_SCORES - 1].score, 300);
+
+        // Ties go to the score that was there first, and a score off the bottom isn't kept
+        assert_eq!(
+            high_scores.insert(GameModeKind::Marathon, &ruleset, entry("BBB", 1200, 0.0)),
+            Some(1)
+        );
+        assert!(!high_scores.qualifies(GameModeKind::Marathon, &ruleset, &entry("CCC", 300, 0.0)));
+        assert_eq!(
+            high_scores.insert(GameModeKind::Marathon, &ruleset, entry("CCC", 300, 0.0)),
+            None
+        );
+
+        // Other rules and other modes have tables of their own
+        let mut wide = ruleset;
+        wide.board_width = 12;
+        assert!(high_scores.table(GameModeKind::Marathon, &wide).is_empty());
+        assert!(high_scores.table(GameModeKind::Ultra, &ruleset).is_empty());
+    }
+
+    #[test]
+    fn test_sprint_ranks_the_fastest_time() {
+        let mut high_scores = HighScores::default();
+        let ruleset = Config::default().ruleset(GameModeKind::Sprint);
+
+        high_scores.insert(GameModeKind::Sprint, &ruleset, entry("SLO", 9000, 95.0));
+        high_scores.insert(GameModeKind::Sprint, &ruleset, entry("FST", 100, 41.5));
+
+        let table = high_scores.table(GameModeKind::Sprint, &ruleset);
+        assert_eq!(table[0].name, "FST");
+        assert_eq!(table[1].name, "SLO");
+    }
+
+    #[test]
+    fn test_only_finished_ranked_games_are_candidates() {
+        let high_scores = HighScores::default();
+
+        // A sprint that topped out before its 40 lines doesn't count
+        let mut world = finished_game(GameModeKind::Sprint, 500);
+        world.resource_mut::<GameMode>().result = Some(ModeResult::ToppedOut);
+        assert!(high_scores.candidate(&world, "ABC").is_none());
+        world.resource_mut::<GameMode>().result = Some(ModeResult::Completed);
+        assert!(high_scores.candidate(&world, "ABC").is_some());
+
+        // Zen never ends, so it has no table
+        let world = finished_game(GameModeKind::Zen, 500);
+        assert!(high_scores.candidate(&world, "ABC").is_none());
+
+        // The last initials are offered again, and typing sticks to three letters or digits
+        let world = finished_game(GameModeKind::Marathon, 500);
+        let mut pending = high_scores.candidate(&world, "JOHNNY").unwrap();
+        assert_eq!(pending.entry.name, "JOH");
+        assert_eq!(pending.entry.score, 500);
+        pending.backspace();
+        pending.type_char('!');
+        pending.type_char('n');
+        pending.type_char('x');
+        assert_eq!(pending.entry.name, "JON");
+    }
+}
@@ -3,12 +3,22 @@
 // Test modules
 pub mod app_tests;
 pub mod components_tests;
+pub mod env_tests;
+pub mod finesse_tests;
 pub mod game_tests;
 pub mod integration_tests;
 //pub mod particles_tests;
 //pub mod screenshake_tests;
 pub mod hard_drop_test;
+pub mod highscore_tests;
+pub mod mode_tests;
+pub mod pieces_tests;
+pub mod randomizer_tests;
+pub mod replay_tests;
+pub mod scoring_tests;
 pub mod sound_tests;
+pub mod stats_tests;
+pub mod suspend_tests;
 pub mod systems_tests;
 pub mod time_tests;
 pub mod ui_tests;
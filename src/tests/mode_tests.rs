@@ -0,0 +1,171 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{GameState, Tetromino, TetrominoType, TopOut};
+    use crate::config::Config;
+    use crate::game::{MARATHON_LINES, SPRINT_LINES, ULTRA_TIME_LIMIT};
+    use crate::mode::{GameMode, GameModeKind, ModeResult, format_time};
+    use crate::scoring::ScoringRuleKind;
+    use crate::systems::{game_mode_system, spawn_tetromino};
+    use crate::tests::test_utils::create_test_world;
+    use bevy_ecs::prelude::*;
+
+    // Fills every column of the spawn rows in the buffer and the top of the field
+    fn block_spawn(world: &mut World) {
+        let mut board = world.resource_mut::<crate::components::Board>();
+        for x in 0..i32::try_from(board.width).unwrap() {
+            for y in -2..2 {
+                board.set_cell(x, y, Some(TetrominoType::O));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sprint_completes_at_line_goal() {
+        let mut mode = GameMode::new(GameModeKind::Sprint);
+        let mut game_state = GameState {
+            lines_cleared: SPRINT_LINES - 1,
+            ..GameState::default()
+        };
+
+        assert!(!mode.update(&game_state, 1.0));
+        assert_eq!(mode.result, None);
+
+        game_state.lines_cleared = SPRINT_LINES;
+        assert!(mode.update(&game_state, 1.5));
+        assert_eq!(mode.result, Some(ModeResult::Completed));
+        assert!((mode.elapsed - 2.5).abs() < f32::EPSILON);
+
+        // The clock stops once the mode is finished
+        assert!(!mode.update(&game_state, 1.0));
+        assert!((mode.elapsed - 2.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_ultra_ends_at_time_limit() {
+        let mut mode = GameMode::new(GameModeKind::Ultra);
+        let game_state = GameState::default();
+
+        assert!(!mode.update(&game_state, ULTRA_TIME_LIMIT - 1.0));
+        assert!(mode.time_remaining().unwrap() > 0.0);
+
+        assert!(mode.update(&game_state, 1.3));
+        assert!((mode.elapsed - ULTRA_TIME_LIMIT).abs() < f32::EPSILON);
+        assert!(mode.time_remaining().unwrap().abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_marathon_and_zen_goals() {
+        let game_state = GameState {
+            lines_cleared: MARATHON_LINES,
+            ..GameState::default()
+        };
+
+        let mut marathon = GameMode::new(GameModeKind::Marathon);
+        assert_eq!(marathon.line_goal(), Some(MARATHON_LINES));
+        assert!(marathon.update(&game_state, 0.05));
+
+        let mut zen = GameMode::new(GameModeKind::Zen);
+        assert_eq!(zen.line_goal(), None);
+        assert!(!zen.update(&game_state, 10_000.0));
+    }
+
+    #[test]
+    fn test_game_mode_system_ends_completed_game() {
+        let mut world = create_test_world();
+        world.insert_resource(GameMode::new(GameModeKind::Sprint));
+        spawn_tetromino(&mut world);
+        world.resource_mut::<GameState>().lines_cleared = SPRINT_LINES;
+
+        game_mode_system(&mut world, 0.05);
+
+        assert!(world.resource::<GameState>().game_over);
+        assert_eq!(
+            world.resource::<GameMode>().result,
+            Some(ModeResult::Completed)
+        );
+        let pieces = world
+            .query_filtered::<Entity, With<Tetromino>>()
+            .iter(&world)
+            .count();
+        assert_eq!(pieces, 0);
+    }
+
+    #[test]
+    fn test_mode_clock_stops_while_paused() {
+        let mut world = create_test_world();
+        world.insert_resource(GameMode::new(GameModeKind::Ultra));
+
+        game_mode_system(&mut world, 1.0);
+        world.resource_mut::<GameState>().pause();
+        game_mode_system(&mut world, 30.0);
+        world.resource_mut::<GameState>().resume();
+        game_mode_system(&mut world, 30.0);
+
+        assert!((world.resource::<GameMode>().elapsed - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_blocked_spawn_tops_out() {
+        let mut world = create_test_world();
+        world.insert_resource(GameMode::new(GameModeKind::Marathon));
+        block_spawn(&mut world);
+
+        spawn_tetromino(&mut world);
+
+        assert!(world.resource::<GameState>().game_over);
+        assert_eq!(
+            world.resource::<GameState>().top_out,
+            Some(TopOut::BlockOut)
+        );
+        assert_eq!(
+            world.resource::<GameMode>().result,
+            Some(ModeResult::ToppedOut)
+        );
+    }
+
+    #[test]
+    fn test_zen_clears_stack_instead_of_topping_out() {
+        let mut world = create_test_world();
+        world.insert_resource(GameMode::new(GameModeKind::Zen));
+        block_spawn(&mut world);
+
+        spawn_tetromino(&mut world);
+
+        assert!(!world.resource::<GameState>().game_over);
+        assert_eq!(world.resource::<GameMode>().result, None);
+        let board = world.resource::<crate::components::Board>();
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_scoring_rule_is_chosen_per_mode() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.scoring_rule_for(GameModeKind::Marathon),
+            ScoringRuleKind::Classic
+        );
+        assert_eq!(
+            config.scoring_rule_for(GameModeKind::Sprint),
+            ScoringRuleKind::Guideline
+        );
+
+        config.cycle_scoring_rule(GameModeKind::Marathon);
+        assert_eq!(
+            config.scoring_rule_for(GameModeKind::Marathon),
+            ScoringRuleKind::Guideline
+        );
+        assert_eq!(
+            config.scoring_rule_for(GameModeKind::Zen),
+            ScoringRuleKind::Classic
+        );
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0.0), "0:00.00");
+        assert_eq!(format_time(83.456), "1:23.45");
+        assert_eq!(format_time(ULTRA_TIME_LIMIT), "2:00.00");
+    }
+}
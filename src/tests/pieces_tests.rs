@@ -0,0 +1,198 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::{Board, GameState, Input, Tetromino, TetrominoType};
+    use crate::config::Config;
+    use crate::game::MIN_BOARD_WIDTH;
+    use crate::mode::GameModeKind;
+    use crate::pieces::{PieceSet, PieceSetKind};
+    use crate::randomizer::{BagGenerator, Randomizer};
+    use crate::sound::AudioState;
+    use crate::suspend::SuspendedGame;
+    use crate::systems::step_frame;
+    use bevy_ecs::prelude::*;
+
+    const TROMINO_SET: &str = r##"{
+        "name": "Bars",
+        "pieces": [
+            {
+                "name": "I",
+                "color": "#ff8800",
+                "cells": [
+                    [[-1, 0], [0, 0], [1, 0]],
+                    [[0, -1], [0, 0], [0, 1]],
+                    [[-1, 0], [0, 0], [1, 0]],
+                    [[0, -1], [0, 0], [0, 1]]
+                ]
+            }
+        ]
+    }"##;
+
+    #[test]
+    fn test_built_in_sets() {
+        for (kind, pieces, cells) in [
+            (PieceSetKind::Classic, 7, 4),
+            (PieceSetKind::Pentomino, 18, 5),
+            (PieceSetKind::Mini, 2, 3),
+        ] {
+            let set = kind.load().unwrap();
+            assert_eq!(set.types().count(), pieces, "{} pieces", kind.name());
+            for piece in &set.pieces {
+                assert!(piece.cells.iter().all(|state| state.len() == cells));
+                // Every rotation state is the spawn state turned about the pivot
+                for (state, next) in piece.cells.iter().zip(piece.cells.iter().skip(1)) {
+                    let mut turned: Vec<_> = state.iter().map(|&(x, y)| (-y, x)).collect();
+                    let mut next = next.clone();
+                    turned.sort_unstable();
+                    next.sort_unstable();
+                    assert_eq!(turned, next, "{} turns cleanly", piece.name);
+                }
+            }
+        }
+
+        // Only the classic T is judged for T-spins, and the TGM history keeps its snakes
+        let classic = PieceSet::classic();
+        assert!(classic.piece(TetrominoType::T).t_spin);
+        assert_eq!(
+            classic.types().filter(|&t| classic.piece(t).t_spin).count(),
+            1
+        );
+        assert_eq!(
+            classic.tgm_history(),
+            [
+                TetrominoType::Z,
+                TetrominoType::S,
+                TetrominoType::S,
+                TetrominoType::Z
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_sets_are_checked() {
+        let set = PieceSet::from_json(TROMINO_SET).unwrap();
+        let bar = Tetromino::new(TetrominoType(0));
+        assert_eq!(
+            set.color(bar.tetromino_type),
+            ratatui::style::Color::Rgb(255, 136, 0)
+        );
+        assert!(set.piece(bar.tetromino_type).opener);
+
+        // A piece without kicks just turns in place
+        let mut turned = bar;
+        turned.rotate();
+        assert_eq!(set.kick_tests(&bar, &turned), [(0, 0)]);
+
+        let broken = [
+            TROMINO_SET.replace("#ff8800", "no such color"),
+            TROMINO_SET.replace("[[-1, 0], [0, 0], [1, 0]],\n", "[],\n"),
+            TROMINO_SET.replace(
+                r#""name": "Bars","#,
+                r#""name": "Bars", "tgm_history": ["Z"],"#,
+            ),
+            r#"{ "name": "Nothing", "pieces": [] }"#.to_string(),
+        ];
+        for json in broken {
+            assert!(PieceSet::from_json(&json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_bag_deals_every_piece_of_the_set() {
+        let set = PieceSet::pentomino();
+        let mut generator = BagGenerator::new(3, 1, &set);
+        for _ in 0..2 {
+            let mut bag: Vec<TetrominoType> = (0..18).map(|_| generator.next_piece()).collect();
+            bag.sort_unstable_by_key(|piece| piece.0);
+            assert_eq!(bag, set.types().collect::<Vec<_>>());
+        }
+    }
+
+    fn pentomino_game() -> World {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        let mut ruleset = Config::default().ruleset(GameModeKind::Marathon);
+        ruleset.piece_set = PieceSetKind::Pentomino;
+        start_game(&mut world, GameModeKind::Marathon, &ruleset, 11);
+        world
+    }
+
+    #[test]
+    fn test_pentomino_game_plays() {
+        let mut world = pentomino_game();
+        assert_eq!(
+            world.resource::<GameState>().piece_set,
+            PieceSetKind::Pentomino
+        );
+
+        // Five pieces, each turned once and sent alternately towards either wall
+        for frame in 0..300 {
+            {
+                let mut input = world.resource_mut::<Input>();
+                match frame % 60 {
+                    10 => input.rotate = true,
+                    20 | 30 if frame % 120 < 60 => input.left = true,
+                    20 | 30 => input.right = true,
+                    59 => input.hard_drop = true,
+                    _ => {}
+                }
+            }
+            step_frame(&mut world);
+        }
+
+        // Every cell placed came from a five cell piece of the set
+        let board = world.resource::<Board>();
+        assert!(!world.resource::<GameState>().game_over);
+        let cells: Vec<TetrominoType> = board.cells.iter().flatten().flatten().copied().collect();
+        let lines = world.resource::<GameState>().lines_cleared as usize;
+        assert_eq!(cells.len() + lines * board.width, 25);
+        assert!(cells.iter().all(|&piece| board.piece_set.contains(piece)));
+    }
+
+    #[test]
+    fn test_boards_are_wide_enough_for_the_set() {
+        assert_eq!(PieceSet::classic().min_board_width(), 4);
+        assert_eq!(PieceSet::pentomino().min_board_width(), 5);
+        assert_eq!(PieceSet::mini().min_board_width(), 3);
+
+        // The narrowest preset is widened to fit the pentomino I
+        let mut config = Config {
+            board_width: MIN_BOARD_WIDTH,
+            piece_set: PieceSetKind::Pentomino,
+            ..Config::default()
+        };
+        assert_eq!(config.board_size().0, 5);
+        config.cycle_board_width();
+        assert_eq!(config.board_size().0, 6);
+        config.piece_set = PieceSetKind::Classic;
+        config.board_width = MIN_BOARD_WIDTH;
+        assert_eq!(config.board_size().0, MIN_BOARD_WIDTH);
+
+        // A ruleset asking for too narrow a board still gets a game that plays
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        let mut ruleset = Config::default().ruleset(GameModeKind::Marathon);
+        ruleset.piece_set = PieceSetKind::Pentomino;
+        ruleset.board_width = MIN_BOARD_WIDTH;
+        start_game(&mut world, GameModeKind::Marathon, &ruleset, 11);
+        step_frame(&mut world);
+        assert_eq!(world.resource::<Board>().width, 5);
+        assert!(!world.resource::<GameState>().game_over);
+
+        // Sets whose pieces wouldn't spawn on any board are turned away
+        let wide = TROMINO_SET.replacen("[-1, 0], [0, 0], [1, 0]", "[-11, 0], [0, 0], [11, 0]", 1);
+        assert!(PieceSet::from_json(&wide).is_err());
+    }
+
+    #[test]
+    fn test_suspended_pieces_must_be_in_the_set() {
+        let mut world = pentomino_game();
+        let mut saved = serde_json::to_value(SuspendedGame::capture(&mut world, None)).unwrap();
+        assert!(SuspendedGame::from_json(&saved.to_string()).is_ok());
+
+        saved["game_state"]["held_tetromino"] = serde_json::json!(18);
+        assert!(SuspendedGame::from_json(&saved.to_string()).is_err());
+    }
+}
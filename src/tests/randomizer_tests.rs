@@ -0,0 +1,108 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::components::TetrominoType;
+    use crate::pieces::PieceSet;
+    use crate::randomizer::{
+        BagGenerator, GameRng, PieceRandomizer, Randomizer, RandomizerKind, TgmGenerator, seed_game,
+    };
+    use bevy_ecs::prelude::*;
+
+    fn count(pieces: &[TetrominoType], tetromino_type: TetrominoType) -> usize {
+        pieces
+            .iter()
+            .filter(|&&piece| piece == tetromino_type)
+            .count()
+    }
+
+    #[test]
+    fn test_seven_bag_deals_each_piece_once_per_bag() {
+        let mut generator = BagGenerator::new(42, 1, &PieceSet::classic());
+
+        for _ in 0..5 {
+            let bag: Vec<TetrominoType> = (0..7).map(|_| generator.next_piece()).collect();
+            for tetromino_type in TetrominoType::ALL {
+                assert_eq!(count(&bag, tetromino_type), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fourteen_bag_deals_each_piece_twice_per_bag() {
+        let mut generator = BagGenerator::new(7, 2, &PieceSet::classic());
+
+        for _ in 0..3 {
+            let bag: Vec<TetrominoType> = (0..14).map(|_| generator.next_piece()).collect();
+            for tetromino_type in TetrominoType::ALL {
+                assert_eq!(count(&bag, tetromino_type), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tgm_never_opens_with_overhang_piece() {
+        for seed in 0..200 {
+            let mut generator = TgmGenerator::new(seed, &PieceSet::classic());
+            let first = generator.next_piece();
+            assert!(
+                !matches!(
+                    first,
+                    TetrominoType::S | TetrominoType::Z | TetrominoType::O
+                ),
+                "seed {seed} opened with {first:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_sequence() {
+        for kind in [
+            RandomizerKind::SevenBag,
+            RandomizerKind::FourteenBag,
+            RandomizerKind::Tgm,
+            RandomizerKind::Random,
+        ] {
+            let mut a = PieceRandomizer::new(kind, 1234, &PieceSet::classic());
+            let mut b = PieceRandomizer::new(kind, 1234, &PieceSet::classic());
+            let seq_a: Vec<TetrominoType> = (0..50).map(|_| a.next_piece()).collect();
+            let seq_b: Vec<TetrominoType> = (0..50).map(|_| b.next_piece()).collect();
+            assert_eq!(seq_a, seq_b, "{} is not deterministic", kind.name());
+        }
+    }
+
+    #[test]
+    fn test_game_rng_is_seeded_apart_from_the_pieces() {
+        let seeded = |seed| {
+            let mut world = World::new();
+            seed_game(
+                &mut world,
+                RandomizerKind::Random,
+                &PieceSet::classic(),
+                seed,
+            );
+            world.resource::<GameRng>().state()
+        };
+
+        assert_eq!(seeded(1234), seeded(1234));
+        assert_ne!(seeded(1234), 1234);
+        assert_ne!(seeded(1234), seeded(1235));
+    }
+
+    #[test]
+    fn test_randomizer_kind_cycles_through_all() {
+        let mut kind = RandomizerKind::default();
+        assert_eq!(kind, RandomizerKind::SevenBag);
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(kind);
+            kind = kind.next();
+        }
+
+        assert_eq!(kind, RandomizerKind::SevenBag);
+        assert!(seen.contains(&RandomizerKind::FourteenBag));
+        assert!(seen.contains(&RandomizerKind::Tgm));
+        assert!(seen.contains(&RandomizerKind::Random));
+    }
+}
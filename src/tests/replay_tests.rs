@@ -0,0 +1,146 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::{AutoShift, Board, GameState, Handling, Input, TetrominoType};
+    use crate::config::Config;
+    use crate::mode::GameModeKind;
+    use crate::replay::{REPLAY_VERSION, Replay, ReplayPlayer, ReplayRecorder, format_date};
+    use crate::sound::AudioState;
+    use bevy_ecs::prelude::*;
+
+    type Snapshot = (Vec<Vec<Option<TetrominoType>>>, u32, Vec<TetrominoType>);
+
+    fn snapshot(world: &World) -> Snapshot {
+        let game_state = world.resource::<GameState>();
+        (
+            world.resource::<Board>().cells.clone(),
+            game_state.score,
+            game_state.next_queue.iter().copied().collect(),
+        )
+    }
+
+    // The game systems play sounds, everything else comes from start_game
+    fn new_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        world
+    }
+
+    // Plays a game with taps, held keys and a pause, the way the main loop feeds the world
+    fn record_game(seed: u64) -> (Replay, Snapshot) {
+        let mut world = new_world();
+        let ruleset = Config::default().ruleset(GameModeKind::Marathon);
+        start_game(&mut world, GameModeKind::Marathon, &ruleset, seed);
+        world.insert_resource(Handling::default());
+        let mut recorder =
+            ReplayRecorder::new(seed, GameModeKind::Marathon, ruleset, Handling::default());
+
+        for frame in 0..1500 {
+            match frame % 60 {
+                3 => world.resource_mut::<AutoShift>().press_horizontal(-1),
+                20 => world.resource_mut::<AutoShift>().release_horizontal(-1),
+                25 => world.resource_mut::<Input>().rotate = true,
+                40 if frame % 120 == 40 => world.resource_mut::<Input>().hold = true,
+                59 => world.resource_mut::<Input>().hard_drop = true,
+                _ => {}
+            }
+            match frame {
+                500 => world.resource_mut::<GameState>().pause(),
+                560 => world.resource_mut::<GameState>().resume(),
+                _ => {}
+            }
+            recorder.step(&mut world);
+        }
+
+        let replay = recorder.finish(&world);
+        (replay, snapshot(&world))
+    }
+
+    #[test]
+    fn test_replay_plays_back_the_recorded_game() {
+        let (replay, recorded) = record_game(11);
+        assert_eq!(replay.frames, 1500);
+
+        // The file round trip keeps everything the playback needs
+        let json = serde_json::to_string(&replay).unwrap();
+        let replay: Replay = serde_json::from_str(&json).unwrap();
+
+        let mut world = new_world();
+        let mut player = ReplayPlayer::new(replay, &mut world);
+        while player.step(&mut world) {}
+
+        assert!(player.is_finished());
+        assert_eq!(snapshot(&world), recorded);
+        assert!(recorded.0.iter().flatten().any(Option::is_some));
+    }
+
+    #[test]
+    fn test_seeking_back_matches_playing_forward() {
+        let (replay, _) = record_game(23);
+        let mut world = new_world();
+        let mut player = ReplayPlayer::new(replay, &mut world);
+
+        player.seek(&mut world, 900);
+        let forward = snapshot(&world);
+
+        player.seek(&mut world, 300);
+        assert_eq!(player.frame(), 300);
+        player.seek(&mut world, 900);
+        assert_eq!(snapshot(&world), forward);
+
+        // Paused playback doesn't move, and slow speeds bank frames until a whole one is due
+        player.toggle_pause();
+        assert_eq!(player.advance(&mut world, 10), 0);
+        player.toggle_pause();
+        player.slower();
+        player.slower();
+        assert_eq!(player.advance(&mut world, 3), 0);
+        assert_eq!(player.advance(&mut world, 1), 1);
+    }
+
+    #[test]
+    fn test_other_replay_versions_are_rejected() {
+        let (mut replay, _) = record_game(5);
+        replay.version = REPLAY_VERSION + 1;
+
+        let path = std::env::temp_dir().join(format!(
+            "fallingblocks-replay-test-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, serde_json::to_string(&replay).unwrap()).unwrap();
+        let result = Replay::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replays_ending_in_the_same_second_are_both_kept() {
+        let (replay, _) = record_game(5);
+        let dir = std::env::temp_dir().join(format!(
+            "fallingblocks-replay-dir-test-{}",
+            std::process::id()
+        ));
+
+        let first = replay.save_in(&dir).unwrap();
+        let second = replay.save_in(&dir).unwrap();
+        let saved = [Replay::load(&first), Replay::load(&second)];
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_ne!(first, second);
+        assert!(saved.iter().all(|loaded| {
+            loaded
+                .as_ref()
+                .is_ok_and(|loaded| loaded.seed == replay.seed)
+        }));
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01 00:00");
+        assert_eq!(format_date(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14 22:13");
+    }
+}
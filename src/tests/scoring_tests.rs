@@ -0,0 +1,129 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{GameState, TSpin};
+    use crate::scoring::ScoringRuleKind;
+
+    // Every rule recomputes the level from lines cleared, so start partway into the level
+    fn state_with(rule: ScoringRuleKind, level: u32) -> GameState {
+        GameState {
+            scoring_rule: rule,
+            level,
+            lines_cleared: (level - 1) * crate::game::LINES_PER_LEVEL,
+            ..GameState::default()
+        }
+    }
+
+    #[test]
+    fn test_classic_rule_is_the_default() {
+        let mut game_state = GameState::default();
+        assert_eq!(game_state.scoring_rule, ScoringRuleKind::Classic);
+
+        game_state.update_score(4, TSpin::None, false);
+        assert_eq!(game_state.score, crate::game::POINTS_TETRIS);
+    }
+
+    #[test]
+    fn test_guideline_line_clears_scale_with_level() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 3);
+
+        game_state.update_score(1, TSpin::None, false);
+        assert_eq!(game_state.score, 100 * 3);
+
+        game_state.combo_count = 0;
+        game_state.update_score(3, TSpin::None, false);
+        assert_eq!(game_state.score, 100 * 3 + 500 * 3);
+    }
+
+    #[test]
+    fn test_guideline_t_spin_minis() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 2);
+
+        game_state.update_score(0, TSpin::Mini, false);
+        assert_eq!(game_state.score, 100 * 2);
+
+        game_state.update_score(1, TSpin::Mini, false);
+        assert_eq!(game_state.score, 100 * 2 + 200 * 2);
+        assert!(game_state.back_to_back);
+    }
+
+    #[test]
+    fn test_guideline_back_to_back_applies_to_level_multiplied_value() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 3);
+
+        game_state.update_score(4, TSpin::None, false);
+        assert_eq!(game_state.score, 800 * 3);
+
+        // Break the combo so only the back-to-back bonus applies
+        game_state.update_score(0, TSpin::None, false);
+        game_state.update_score(2, TSpin::Full, false);
+        assert_eq!(game_state.score, 800 * 3 + 1200 * 3 * 3 / 2);
+
+        // A plain clear breaks the chain
+        game_state.update_score(0, TSpin::None, false);
+        game_state.update_score(1, TSpin::None, false);
+        assert!(!game_state.back_to_back);
+    }
+
+    #[test]
+    fn test_guideline_combo_is_fifty_per_step_per_level() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 2);
+
+        game_state.update_score(1, TSpin::None, false);
+        game_state.update_score(1, TSpin::None, false);
+        game_state.update_score(1, TSpin::None, false);
+
+        let singles = 3 * 100 * 2;
+        let combo = 50 * 2 + 50 * 2 * 2;
+        assert_eq!(game_state.score, singles + combo);
+    }
+
+    #[test]
+    fn test_guideline_pays_drops_on_every_lock() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 1);
+        game_state.soft_drop_distance = 3;
+        game_state.hard_drop_distance = 5;
+
+        game_state.update_score(0, TSpin::None, false);
+        assert_eq!(game_state.score, 3 + 5 * 2);
+        assert_eq!(game_state.soft_drop_distance, 0);
+        assert_eq!(game_state.hard_drop_distance, 0);
+    }
+
+    #[test]
+    fn test_guideline_levels_by_lines_only() {
+        let mut game_state = state_with(ScoringRuleKind::Guideline, 1);
+        game_state.score = 100_000;
+        game_state.lines_cleared = 9;
+
+        game_state.update_score(1, TSpin::None, false);
+        assert_eq!(game_state.level, 2);
+    }
+
+    #[test]
+    fn test_nes_scoring_ignores_t_spins_and_combos() {
+        let mut game_state = state_with(ScoringRuleKind::Nes, 5);
+        game_state.soft_drop_distance = 4;
+        game_state.hard_drop_distance = 10;
+
+        game_state.update_score(4, TSpin::None, false);
+        assert_eq!(game_state.score, 1200 * 5 + 4);
+
+        game_state.update_score(4, TSpin::None, false);
+        game_state.update_score(2, TSpin::Full, false);
+        assert_eq!(game_state.score, 1200 * 5 + 4 + 1200 * 5 + 100 * 5);
+        assert!(!game_state.back_to_back);
+    }
+
+    #[test]
+    fn test_reset_keeps_scoring_rule() {
+        let mut game_state = state_with(ScoringRuleKind::Nes, 4);
+        game_state.score = 1234;
+
+        game_state.reset();
+        assert_eq!(game_state.scoring_rule, ScoringRuleKind::Nes);
+        assert_eq!(game_state.score, 0);
+        assert_eq!(game_state.level, crate::game::STARTING_LEVEL);
+    }
+}
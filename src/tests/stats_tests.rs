@@ -0,0 +1,100 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::{Board, Input, TSpin, TetrominoType};
+    use crate::config::Config;
+    use crate::mode::GameModeKind;
+    use crate::sound::AudioState;
+    use crate::stats::{GameStats, StatKind};
+    use crate::systems::step_frame;
+    use bevy_ecs::prelude::*;
+
+    #[test]
+    fn test_attack_follows_the_guideline_table() {
+        let mut stats = GameStats::default();
+        let sent = |stats: &mut GameStats, lines, t_spin, perfect| {
+            let before = stats.attack;
+            stats.record_lock(TetrominoType::I, lines, t_spin, perfect, 0);
+            stats.attack - before
+        };
+
+        assert_eq!(sent(&mut stats, 4, TSpin::None, false), 4);
+        // Back-to-back adds a line, the combo only starts paying from its third clear
+        assert_eq!(sent(&mut stats, 4, TSpin::None, false), 5);
+        assert_eq!(sent(&mut stats, 1, TSpin::None, false), 1);
+        // A piece that clears nothing ends the combo but not the back-to-back chain
+        assert_eq!(sent(&mut stats, 0, TSpin::None, false), 0);
+        assert_eq!(sent(&mut stats, 2, TSpin::Full, false), 4);
+        assert_eq!(sent(&mut stats, 0, TSpin::None, false), 0);
+        assert_eq!(sent(&mut stats, 1, TSpin::None, true), 10);
+        assert_eq!(stats.attack, 24);
+    }
+
+    #[test]
+    fn test_piece_counts_drought_and_rates() {
+        let mut stats = GameStats::default();
+        for piece in [
+            TetrominoType::T,
+            TetrominoType::S,
+            TetrominoType::Z,
+            TetrominoType::I,
+            TetrominoType::O,
+        ] {
+            stats.record_lock(piece, 0, TSpin::None, false, 1);
+        }
+        stats.keys = 15;
+
+        assert_eq!(stats.pieces, 5);
+        assert_eq!(stats.piece_counts, [1, 0, 0, 1, 1, 1, 1]);
+        assert_eq!(stats.i_drought, 1);
+        assert_eq!(stats.max_i_drought, 3);
+        assert_eq!(stats.holes_created, 5);
+        assert_eq!(stats.value(StatKind::Pps, 2.0), "2.50");
+        assert_eq!(stats.value(StatKind::Kpp, 2.0), "3.00");
+        assert_eq!(stats.value(StatKind::Apm, 0.0), "0.0");
+    }
+
+    #[test]
+    fn test_board_holes_count_covered_cells() {
+        let mut board = Board::new(10, 20);
+        board.set_cell(0, 19, Some(TetrominoType::O));
+        assert_eq!(board.holes(), 0);
+
+        // A block over two empty cells makes two holes, open cells beside it don't count
+        board.set_cell(3, 17, Some(TetrominoType::T));
+        assert_eq!(board.holes(), 2);
+        board.set_cell(3, 19, Some(TetrominoType::T));
+        assert_eq!(board.holes(), 1);
+    }
+
+    #[test]
+    fn test_game_systems_keep_the_stats() {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        let ruleset = Config::default().ruleset(GameModeKind::Marathon);
+        start_game(&mut world, GameModeKind::Marathon, &ruleset, 3);
+
+        // Keys land a second apart
+        for frame in 0..180 {
+            {
+                let mut input = world.resource_mut::<Input>();
+                match frame {
+                    0 => {
+                        input.rotate = true;
+                        input.left = true;
+                    }
+                    60 | 120 => input.hard_drop = true,
+                    _ => {}
+                }
+            }
+            step_frame(&mut world);
+        }
+
+        let stats = world.resource::<GameStats>();
+        assert_eq!(stats.pieces, 2);
+        assert_eq!(stats.keys, 4);
+        assert_eq!(stats.piece_counts.iter().sum::<u32>(), 2);
+    }
+}
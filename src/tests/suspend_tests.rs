@@ -0,0 +1,186 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+#[cfg(test)]
+mod tests {
+    use crate::app::start_game;
+    use crate::components::{
+        Board, GameState, Handling, Input, Position, Tetromino, TetrominoType,
+    };
+    use crate::config::Config;
+    use crate::mode::GameModeKind;
+    use crate::randomizer::{PieceRandomizer, RandomizerKind};
+    use crate::replay::{ReplayPlayer, ReplayRecorder};
+    use crate::sound::AudioState;
+    use crate::suspend::{SUSPEND_VERSION, SuspendedGame};
+    use crate::systems::step_frame;
+    use bevy_ecs::prelude::*;
+
+    fn new_game(randomizer: RandomizerKind) -> World {
+        let mut world = World::new();
+        world.insert_resource(AudioState::new());
+        let mut ruleset = Config::default().ruleset(GameModeKind::Sprint);
+        ruleset.randomizer = randomizer;
+        start_game(&mut world, GameModeKind::Sprint, &ruleset, 42);
+        world
+    }
+
+    // Drops a piece every half second, holding now and then
+    fn play(world: &mut World, frames: u32) {
+        for frame in 0..frames {
+            {
+                let mut input = world.resource_mut::<Input>();
+                match frame % 30 {
+                    7 => input.left = true,
+                    12 if frame % 90 == 12 => input.hold = true,
+                    29 => input.hard_drop = true,
+                    _ => {}
+                }
+            }
+            step_frame(world);
+        }
+    }
+
+    fn piece(world: &mut World) -> Option<(TetrominoType, usize, Position)> {
+        world
+            .query::<(&Tetromino, &Position)>()
+            .iter(world)
+            .next()
+            .map(|(tetromino, position)| (tetromino.tetromino_type, tetromino.rotation, *position))
+    }
+
+    #[test]
+    fn test_continued_game_plays_on_exactly() {
+        for randomizer in [RandomizerKind::SevenBag, RandomizerKind::Tgm] {
+            let mut original = new_game(randomizer);
+            play(&mut original, 200);
+
+            let json = serde_json::to_string(&SuspendedGame::capture(&mut original, None)).unwrap();
+            let mut continued = new_game(RandomizerKind::Random);
+            let _ = SuspendedGame::from_json(&json)
+                .unwrap()
+                .restore(&mut continued);
+
+            // The continued game waits on the pause menu, the original is paused to match
+            assert!(continued.resource::<GameState>().paused);
+            original.resource_mut::<GameState>().pause();
+            assert_eq!(piece(&mut continued), piece(&mut original));
+
+            for world in [&mut original, &mut continued] {
+                world.resource_mut::<GameState>().resume();
+                play(world, 600);
+            }
+
+            assert_eq!(
+                continued.resource::<Board>().cells,
+                original.resource::<Board>().cells
+            );
+            let (continued_state, original_state) = (
+                continued.resource::<GameState>(),
+                original.resource::<GameState>(),
+            );
+            assert_eq!(continued_state.score, original_state.score);
+            assert_eq!(continued_state.next_queue, original_state.next_queue);
+            assert_eq!(
+                continued_state.held_tetromino,
+                original_state.held_tetromino
+            );
+            assert_eq!(
+                continued.resource::<PieceRandomizer>().state(),
+                original.resource::<PieceRandomizer>().state()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bad_suspend_files_are_rejected() {
+        let mut world = new_game(RandomizerKind::SevenBag);
+        let mut saved: serde_json::Value =
+            serde_json::to_value(SuspendedGame::capture(&mut world, None)).unwrap();
+
+        assert!(SuspendedGame::from_json("{ not json").is_err());
+
+        // A file from another version is turned away before it's looked at any further
+        let mut other_version = saved.clone();
+        other_version["version"] = serde_json::json!(SUSPEND_VERSION + 1);
+        let err = SuspendedGame::from_json(&other_version.to_string())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("different version"));
+
+        // A board whose columns don't match its width would index out of bounds later
+        saved["board"]["width"] = serde_json::json!(12);
+        assert!(SuspendedGame::from_json(&saved.to_string()).is_err());
+    }
+
+    // Plays a recorded game up to `suspend_at` frames in, with `keys` pressing keys on the way,
+    // suspends and continues it, then plays on and returns the continued world and the world its
+    // replay played back into
+    fn continue_recorded_game(
+        entry_delay_ms: u32,
+        suspend_at: u32,
+        keys: fn(u32, &mut Input),
+    ) -> (World, World) {
+        let mut world = World::new();
+        let mut ruleset = Config::default().ruleset(GameModeKind::Sprint);
+        ruleset.timings.entry_delay_ms = entry_delay_ms;
+        start_game(&mut world, GameModeKind::Sprint, &ruleset, 42);
+        let mut recorder =
+            ReplayRecorder::new(42, GameModeKind::Sprint, ruleset, Handling::default());
+        for frame in 0..suspend_at {
+            keys(frame, &mut world.resource_mut::<Input>());
+            recorder.step(&mut world);
+        }
+
+        let json =
+            serde_json::to_string(&SuspendedGame::capture(&mut world, Some(recorder))).unwrap();
+        let mut continued = World::new();
+        let mut recorder = SuspendedGame::from_json(&json)
+            .unwrap()
+            .restore(&mut continued)
+            .unwrap();
+
+        // Resume and move as soon as the countdown is over, then keep dropping pieces
+        continued.resource_mut::<GameState>().resume();
+        for frame in 0..400 {
+            {
+                let mut input = continued.resource_mut::<Input>();
+                match frame % 40 {
+                    _ if (180..190).contains(&frame) => input.left = true,
+                    20 => input.hard_drop = true,
+                    _ => {}
+                }
+            }
+            recorder.step(&mut continued);
+        }
+
+        let mut playback = World::new();
+        let mut player = ReplayPlayer::new(recorder.finish(&continued), &mut playback);
+        while player.step(&mut playback) {}
+        (continued, playback)
+    }
+
+    #[test]
+    fn test_continued_game_matches_its_replay() {
+        // Quit while a drop's screen shake is still running
+        let during_shake = continue_recorded_game(0, 8, |frame, input| {
+            input.hard_drop = frame == 5;
+        });
+        // Quit during the entry delay with the next piece already turned
+        let during_entry_delay = continue_recorded_game(500, 12, |frame, input| {
+            input.hard_drop = frame == 5;
+            input.rotate = frame == 10;
+        });
+
+        for (mut continued, mut playback) in [during_shake, during_entry_delay] {
+            assert_eq!(
+                playback.resource::<Board>().cells,
+                continued.resource::<Board>().cells
+            );
+            assert_eq!(
+                playback.resource::<GameState>().score,
+                continued.resource::<GameState>().score
+            );
+            assert_eq!(piece(&mut playback), piece(&mut continued));
+        }
+    }
+}
@@ -4,8 +4,12 @@
 mod tests {
     use crate::Time;
     use crate::components::*;
-    use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
-    use crate::systems::{input_system, spawn_tetromino};
+    use crate::events::{HoldUsed, LinesCleared, PieceLocked};
+    use crate::game::{BOARD_HEIGHT, BOARD_WIDTH, MAX_PREVIEW_COUNT};
+    use crate::pieces::PieceSet;
+    use crate::randomizer::{RandomizerKind, seed_game};
+    use crate::stats::GameStats;
+    use crate::systems::{auto_shift_system, input_system, spawn_tetromino, step_frame};
     use bevy_ecs::prelude::*;
 
     // Helper function to create a test world
@@ -25,8 +29,8 @@ mod tests {
         let input = Input::default();
         world.insert_resource(input);
 
-        let coyote_time = CoyoteTime::default();
-        world.insert_resource(coyote_time);
+        let lock_delay = LockDelay::default();
+        world.insert_resource(lock_delay);
 
         let screen_shake = ScreenShake {
             intensity: 0.0,
@@ -41,9 +45,18 @@ mod tests {
         let audio_state = crate::sound::AudioState::new();
         world.insert_resource(audio_state);
 
+        world.insert_resource(crate::mode::GameMode::default());
+
         world
     }
 
+    // Runs the game on by whole frames with no keys pressed
+    fn run_frames(world: &mut World, frames: u32) {
+        for _ in 0..frames {
+            step_frame(world);
+        }
+    }
+
     #[test]
     fn test_spawn_tetromino() {
         let mut world = setup_test_world();
@@ -128,6 +141,58 @@ mod tests {
         assert_ne!(new_rotation, initial_rotation);
     }
 
+    #[test]
+    fn test_counter_clockwise_and_180_rotation_inputs() {
+        let mut world = setup_test_world();
+
+        let entity = world
+            .spawn((
+                Tetromino::new(TetrominoType::T),
+                Position { x: 5, y: 5 },
+                Ghost {
+                    position: Position { x: 5, y: 5 },
+                },
+            ))
+            .id();
+
+        world.resource_mut::<Input>().rotate_ccw = true;
+        input_system(&mut world);
+        assert_eq!(world.get::<Tetromino>(entity).unwrap().rotation, 3);
+
+        *world.resource_mut::<Input>() = Input::default();
+        world.resource_mut::<Input>().rotate_180 = true;
+        input_system(&mut world);
+        assert_eq!(world.get::<Tetromino>(entity).unwrap().rotation, 1);
+    }
+
+    #[test]
+    fn test_rotation_wall_kick() {
+        let mut world = setup_test_world();
+
+        // A T pointing right, flush against the left wall
+        let mut tetromino = Tetromino::new(TetrominoType::T);
+        tetromino.rotate();
+        let entity = world
+            .spawn((
+                tetromino,
+                Position { x: 0, y: 5 },
+                Ghost {
+                    position: Position { x: 0, y: 5 },
+                },
+            ))
+            .id();
+
+        world.resource_mut::<Input>().rotate = true;
+        input_system(&mut world);
+
+        // The rotation goes through by kicking the piece away from the wall
+        assert_eq!(world.get::<Tetromino>(entity).unwrap().rotation, 2);
+        assert_eq!(
+            *world.get::<Position>(entity).unwrap(),
+            Position { x: 1, y: 5 }
+        );
+    }
+
     #[test]
     fn test_next_tetromino_preview() {
         let mut world = setup_test_world();
@@ -135,10 +200,10 @@ mod tests {
         // Initialize the next tetromino in GameState
         {
             let mut game_state = world.resource_mut::<GameState>();
-            game_state.next_tetromino = Some(TetrominoType::T);
+            game_state.next_queue.push_back(TetrominoType::T);
         }
 
-        // Spawn a tetromino, which should use the next_tetromino we just set
+        // Spawn a tetromino, which should use the queued piece we just set
         spawn_tetromino(&mut world);
 
         // Check if the active tetromino is of type T
@@ -151,10 +216,10 @@ mod tests {
 
         assert_eq!(active_tetromino_type, TetrominoType::T);
 
-        // Verify that a new next_tetromino was generated
+        // Verify that the next queue was refilled
         {
             let game_state = world.resource::<GameState>();
-            assert!(game_state.next_tetromino.is_some());
+            assert_eq!(game_state.next_queue.len(), MAX_PREVIEW_COUNT);
         }
 
         // Get the entity ID of the first tetromino
@@ -174,4 +239,665 @@ mod tests {
         let tetromino_count = world.query::<&Tetromino>().iter(&world).count();
         assert_eq!(tetromino_count, 1);
     }
+
+    #[test]
+    fn test_next_queue_deals_in_order() {
+        let mut world = setup_test_world();
+        spawn_tetromino(&mut world);
+
+        // Every queued piece should be dealt in turn, with the queue staying full
+        let queued: Vec<TetrominoType> = world
+            .resource::<GameState>()
+            .next_queue
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(queued.len(), MAX_PREVIEW_COUNT);
+
+        for expected in queued {
+            spawn_tetromino(&mut world);
+            let active_type = world
+                .query::<&Tetromino>()
+                .iter(&world)
+                .next()
+                .unwrap()
+                .tetromino_type;
+            assert_eq!(active_type, expected);
+            assert_eq!(
+                world.resource::<GameState>().next_queue.len(),
+                MAX_PREVIEW_COUNT
+            );
+        }
+    }
+
+    // Returns the position of the active tetromino
+    fn active_position(world: &mut World) -> Position {
+        *world
+            .query_filtered::<&Position, With<Tetromino>>()
+            .iter(world)
+            .next()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_auto_shift_zero_arr_reaches_wall() {
+        let mut world = setup_test_world();
+        world.insert_resource(AutoShift::default());
+        world.insert_resource(Handling {
+            das_ms: 100,
+            arr_ms: 0,
+            soft_drop_factor: 20,
+        });
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.next_queue.push_back(TetrominoType::O);
+        }
+        spawn_tetromino(&mut world);
+        let start = active_position(&mut world);
+
+        // Holding right does nothing until DAS charges
+        world.resource_mut::<AutoShift>().press_horizontal(1);
+        auto_shift_system(&mut world, 0.05);
+        assert_eq!(active_position(&mut world).x, start.x);
+
+        // Then the piece goes straight to the right wall
+        auto_shift_system(&mut world, 0.06);
+        let right_wall = i32::try_from(BOARD_WIDTH).unwrap();
+        assert_eq!(active_position(&mut world).x, right_wall - 2);
+    }
+
+    #[test]
+    fn test_held_soft_drop_uses_soft_drop_factor() {
+        let mut world = setup_test_world();
+        world.insert_resource(AutoShift::default());
+        world.insert_resource(Handling {
+            das_ms: 167,
+            arr_ms: 33,
+            soft_drop_factor: 10,
+        });
+        spawn_tetromino(&mut world);
+        let start = active_position(&mut world);
+
+        // Holding down falls ten times faster than gravity
+        let drop_delay = world.resource::<GameState>().get_drop_delay();
+        world.resource_mut::<AutoShift>().press_down();
+        auto_shift_system(&mut world, drop_delay * 0.35);
+
+        assert_eq!(active_position(&mut world).y, start.y + 3);
+        assert_eq!(world.resource::<GameState>().soft_drop_distance, 3);
+    }
+
+    #[test]
+    fn test_fast_gravity_falls_several_rows_per_tick() {
+        let mut world = setup_test_world();
+        world.resource_mut::<GameState>().level = 15;
+        spawn_tetromino(&mut world);
+        let start = active_position(&mut world);
+
+        // Over 2G, three frames cover several rows
+        run_frames(&mut world, 3);
+        assert!(active_position(&mut world).y > start.y + 1);
+    }
+
+    #[test]
+    fn test_spawn_drops_into_view_from_the_buffer() {
+        let mut world = setup_test_world();
+        world.insert_resource(Board::new(4, 12));
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_front(TetrominoType::I);
+        spawn_tetromino(&mut world);
+
+        // The I piece spawns centered just above the field and drops one row onto the top row
+        let position = active_position(&mut world);
+        let tetromino = Tetromino::new(TetrominoType::I);
+        let rows: Vec<i32> = world
+            .resource::<Board>()
+            .piece_set
+            .blocks(&tetromino)
+            .iter()
+            .map(|&(_, y)| position.y + y)
+            .collect();
+        assert_eq!(position.x, 1);
+        assert!(rows.iter().all(|&row| row == 0));
+
+        // With the top row taken it stays in the buffer instead of topping out
+        let mut world = setup_test_world();
+        {
+            let mut board = world.resource_mut::<Board>();
+            for x in 0..board.width {
+                board.cells[x][0] = Some(TetrominoType::O);
+            }
+        }
+        spawn_tetromino(&mut world);
+        assert!(!world.resource::<GameState>().game_over);
+        assert!(active_position(&mut world).y < 0);
+    }
+
+    // Fills the visible field from the given row down, leaving the rightmost column open so no
+    // lines clear, then spawns an O piece and hard drops it
+    fn hard_drop_o_onto_stack(world: &mut World, from_row: usize) {
+        {
+            let mut board = world.resource_mut::<Board>();
+            for x in 0..board.width - 1 {
+                for y in from_row..board.height {
+                    board.cells[x][y] = Some(TetrominoType::Z);
+                }
+            }
+        }
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_front(TetrominoType::O);
+        spawn_tetromino(world);
+
+        {
+            let mut input = world.resource_mut::<Input>();
+            input.hard_drop = true;
+            input.hard_drop_released = true;
+        }
+        input_system(world);
+    }
+
+    #[test]
+    fn test_locking_above_the_field_is_lock_out() {
+        let mut world = setup_test_world();
+        hard_drop_o_onto_stack(&mut world, 0);
+
+        let game_state = world.resource::<GameState>();
+        assert!(game_state.game_over);
+        assert_eq!(game_state.top_out, Some(TopOut::LockOut));
+    }
+
+    #[test]
+    fn test_partial_lock_out_is_optional() {
+        // By default a piece poking above the field stays there, and here the next piece can't
+        // spawn over it so that one blocks out instead
+        let mut world = setup_test_world();
+        hard_drop_o_onto_stack(&mut world, 1);
+        assert!(!world.resource::<Board>().is_buffer_empty());
+        assert_eq!(
+            world.resource::<GameState>().top_out,
+            Some(TopOut::BlockOut)
+        );
+
+        let mut world = setup_test_world();
+        world.resource_mut::<GameState>().partial_lock_out = true;
+        hard_drop_o_onto_stack(&mut world, 1);
+
+        let game_state = world.resource::<GameState>();
+        assert!(game_state.game_over);
+        assert_eq!(game_state.top_out, Some(TopOut::PartialLockOut));
+    }
+
+    #[test]
+    fn test_pause_freezes_gravity_until_countdown_ends() {
+        let mut world = setup_test_world();
+        world.resource_mut::<GameState>().level = 10;
+        spawn_tetromino(&mut world);
+        let start = active_position(&mut world);
+
+        world.resource_mut::<GameState>().pause();
+        run_frames(&mut world, 120);
+        assert_eq!(active_position(&mut world), start);
+
+        // Resuming counts down before the piece falls again
+        world.resource_mut::<GameState>().resume();
+        run_frames(&mut world, 120);
+        assert_eq!(active_position(&mut world), start);
+        assert!(world.resource::<GameState>().is_frozen());
+
+        // The last second, plus a frame as sixtieths of a second add up to a hair under it
+        run_frames(&mut world, 61);
+        assert!(!world.resource::<GameState>().is_frozen());
+        run_frames(&mut world, 6);
+        assert!(active_position(&mut world).y > start.y);
+    }
+
+    // Plays a scripted game from a seed, returning the final board, score and upcoming pieces
+    fn play_scripted_game(seed: u64) -> (Vec<Vec<Option<TetrominoType>>>, u32, Vec<TetrominoType>) {
+        let mut world = setup_test_world();
+        seed_game(
+            &mut world,
+            RandomizerKind::SevenBag,
+            &PieceSet::classic(),
+            seed,
+        );
+        for frame in 0..1200 {
+            {
+                let mut input = world.resource_mut::<Input>();
+                match frame % 40 {
+                    5 => input.left = true,
+                    15 => input.rotate = true,
+                    25 if frame % 80 == 25 => input.right = true,
+                    39 => {
+                        input.hard_drop = true;
+                        input.hard_drop_released = true;
+                    }
+                    _ => {}
+                }
+            }
+            step_frame(&mut world);
+        }
+
+        let board = world.resource::<Board>().cells.clone();
+        let game_state = world.resource::<GameState>();
+        (
+            board,
+            game_state.score,
+            game_state.next_queue.iter().copied().collect(),
+        )
+    }
+
+    #[test]
+    fn test_same_seed_and_inputs_replay_identically() {
+        let first = play_scripted_game(7);
+        let second = play_scripted_game(7);
+        assert_eq!(first, second);
+
+        // Something was actually played
+        assert!(first.0.iter().flatten().any(Option::is_some));
+    }
+
+    #[test]
+    fn test_twenty_g_spawns_on_the_stack() {
+        let mut world = setup_test_world();
+        world.resource_mut::<GameState>().level = 20;
+        spawn_tetromino(&mut world);
+
+        let (tetromino, position) = world
+            .query::<(&Tetromino, &Position)>()
+            .iter(&world)
+            .next()
+            .map(|(tetromino, position)| (*tetromino, *position))
+            .unwrap();
+        let below = Position {
+            x: position.x,
+            y: position.y + 1,
+        };
+        assert!(position.y > 0);
+        assert!(
+            !world
+                .resource::<Board>()
+                .is_valid_position(below, &tetromino)
+        );
+    }
+
+    #[test]
+    fn test_soft_drop_onto_stack_waits_for_lock_delay() {
+        let mut world = setup_test_world();
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.next_queue.push_back(TetrominoType::O);
+        }
+        spawn_tetromino(&mut world);
+
+        // Drop the piece onto the floor without locking it
+        let floor = i32::try_from(BOARD_HEIGHT).unwrap() - 1;
+        let start = active_position(&mut world);
+        let grounded = Position {
+            x: start.x,
+            y: floor,
+        };
+        let entity = world
+            .query_filtered::<Entity, With<Tetromino>>()
+            .iter(&world)
+            .next()
+            .unwrap();
+        world.entity_mut(entity).insert(grounded);
+
+        // Pressing soft drop on the stack leaves the piece in play
+        world.resource_mut::<Input>().down = true;
+        input_system(&mut world);
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 1);
+        assert!(
+            world
+                .resource::<Board>()
+                .cells
+                .iter()
+                .flatten()
+                .all(Option::is_none)
+        );
+
+        // It only locks once it has rested for the whole lock delay
+        run_frames(&mut world, 18);
+        assert_eq!(active_position(&mut world), grounded);
+        assert!(world.resource::<LockDelay>().active);
+
+        run_frames(&mut world, 18);
+        assert!(
+            world
+                .resource::<Board>()
+                .cells
+                .iter()
+                .flatten()
+                .any(Option::is_some)
+        );
+    }
+
+    #[test]
+    fn test_move_on_ground_resets_lock_delay() {
+        let mut world = setup_test_world();
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.next_queue.push_back(TetrominoType::O);
+        }
+        spawn_tetromino(&mut world);
+
+        let floor = i32::try_from(BOARD_HEIGHT).unwrap() - 1;
+        let start = active_position(&mut world);
+        let entity = world
+            .query_filtered::<Entity, With<Tetromino>>()
+            .iter(&world)
+            .next()
+            .unwrap();
+        world.entity_mut(entity).insert(Position {
+            x: start.x,
+            y: floor,
+        });
+
+        // The piece rests on the floor a while before moving
+        run_frames(&mut world, 24);
+        world.resource_mut::<Input>().left = true;
+        input_system(&mut world);
+        assert_eq!(world.resource::<LockDelay>().resets, 1);
+
+        // The move restarted the timer, so the piece is still in play past the original deadline
+        run_frames(&mut world, 24);
+        assert!(
+            world
+                .resource::<Board>()
+                .cells
+                .iter()
+                .flatten()
+                .all(Option::is_none)
+        );
+    }
+
+    // Sets up a T-spin double slot and puts a downward T in it, as if it got there by `last_action`
+    fn place_t_in_slot(world: &mut World, last_action: LastAction) {
+        {
+            let mut board = world.resource_mut::<Board>();
+            for x in 0..BOARD_WIDTH {
+                if x != 4 {
+                    board.cells[x][19] = Some(TetrominoType::I);
+                }
+                if !(3..=5).contains(&x) {
+                    board.cells[x][18] = Some(TetrominoType::I);
+                }
+            }
+            board.cells[3][17] = Some(TetrominoType::J);
+        }
+
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_back(TetrominoType::T);
+        spawn_tetromino(world);
+
+        let entity = world
+            .query_filtered::<Entity, With<Tetromino>>()
+            .iter(world)
+            .next()
+            .unwrap();
+        let mut tetromino = Tetromino::new(TetrominoType::T);
+        tetromino.rotate_180();
+        world
+            .entity_mut(entity)
+            .insert((tetromino, Position { x: 4, y: 18 }, last_action));
+    }
+
+    #[test]
+    fn test_hard_drop_after_rotation_scores_t_spin() {
+        let mut world = setup_test_world();
+        place_t_in_slot(&mut world, LastAction::Rotate { kick: 0 });
+
+        world.resource_mut::<Input>().hard_drop = true;
+        input_system(&mut world);
+
+        let game_state = world.resource::<GameState>();
+        assert_eq!(game_state.t_spin_count, 1);
+        assert!(game_state.back_to_back);
+    }
+
+    #[test]
+    fn test_hard_drop_after_move_is_not_t_spin() {
+        let mut world = setup_test_world();
+        place_t_in_slot(&mut world, LastAction::Move);
+
+        world.resource_mut::<Input>().hard_drop = true;
+        input_system(&mut world);
+
+        // The lines still clear, they just don't count as a T-spin
+        let game_state = world.resource::<GameState>();
+        assert_eq!(game_state.t_spin_count, 0);
+        assert!(!game_state.back_to_back);
+        assert_eq!(game_state.lines_cleared, 2);
+    }
+
+    #[test]
+    fn test_line_clear_and_entry_delays() {
+        let mut world = setup_test_world();
+        world.insert_resource(Timings {
+            lock_delay_ms: 500,
+            line_clear_delay_ms: 300,
+            entry_delay_ms: 200,
+        });
+
+        // Two rows with a gap the O fills
+        {
+            let mut board = world.resource_mut::<Board>();
+            for x in (0..BOARD_WIDTH).filter(|&x| x != 4 && x != 5) {
+                board.cells[x][18] = Some(TetrominoType::Z);
+                board.cells[x][19] = Some(TetrominoType::Z);
+            }
+        }
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_front(TetrominoType::O);
+        spawn_tetromino(&mut world);
+        {
+            let mut input = world.resource_mut::<Input>();
+            input.hard_drop = true;
+            input.hard_drop_released = true;
+        }
+        input_system(&mut world);
+
+        // The lines score straight away, but the full rows stay up and flash with no piece dealt
+        assert_eq!(world.resource::<GameState>().lines_cleared, 2);
+        assert_eq!(world.resource::<Phase>().flashing_rows(), [18, 19]);
+        assert!(
+            world
+                .resource::<Board>()
+                .cells
+                .iter()
+                .all(|column| column[18].is_some() && column[19].is_some())
+        );
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 0);
+
+        run_frames(&mut world, 9);
+        assert!(world.resource::<Phase>().flashing_rows().is_empty());
+        assert!(!world.resource::<Board>().is_empty());
+
+        // Once the clear is over the rows go, then the board waits out the entry delay
+        run_frames(&mut world, 9);
+        assert!(world.resource::<Board>().is_empty());
+        assert_eq!(*world.resource::<Phase>(), Phase::EntryDelay { timer: 0.0 });
+
+        run_frames(&mut world, 6);
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 0);
+
+        run_frames(&mut world, 6);
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 1);
+        assert_eq!(*world.resource::<Phase>(), Phase::Falling);
+    }
+
+    #[test]
+    fn test_initial_rotation_and_hold() {
+        let mut world = setup_test_world();
+        world.insert_resource(Timings {
+            entry_delay_ms: 200,
+            ..Timings::default()
+        });
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_front(TetrominoType::O);
+        spawn_tetromino(&mut world);
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.next_queue[0] = TetrominoType::T;
+            game_state.next_queue[1] = TetrominoType::J;
+        }
+        {
+            let mut input = world.resource_mut::<Input>();
+            input.hard_drop = true;
+            input.hard_drop_released = true;
+        }
+        input_system(&mut world);
+
+        // Rotating and holding while there's no piece in play waits for the next one
+        {
+            let mut input = world.resource_mut::<Input>();
+            input.rotate = true;
+            input.hold = true;
+        }
+        input_system(&mut world);
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 0);
+        assert_eq!(world.resource::<GameState>().held_tetromino, None);
+
+        // The dealt T goes straight into the hold slot and the J after it spawns turned
+        run_frames(&mut world, 12);
+        let active = *world.query::<&Tetromino>().single(&world);
+        assert_eq!(active.tetromino_type, TetrominoType::J);
+        assert_eq!(active.rotation, 1);
+        let game_state = world.resource::<GameState>();
+        assert_eq!(game_state.held_tetromino, Some(TetrominoType::T));
+        assert!(game_state.hold_used);
+    }
+
+    #[test]
+    fn test_hold_once_per_piece() {
+        let mut world = setup_test_world();
+
+        // Start with a known active piece and next piece
+        {
+            let mut game_state = world.resource_mut::<GameState>();
+            game_state.next_queue.push_back(TetrominoType::T);
+        }
+        spawn_tetromino(&mut world);
+        world.resource_mut::<GameState>().next_queue[0] = TetrominoType::O;
+
+        // First hold stores the T and pulls the O from the preview
+        world.resource_mut::<Input>().hold = true;
+        input_system(&mut world);
+
+        let active_type = world
+            .query::<&Tetromino>()
+            .iter(&world)
+            .next()
+            .unwrap()
+            .tetromino_type;
+        assert_eq!(active_type, TetrominoType::O);
+        {
+            let game_state = world.resource::<GameState>();
+            assert_eq!(game_state.held_tetromino, Some(TetrominoType::T));
+            assert!(game_state.hold_used);
+        }
+
+        // A second hold before the piece locks is ignored
+        world.resource_mut::<Input>().hold = true;
+        input_system(&mut world);
+
+        let active_type = world
+            .query::<&Tetromino>()
+            .iter(&world)
+            .next()
+            .unwrap()
+            .tetromino_type;
+        assert_eq!(active_type, TetrominoType::O);
+        assert_eq!(
+            world.resource::<GameState>().held_tetromino,
+            Some(TetrominoType::T)
+        );
+
+        // Once a new piece is dealt, the held T can be swapped back in
+        spawn_tetromino(&mut world);
+        assert!(!world.resource::<GameState>().hold_used);
+
+        world.resource_mut::<Input>().hold = true;
+        input_system(&mut world);
+
+        let active_type = world
+            .query::<&Tetromino>()
+            .iter(&world)
+            .next()
+            .unwrap()
+            .tetromino_type;
+        assert_eq!(active_type, TetrominoType::T);
+        assert_eq!(world.query::<&Tetromino>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn test_frames_send_game_events() {
+        let mut world = setup_test_world();
+        world.insert_resource(GameStats::default());
+
+        // A row with a gap the O fills
+        {
+            let mut board = world.resource_mut::<Board>();
+            for x in (0..BOARD_WIDTH).filter(|&x| x != 4 && x != 5) {
+                board.cells[x][19] = Some(TetrominoType::Z);
+            }
+        }
+        world
+            .resource_mut::<GameState>()
+            .next_queue
+            .push_front(TetrominoType::T);
+        spawn_tetromino(&mut world);
+        world.resource_mut::<GameState>().next_queue[0] = TetrominoType::O;
+
+        world.resource_mut::<Input>().hold = true;
+        step_frame(&mut world);
+        assert_eq!(
+            world
+                .resource::<Events<HoldUsed>>()
+                .iter_current_update_events()
+                .count(),
+            1
+        );
+        assert_eq!(
+            world.resource::<GameState>().held_tetromino,
+            Some(TetrominoType::T)
+        );
+
+        {
+            let mut input = world.resource_mut::<Input>();
+            input.hard_drop = true;
+            input.hard_drop_released = true;
+        }
+        step_frame(&mut world);
+        let locked: Vec<PieceLocked> = world
+            .resource::<Events<PieceLocked>>()
+            .iter_current_update_events()
+            .copied()
+            .collect();
+        assert_eq!(locked.len(), 1);
+        assert_eq!(locked[0].tetromino.tetromino_type, TetrominoType::O);
+        assert_eq!(locked[0].lines_cleared, 1);
+        let cleared: Vec<LinesCleared> = world
+            .resource::<Events<LinesCleared>>()
+            .iter_current_update_events()
+            .cloned()
+            .collect();
+        assert_eq!(cleared.len(), 1);
+        assert_eq!(cleared[0].rows, [19]);
+
+        // The effects stage followed the lock, and the hold is gone from the queues a frame on
+        assert_eq!(world.resource::<GameStats>().pieces, 1);
+        step_frame(&mut world);
+        assert!(world.resource::<Events<HoldUsed>>().is_empty());
+    }
 }
@@ -2,7 +2,9 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::Time;
+    use crate::game::FIXED_TIMESTEP;
+    use crate::{MAX_CATCH_UP_FRAMES, ManualClock, Time};
+    use std::sync::Arc;
     use std::thread::sleep;
     use std::time::Duration;
 
@@ -45,4 +47,40 @@ mod tests {
         // Allow a small margin for timing discrepancies
         assert!((actual - expected).abs() < 0.1);
     }
+
+    #[test]
+    fn test_fixed_frames_follow_injected_clock() {
+        let clock = ManualClock::new();
+        let mut time = Time::with_clock(Arc::new(clock.clone()));
+
+        // Less than a frame of clock time runs nothing
+        clock.advance(Duration::from_millis(10));
+        time.update();
+        assert!(!time.step());
+        assert_eq!(time.frame(), 0);
+
+        // 40ms in total is two frames with a bit left over for the renderer to interpolate
+        clock.advance(Duration::from_millis(30));
+        time.update();
+        assert!(time.step());
+        assert!(time.step());
+        assert!(!time.step());
+        assert_eq!(time.frame(), 2);
+        let expected_alpha = (0.040 - 2.0 * FIXED_TIMESTEP) / FIXED_TIMESTEP;
+        assert!((time.alpha() - expected_alpha).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_clock_jump_runs_limited_catch_up_frames() {
+        let clock = ManualClock::new();
+        let mut time = Time::with_clock(Arc::new(clock.clone()));
+
+        clock.advance(Duration::from_secs(5));
+        time.update();
+        let mut frames = 0;
+        while time.step() {
+            frames += 1;
+        }
+        assert_eq!(frames, MAX_CATCH_UP_FRAMES);
+    }
 }
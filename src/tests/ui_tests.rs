@@ -3,8 +3,14 @@
 #[cfg(test)]
 mod tests {
     use crate::app::App;
-    use crate::components::{GameState, TetrominoType};
-    use crate::ui::{self, calculate_responsive_board_size, centered_rect, render_next_tetromino};
+    use crate::components::{Board, GameState, TetrominoType};
+    use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
+    use crate::mode::{GameMode, GameModeKind, ModeResult};
+    use crate::pieces::PieceSet;
+    use crate::ui::{
+        self, calculate_responsive_board_size, centered_rect, render_hold_tetromino,
+        render_next_tetromino,
+    };
     use ratatui::{backend::TestBackend, layout::Rect, prelude::*};
 
     // Helper function to create a test terminal
@@ -31,7 +37,7 @@ mod tests {
         // Test with a standard 80x24 terminal size
         let area = Rect::new(0, 0, 80, 24);
         let (board_width, board_height, cell_width, cell_height) =
-            calculate_responsive_board_size(area);
+            calculate_responsive_board_size(area, BOARD_WIDTH, BOARD_HEIGHT);
 
         // Board should fit within the area
         assert!(board_width <= area.width);
@@ -47,7 +53,7 @@ mod tests {
         // Test with a very small terminal - increase the height to 20 to ensure it's big enough
         let small_area = Rect::new(0, 0, 30, 20);
         let (small_board_width, _small_board_height, small_cell_width, small_cell_height) =
-            calculate_responsive_board_size(small_area);
+            calculate_responsive_board_size(small_area, BOARD_WIDTH, BOARD_HEIGHT);
 
         // Board should still fit
         assert!(small_board_width <= small_area.width);
@@ -59,7 +65,7 @@ mod tests {
         // Test with a very large terminal
         let large_area = Rect::new(0, 0, 200, 100);
         let (large_board_width, large_board_height, large_cell_width, large_cell_height) =
-            calculate_responsive_board_size(large_area);
+            calculate_responsive_board_size(large_area, BOARD_WIDTH, BOARD_HEIGHT);
 
         // Board should be larger with a bigger terminal
         assert!(large_board_width > board_width);
@@ -70,6 +76,35 @@ mod tests {
         assert!(large_cell_height >= cell_height);
     }
 
+    #[test]
+    fn test_board_layout_adapts_to_width() {
+        for columns in [4, 20] {
+            let mut app = App::new();
+            app.menu.state = crate::menu_types::MenuState::Game;
+            let mut board = Board::new(columns, BOARD_HEIGHT);
+            for x in 0..columns {
+                board.cells[x][BOARD_HEIGHT - 1] = Some(TetrominoType::Z);
+            }
+            app.world.insert_resource(board);
+
+            let mut terminal = create_test_terminal(100, 40);
+            terminal.draw(|f| ui::render(f, &mut app)).unwrap();
+            assert!(!app.world.resource::<GameState>().was_paused_for_resize);
+
+            // The bottom row is drawn across every column, each cell_width characters wide
+            let area = Rect::new(0, 0, 100, 40);
+            let (_, _, cell_width, _) =
+                calculate_responsive_board_size(area, columns, BOARD_HEIGHT);
+            let buffer = terminal.backend().buffer();
+            let z_color = PieceSet::classic().color(TetrominoType::Z);
+            let widest_row = (0..40)
+                .map(|y| (0..100).filter(|&x| buffer[(x, y)].fg == z_color).count())
+                .max()
+                .unwrap();
+            assert_eq!(widest_row, columns * cell_width as usize);
+        }
+    }
+
     #[test]
     fn test_game_render_with_small_terminal() {
         // Create a very small terminal that can't fit the game
@@ -103,6 +138,67 @@ mod tests {
         terminal.draw(|f| ui::render(f, &mut app)).unwrap();
     }
 
+    #[test]
+    fn test_completed_mode_shows_results() {
+        let mut app = App::new();
+        app.menu.state = crate::menu_types::MenuState::Game;
+        app.world.resource_mut::<GameState>().game_over = true;
+        {
+            let mut mode = app.world.resource_mut::<GameMode>();
+            *mode = GameMode::new(GameModeKind::Sprint);
+            mode.elapsed = 83.5;
+            mode.result = Some(ModeResult::Completed);
+        }
+
+        let mut terminal = create_test_terminal(100, 40);
+        terminal.draw(|f| ui::render(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(screen.contains("SPRINT COMPLETE"));
+        assert!(screen.contains("Time: 1:23.50"));
+    }
+
+    #[test]
+    fn test_pause_hides_board_and_shows_menu() {
+        let mut app = App::new();
+        app.menu.state = crate::menu_types::MenuState::Game;
+        {
+            let mut board = app.world.resource_mut::<Board>();
+            let bottom = board.height - 1;
+            board.cells[0][bottom] = Some(TetrominoType::Z);
+        }
+        {
+            let mut game_state = app.world.resource_mut::<GameState>();
+            game_state.next_queue.push_back(TetrominoType::I);
+            game_state.pause();
+        }
+
+        let mut terminal = create_test_terminal(100, 40);
+        terminal.draw(|f| ui::render(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(screen.contains("PAUSED"));
+        assert!(screen.contains("Resume"));
+        assert!(screen.contains("Quit to Menu"));
+
+        // Neither the stack nor the next queue give anything away
+        let classic = PieceSet::classic();
+        let pieces_drawn = buffer.content().iter().any(|cell| {
+            cell.fg == classic.color(TetrominoType::Z) || cell.fg == classic.color(TetrominoType::I)
+        });
+        assert!(!pieces_drawn);
+    }
+
     #[test]
     fn test_next_tetromino_rendering() {
         // Create a test app
@@ -111,7 +207,7 @@ mod tests {
         // Set a known next tetromino
         {
             let mut game_state = app.world.resource_mut::<GameState>();
-            game_state.next_tetromino = Some(TetrominoType::I);
+            game_state.next_queue.push_back(TetrominoType::I);
         }
 
         // Create a test terminal
@@ -148,4 +244,38 @@ mod tests {
 
         assert!(has_content, "Next tetromino preview should render content");
     }
+
+    #[test]
+    fn test_hold_tetromino_grayed_out_when_used() {
+        let mut app = App::new();
+        {
+            let mut game_state = app.world.resource_mut::<GameState>();
+            game_state.held_tetromino = Some(TetrominoType::T);
+            game_state.hold_used = true;
+        }
+
+        let mut terminal = create_test_terminal(40, 20);
+        let preview_area = Rect::new(5, 5, 10, 10);
+
+        terminal
+            .draw(|f| {
+                render_hold_tetromino(f, &mut app, preview_area);
+            })
+            .unwrap();
+
+        // The held piece is drawn, but only in gray while a swap isn't allowed
+        let buffer = terminal.backend().buffer();
+        let mut has_gray_block = false;
+        for x in preview_area.left()..preview_area.right() {
+            for y in preview_area.top()..preview_area.bottom() {
+                let cell = buffer.cell((x, y)).unwrap();
+                assert_ne!(cell.fg, PieceSet::classic().color(TetrominoType::T));
+                if cell.symbol() == "█" && cell.fg == Color::DarkGray {
+                    has_gray_block = true;
+                }
+            }
+        }
+
+        assert!(has_gray_block, "Held tetromino should render grayed out");
+    }
 }
@@ -14,14 +14,21 @@
     clippy::no_effect_underscore_binding
 )]
 
+use crate::Time;
 use crate::app::App;
-use crate::components::{GameState, Particle, ScreenShake};
-use crate::game::{BOARD_HEIGHT, BOARD_WIDTH};
+use crate::components::{
+    Board, GameState, LockDelay, Particle, Phase, Position, ScreenShake, Tetromino,
+};
+use crate::finesse::Finesse;
+use crate::game::{BOARD_HEIGHT, BOARD_WIDTH, FIXED_TIMESTEP, HIGH_SCORE_NAME_LENGTH};
 use crate::menu::MenuRenderer;
-use crate::menu_types::{MenuState, OptionsOption};
+use crate::menu_types::{MenuState, PauseOption};
+use crate::mode::{GameMode, GameModeKind, ModeResult, format_time};
+use crate::pieces::PieceSet;
+use crate::stats::{GameStats, StatKind};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
 pub fn render(f: &mut Frame, app: &mut App) {
@@ -46,8 +53,9 @@ fn render_game(f: &mut Frame, app: &mut App) {
     let available_area = f.area();
 
     // Calculate ideal game board dimensions based on available space
+    let (columns, rows) = board_size(app);
     let (board_width, board_height, cell_width, cell_height) =
-        calculate_responsive_board_size(available_area);
+        calculate_responsive_board_size(available_area, columns, rows);
 
     // Minimum info panel width
     let min_info_width = 20u16;
@@ -130,15 +138,14 @@ fn render_game(f: &mut Frame, app: &mut App) {
     let (final_board_width, final_board_height, final_cell_width, final_cell_height) =
         if board_height > available_board_height {
             // Recalculate with height constraint
-            let height_constrained_width = (f32::from(available_board_height)
-                * (BOARD_WIDTH as f32 / BOARD_HEIGHT as f32))
-                as u16;
-            let new_cell_width = (height_constrained_width / BOARD_WIDTH as u16).max(2);
+            let height_constrained_width =
+                (f32::from(available_board_height) * (columns as f32 / rows as f32)) as u16;
+            let new_cell_width = (height_constrained_width / columns as u16).max(2);
             let new_cell_height = (new_cell_width / 2).max(1);
 
             (
-                BOARD_WIDTH as u16 * new_cell_width + 2,
-                BOARD_HEIGHT as u16 * new_cell_height + 2,
+                columns as u16 * new_cell_width + 2,
+                rows as u16 * new_cell_height + 2,
                 new_cell_width,
                 new_cell_height,
             )
@@ -160,20 +167,41 @@ fn render_game(f: &mut Frame, app: &mut App) {
     let board_area =
         centered_horizontal_rect(final_board_width, final_board_height, game_layout[1]);
 
+    // The next queue grows with the number of previews: the first piece gets 6 rows, the rest 3 each
+    let preview_height = (8 + 3 * (app.config.visible_previews() as u16 - 1)).max(10);
+
+    let hud_height = app.config.hud_stats.len() as u16;
+
     // Define the info panel layout
     let info_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(2),  // Title
-            Constraint::Length(13), // Stats
-            Constraint::Length(10), // Next piece preview
-            Constraint::Min(5),     // Controls
-            Constraint::Length(1),  // Bottom border
+            Constraint::Length(2),              // Title
+            Constraint::Length(13),             // Stats
+            Constraint::Length(hud_height),     // Live stats picked in the options
+            Constraint::Length(preview_height), // Hold slot and next queue
+            Constraint::Min(5),                 // Controls
+            Constraint::Length(1),              // Bottom border
         ])
         .split(main_layout[1]);
 
-    // Render game title
-    let title = Paragraph::new("TETRIS")
+    // Render game title, or the playback position while watching a replay
+    let title_text = app.replay_player.as_ref().map_or_else(
+        || "TETRIS".to_string(),
+        |player| {
+            format!(
+                "REPLAY  {} / {}  {}",
+                format_time(player.frame() as f32 * FIXED_TIMESTEP),
+                format_time(player.replay.duration()),
+                if player.paused {
+                    "PAUSED".to_string()
+                } else {
+                    format!("{}x", player.speed())
+                }
+            )
+        },
+    );
+    let title = Paragraph::new(title_text)
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     f.render_widget(title, game_layout[0]);
@@ -181,10 +209,14 @@ fn render_game(f: &mut Frame, app: &mut App) {
     // Render game board with the calculated dimensions
     render_game_board(f, app, board_area, final_cell_width, final_cell_height);
 
-    // Render score and info
-    let info_title = Paragraph::new("INFO")
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::BOTTOM));
+    // Render score and info, titled with the mode being played
+    let mode = app.world.get_resource::<GameMode>().cloned();
+    let info_title = Paragraph::new(mode.as_ref().map_or_else(
+        || "INFO".to_string(),
+        |mode| mode.kind.name().to_uppercase(),
+    ))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::BOTTOM));
     f.render_widget(info_title, info_layout[0]);
 
     // Create stats layout first
@@ -200,9 +232,26 @@ fn render_game(f: &mut Frame, app: &mut App) {
     // Basic stats
     let game_state = app.world.resource::<GameState>();
 
+    let lines_text = match mode.as_ref().and_then(GameMode::line_goal) {
+        Some(goal) => format!("{}/{goal}", game_state.lines_cleared),
+        None => game_state.lines_cleared.to_string(),
+    };
+
+    // Timed modes show their clock under the lines
+    let clock_text = match &mode {
+        Some(mode) => match mode.time_remaining() {
+            Some(remaining) => format!("\nTime left: {}", format_time(remaining)),
+            None if mode.kind != GameModeKind::Zen => {
+                format!("\nTime: {}", format_time(mode.elapsed))
+            }
+            None => String::new(),
+        },
+        None => String::new(),
+    };
+
     let basic_stats = format!(
-        "Score: {}\nLevel: {}\nLines: {}",
-        game_state.score, game_state.level, game_state.lines_cleared,
+        "Score: {}\nLevel: {}\nLines: {lines_text}{clock_text}",
+        game_state.score, game_state.level,
     );
 
     let basic_info = Paragraph::new(basic_stats)
@@ -210,9 +259,13 @@ fn render_game(f: &mut Frame, app: &mut App) {
         .wrap(Wrap { trim: true });
     f.render_widget(basic_info, stats_layout[0]);
 
-    // Achievement stats
+    // Achievement stats, with the finesse faults so far
+    let finesse_faults = app
+        .world
+        .get_resource::<Finesse>()
+        .map_or(0, |finesse| finesse.faults);
     let achievement_stats = format!(
-        "Tetris: {}\nT-Spins: {}\nPerfect Clears: {}",
+        "Tetris: {}\nT-Spins: {}\nPerfect Clears: {}\nFinesse: {finesse_faults}",
         game_state.tetris_count, game_state.t_spin_count, game_state.perfect_clear_count,
     );
 
@@ -245,8 +298,20 @@ fn render_game(f: &mut Frame, app: &mut App) {
 
     let back_to_back_text = if back_to_back { "Back-to-Back" } else { "" };
 
+    let completed = mode
+        .as_ref()
+        .is_some_and(|mode| mode.result == Some(ModeResult::Completed));
     let status_text = if game_state.game_over {
-        "GAME OVER!\nPress Enter to restart".to_string()
+        let headline = if completed { "FINISHED!" } else { "GAME OVER!" };
+        let keys = if app.replay_player.is_some() {
+            "Esc: replays"
+        } else {
+            "Enter: restart\nEsc: menu"
+        };
+        match game_state.top_out {
+            Some(reason) => format!("{headline}\n{}\n{keys}", reason.description()),
+            None => format!("{headline}\n{keys}"),
+        }
     } else {
         let mut text = String::new();
         if !combo_text.is_empty() {
@@ -262,7 +327,9 @@ fn render_game(f: &mut Frame, app: &mut App) {
     };
 
     let current_status = Paragraph::new(status_text)
-        .style(Style::default().fg(if game_state.game_over {
+        .style(Style::default().fg(if completed {
+            Color::LightGreen
+        } else if game_state.game_over {
             Color::Red
         } else {
             combo_color
@@ -272,38 +339,95 @@ fn render_game(f: &mut Frame, app: &mut App) {
 
     f.render_widget(current_status, stats_layout[2]);
 
-    // Render next tetromino preview
-    render_next_tetromino(f, app, info_layout[2]);
+    // Live stats picked in the options, timed on the mode's clock
+    if let Some(stats) = app.world.get_resource::<GameStats>() {
+        let elapsed = mode.as_ref().map_or(0.0, |mode| mode.elapsed);
+        let hud_text = app
+            .config
+            .hud_stats
+            .iter()
+            .map(|&kind| format!("{}: {}", kind.name(), stats.value(kind, elapsed)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let hud = Paragraph::new(hud_text)
+            .style(Style::default().fg(Color::Gray))
+            .block(Block::default().borders(Borders::NONE));
+        f.render_widget(hud, info_layout[2]);
+    }
+
+    // Render hold slot and next queue side by side, the hold slot keeps its old size
+    let preview_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(info_layout[3]);
+    let hold_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(10), Constraint::Min(0)])
+        .split(preview_layout[0])[0];
+    render_hold_tetromino(f, app, hold_area);
+    render_next_tetromino(f, app, preview_layout[1]);
 
     // Render controls with updated key bindings
-    let controls = Paragraph::new(
+    let controls_text = if app.replay_player.is_some() {
+        "Replay:\n\
+        Space/P: Play/pause\n\
+        ↑/↓: Faster/slower\n\
+        ←/→: Seek 5 seconds\n\
+        .: Step one frame\n\
+        Esc: Back to replays\n\
+        Q: Quit\n\
+        "
+    } else {
         "Controls:\n\
         ←/→: Move left/right\n\
         ↓: Soft drop\n\
         E: Hard drop\n\
-        ↑/Space: Rotate\n\
+        ↑/Space/X: Rotate clockwise\n\
+        Z: Rotate counter-clockwise\n\
+        A: Rotate 180°\n\
+        C: Hold\n\
+        P/Esc: Pause\n\
         Q: Quit\n\
-        ",
-    )
-    .block(Block::default().borders(Borders::TOP))
-    .wrap(Wrap { trim: true });
-    f.render_widget(controls, info_layout[3]);
+        "
+    };
+    let controls = Paragraph::new(controls_text)
+        .block(Block::default().borders(Borders::TOP))
+        .wrap(Wrap { trim: true });
+    f.render_widget(controls, info_layout[4]);
+}
+
+// Columns and visible rows of the board being played
+fn board_size(app: &App) -> (usize, usize) {
+    app.world
+        .get_resource::<Board>()
+        .map_or((BOARD_WIDTH, BOARD_HEIGHT), |board| {
+            (board.width, board.height)
+        })
+}
+
+// The pieces of the game on screen, classic when there is no board yet
+fn piece_set(app: &App) -> PieceSet {
+    app.world
+        .get_resource::<Board>()
+        .map(|board| board.piece_set.clone())
+        .unwrap_or_default()
 }
 
-/// Calculate the responsive board size based on available area
+/// Calculate the responsive board size based on available area and the board's columns and rows
 #[must_use]
-pub fn calculate_responsive_board_size(area: Rect) -> (u16, u16, u16, u16) {
+pub fn calculate_responsive_board_size(
+    area: Rect,
+    columns: usize,
+    rows: usize,
+) -> (u16, u16, u16, u16) {
     // Calculate the available space
     let available_width = area.width.saturating_sub(4); // Subtract margin
     let available_height = area.height.saturating_sub(4); // Subtract margin
 
-    // Calculate the aspect ratio of the original game board
-    let _board_aspect_ratio = BOARD_WIDTH as f32 / BOARD_HEIGHT as f32;
-
     // Base cell size calculations
     // Determine maximum possible cell dimensions while maintaining proper aspect ratio
-    let max_cell_width_by_width = available_width / BOARD_WIDTH as u16;
-    let max_cell_height_by_height = available_height / BOARD_HEIGHT as u16;
+    let max_cell_width_by_width = available_width / columns as u16;
+    let max_cell_height_by_height = available_height / rows as u16;
 
     // Enforce 2:1 width-to-height ratio for visual "square" appearance in terminal
     // Terminal characters are typically about twice as tall as they are wide
@@ -318,8 +442,8 @@ pub fn calculate_responsive_board_size(area: Rect) -> (u16, u16, u16, u16) {
     let cell_height = (cell_width / 2).max(1);
 
     // Calculate final board dimensions including borders
-    let board_width = BOARD_WIDTH as u16 * cell_width + 2; // +2 for borders
-    let board_height = BOARD_HEIGHT as u16 * cell_height + 2; // +2 for borders
+    let board_width = columns as u16 * cell_width + 2; // +2 for borders
+    let board_height = rows as u16 * cell_height + 2; // +2 for borders
 
     (board_width, board_height, cell_width, cell_height)
 }
@@ -347,28 +471,51 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
     let game_state = app.world.resource::<GameState>();
     let show_grid = game_state.show_grid;
 
+    // The board is hidden while paused so the pause can't be used to plan ahead
+    if game_state.paused {
+        render_pause_menu(f, app, inner_area);
+        return;
+    }
+    let countdown = game_state.resume_countdown;
+
     // Get blocks to render using the app's helper method
     let blocks = app.get_render_blocks();
+    let (columns, rows) = board_size(app);
+    let piece_set = piece_set(app);
+
+    // Rows being cleared light up on every other beat of their flash
+    let flashing_rows = app
+        .world
+        .get_resource::<Phase>()
+        .map(|phase| phase.flashing_rows().to_vec())
+        .unwrap_or_default();
 
     // Render each block
     for (position, tetromino_type) in blocks {
-        let x = position.x.clamp(0, (BOARD_WIDTH - 1) as i32) as u16;
-        let y = position.y.clamp(0, (BOARD_HEIGHT - 1) as i32) as u16;
+        // Blocks still in the hidden buffer above the field aren't drawn
+        if position.x < 0 || position.y < 0 {
+            continue;
+        }
+        let x = position.x as u16;
+        let y = position.y as u16;
 
         // Each cell is sized according to calculated dimensions
-        if x < BOARD_WIDTH as u16 && y < BOARD_HEIGHT as u16 {
+        if x < columns as u16 && y < rows as u16 {
             let block_x = inner_area
                 .left()
                 .saturating_add(x.saturating_mul(cell_width));
 
             // Fix: Invert Y coordinate to start from the bottom instead of the top
             let block_y = inner_area.bottom().saturating_sub(1).saturating_sub(
-                ((BOARD_HEIGHT as u16).saturating_sub(1).saturating_sub(y))
-                    .saturating_mul(cell_height),
+                ((rows as u16).saturating_sub(1).saturating_sub(y)).saturating_mul(cell_height),
             );
 
             if block_x < inner_area.right() && block_y < inner_area.bottom() {
-                let color = tetromino_type.get_color();
+                let color = if flashing_rows.contains(&usize::from(y)) {
+                    Color::White
+                } else {
+                    piece_set.color(tetromino_type)
+                };
 
                 // Draw a single block with proper proportional size
                 // For cell_width=2 and cell_height=1, this matches the original rendering
@@ -391,6 +538,9 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
         }
     }
 
+    // Show how much lock delay the active piece has left
+    render_lock_delay_indicator(f, app, inner_area, cell_width, cell_height);
+
     // Render particles
     render_particles(f, app, inner_area, cell_width, cell_height);
 
@@ -400,7 +550,7 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
         let grid_color = Color::DarkGray;
 
         // Draw vertical grid lines
-        for x in 0..=BOARD_WIDTH {
+        for x in 0..=columns {
             let grid_x = inner_area.left().saturating_add(x as u16 * cell_width);
 
             // Skip if out of bounds
@@ -414,7 +564,7 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
                     // Use dotted line style for grid ('+' for intersections, '|' for vertical lines)
                     if y % 2 == 0 {
                         let is_intersection =
-                            x % (BOARD_WIDTH + 1) == 0 && y as usize % (BOARD_HEIGHT + 1) == 0;
+                            x.is_multiple_of(columns + 1) && (y as usize).is_multiple_of(rows + 1);
                         cell.set_symbol(if is_intersection { "+" } else { "│" });
                         cell.set_fg(grid_color);
                     }
@@ -423,7 +573,7 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
         }
 
         // Draw horizontal grid lines
-        for y in 0..=BOARD_HEIGHT {
+        for y in 0..=rows {
             let grid_y = inner_area
                 .bottom()
                 .saturating_sub(1)
@@ -447,10 +597,27 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
         }
     }
 
-    // If game is over, overlay "GAME OVER" text
+    // A completed mode gets its results over the board, a top out "GAME OVER" and the reason
     let game_state = app.world.resource::<GameState>();
-    if game_state.game_over {
-        let game_over = Paragraph::new("GAME OVER")
+    let completed_mode = app
+        .world
+        .get_resource::<GameMode>()
+        .filter(|mode| mode.result == Some(ModeResult::Completed));
+    if countdown > 0.0 {
+        render_countdown(f, inner_area, countdown);
+    } else if game_state.game_over && app.show_summary {
+        render_summary(f, inner_area, app);
+    } else if let Some(mode) = completed_mode {
+        render_results(f, inner_area, mode, game_state);
+    } else if game_state.game_over {
+        let text = match game_state.top_out {
+            Some(reason) => format!("GAME OVER\n{}", reason.description().to_uppercase()),
+            None => "GAME OVER".to_string(),
+        };
+        let hint = Paragraph::new("Tab: summary")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray));
+        let game_over = Paragraph::new(text)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
 
@@ -458,10 +625,299 @@ fn render_game_board(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16,
             x: inner_area.x,
             y: inner_area.y.saturating_add(inner_area.height / 2),
             width: inner_area.width,
-            height: 1,
+            height: 2.min(inner_area.height),
         };
 
         f.render_widget(game_over, game_over_area);
+        if game_over_area.bottom() < inner_area.bottom() {
+            let hint_area = Rect {
+                y: game_over_area.bottom(),
+                height: 1,
+                ..game_over_area
+            };
+            f.render_widget(hint, hint_area);
+        }
+    }
+
+    if game_state.game_over {
+        render_high_score_entry(f, inner_area, app);
+    }
+}
+
+// Full breakdown of a finished game's stats, shown in place of the board
+fn render_summary(f: &mut Frame, area: Rect, app: &App) {
+    let game_state = app.world.resource::<GameState>();
+    let elapsed = app
+        .world
+        .get_resource::<GameMode>()
+        .map_or(0.0, |mode| mode.elapsed);
+    let stats = app
+        .world
+        .get_resource::<GameStats>()
+        .cloned()
+        .unwrap_or_default();
+
+    let finesse = app
+        .world
+        .get_resource::<Finesse>()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut lines = vec![
+        format!("Score: {}", game_state.score),
+        format!(
+            "Lines: {}  Level: {}",
+            game_state.lines_cleared, game_state.level
+        ),
+        format!("Time: {}", format_time(elapsed)),
+        String::new(),
+        format!("Pieces: {}", stats.pieces),
+        format!("PPS: {}", stats.value(StatKind::Pps, elapsed)),
+        format!(
+            "APM: {} ({} sent)",
+            stats.value(StatKind::Apm, elapsed),
+            stats.attack
+        ),
+        format!(
+            "KPP: {} ({} keys)",
+            stats.value(StatKind::Kpp, elapsed),
+            stats.keys
+        ),
+        format!("Holes: {}", stats.holes_created),
+        format!(
+            "Finesse faults: {} ({}/{} clean)",
+            finesse.faults, finesse.clean_pieces, finesse.pieces
+        ),
+        format!("Longest I drought: {}", stats.max_i_drought),
+        String::new(),
+    ];
+
+    // Piece counts, four to a row so they fit a narrow board
+    let piece_set = piece_set(app);
+    let counts: Vec<String> = piece_set
+        .types()
+        .map(|piece| {
+            let count = stats
+                .piece_counts
+                .get(usize::from(piece.0))
+                .copied()
+                .unwrap_or(0);
+            format!("{}:{count}", piece_set.name(piece))
+        })
+        .collect();
+    for row in counts.chunks(4) {
+        lines.push(row.join(" "));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Tetrises: {}  T-Spins: {}",
+        game_state.tetris_count, game_state.t_spin_count
+    ));
+    lines.push(format!(
+        "Perfect Clears: {}",
+        game_state.perfect_clear_count
+    ));
+    lines.push(String::new());
+    lines.push("Tab: back".to_string());
+
+    let summary_area =
+        centered_horizontal_rect(area.width, (lines.len() as u16 + 2).min(area.height), area);
+    f.render_widget(Clear, summary_area);
+    let paragraph = Paragraph::new(lines.join("\n"))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("SUMMARY")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::LightCyan)),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, summary_area);
+}
+
+// Initials prompt for a game that made the high scores, then the place it took
+fn render_high_score_entry(f: &mut Frame, area: Rect, app: &App) {
+    let (title, text) = if let Some(pending) = &app.pending_high_score {
+        let blanks = HIGH_SCORE_NAME_LENGTH.saturating_sub(pending.entry.name.len());
+        (
+            "NEW HIGH SCORE",
+            format!(
+                "Initials: {}{}\nEnter: save  Esc: skip",
+                pending.entry.name,
+                "_".repeat(blanks)
+            ),
+        )
+    } else if let Some(place) = app.high_score_place.filter(|_| !app.show_summary) {
+        ("HIGH SCORES", format!("#{} on the table", place + 1))
+    } else {
+        return;
+    };
+
+    let entry_area = Rect {
+        x: area.x,
+        y: area.bottom().saturating_sub(4).max(area.y),
+        width: area.width,
+        height: 4.min(area.height),
+    };
+    f.render_widget(Clear, entry_area);
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(Color::LightYellow)),
+    );
+    f.render_widget(paragraph, entry_area);
+}
+
+// Pause menu drawn in place of the hidden board
+fn render_pause_menu(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines = vec![Line::from("")];
+    for option in PauseOption::ALL {
+        let style = if option == app.menu.pause_selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(option.label(), style)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "P/Esc: resume",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let menu_area = centered_horizontal_rect(area.width, 9, area);
+    let paragraph = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("PAUSED")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+    f.render_widget(paragraph, menu_area);
+}
+
+// 3-2-1 shown over the board after resuming
+fn render_countdown(f: &mut Frame, area: Rect, countdown: f32) {
+    let countdown_area = centered_horizontal_rect(5, 3, area);
+    f.render_widget(Clear, countdown_area);
+    let paragraph = Paragraph::new(format!("{}", countdown.ceil() as u32))
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(paragraph, countdown_area);
+}
+
+// Results screen shown over the board once a mode's goal is reached
+fn render_results(f: &mut Frame, area: Rect, mode: &GameMode, game_state: &GameState) {
+    let headline = match mode.kind {
+        GameModeKind::Ultra => "TIME UP",
+        _ => "COMPLETE",
+    };
+
+    let results = format!(
+        "Time: {}\nScore: {}\nLines: {}\nLevel: {}\nTetrises: {}\nT-Spins: {}\n\nEnter: play again\nEsc: menu\nTab: summary",
+        format_time(mode.elapsed),
+        game_state.score,
+        game_state.lines_cleared,
+        game_state.level,
+        game_state.tetris_count,
+        game_state.t_spin_count,
+    );
+
+    let results_area = centered_horizontal_rect(area.width.saturating_sub(2), 13, area);
+    f.render_widget(Clear, results_area);
+    let paragraph = Paragraph::new(results)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} {headline}", mode.kind.name().to_uppercase()))
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::LightGreen)),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, results_area);
+}
+
+// Underlines the bottom row of a resting piece with a bar that shrinks as the lock delay runs out
+fn render_lock_delay_indicator(
+    f: &mut Frame,
+    app: &mut App,
+    area: Rect,
+    cell_width: u16,
+    cell_height: u16,
+) {
+    let remaining = match app.world.get_resource::<LockDelay>() {
+        Some(lock_delay) if lock_delay.active => lock_delay.remaining_fraction(),
+        _ => return,
+    };
+
+    let piece_set = piece_set(app);
+    let Some((blocks, color)) = app
+        .world
+        .query::<(&Tetromino, &Position)>()
+        .iter(&app.world)
+        .next()
+        .map(|(tetromino, position)| {
+            let blocks: Vec<(i32, i32)> = piece_set
+                .blocks(tetromino)
+                .into_iter()
+                .map(|(dx, dy)| (position.x + dx, position.y + dy))
+                .collect();
+            (blocks, piece_set.color(tetromino.tetromino_type))
+        })
+    else {
+        return;
+    };
+
+    let (columns, rows) = board_size(app);
+    let bottom = blocks.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    let left = blocks.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let right = blocks.iter().map(|&(x, _)| x).max().unwrap_or(0);
+    if bottom < 0 || bottom >= rows as i32 {
+        return;
+    }
+
+    // The bar spans the piece's columns and is drawn on the lowest text row of its bottom cells
+    let bar_y = area.bottom().saturating_sub(1).saturating_sub(
+        (rows as u16)
+            .saturating_sub(1)
+            .saturating_sub(bottom as u16)
+            .saturating_mul(cell_height),
+    );
+    let span = (right - left + 1) as u16 * cell_width;
+    let bar_length = (f32::from(span) * remaining).ceil() as u16;
+
+    for i in 0..bar_length {
+        let column = left + i32::from(i / cell_width);
+        if column < 0 || column >= columns as i32 {
+            continue;
+        }
+
+        let bar_x = area
+            .left()
+            .saturating_add(column as u16 * cell_width + i % cell_width);
+        if bar_x >= area.right() {
+            continue;
+        }
+
+        if let Some(cell) = f.buffer_mut().cell_mut((bar_x, bar_y)) {
+            // Keep the piece's color behind the bar where it has a block
+            if blocks.contains(&(column, bottom)) {
+                cell.set_bg(color);
+            }
+            cell.set_symbol("▁");
+            cell.set_fg(Color::White);
+        }
     }
 }
 
@@ -473,16 +929,23 @@ fn render_particles(f: &mut Frame, app: &mut App, area: Rect, cell_width: u16, c
         .query::<&Particle>()
         .iter(&app.world)
         .collect::<Vec<_>>();
+    let (columns, rows) = board_size(app);
+
+    // Particles are drawn where they'll be part way into the next frame, so they move smoothly
+    // however the render rate lines up with the simulation
+    let lead = app.world.get_resource::<Time>().map_or(0.0, Time::alpha) * FIXED_TIMESTEP;
 
     for particle in particles_data {
         // Convert position to u16, clamping to board boundaries
-        let x = particle.position.x.clamp(0, (BOARD_WIDTH - 1) as i32) as u16;
-        let y = particle.position.y.clamp(0, (BOARD_HEIGHT - 1) as i32) as u16;
+        let x = (particle.position.x as f32 + particle.velocity.0 * lead).round() as i32;
+        let y = (particle.position.y as f32 + particle.velocity.1 * lead).round() as i32;
+        let x = x.clamp(0, columns as i32 - 1) as u16;
+        let y = y.clamp(0, rows as i32 - 1) as u16;
 
         // Calculate screen position
         let particle_x = area.left().saturating_add(x.saturating_mul(cell_width));
         let particle_y = area.bottom().saturating_sub(1).saturating_sub(
-            ((BOARD_HEIGHT as u16).saturating_sub(1).saturating_sub(y)).saturating_mul(cell_height),
+            ((rows as u16).saturating_sub(1).saturating_sub(y)).saturating_mul(cell_height),
         );
 
         // Only render if within screen bounds
@@ -541,7 +1004,7 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-// Function to render the next tetromino preview
+// Function to render the next queue, stacked top to bottom with the first piece drawn larger
 pub fn render_next_tetromino(f: &mut Frame, app: &mut App, area: Rect) {
     // Create a preview box with a title
     let next_block = Block::default().title("NEXT").borders(Borders::ALL);
@@ -551,80 +1014,119 @@ pub fn render_next_tetromino(f: &mut Frame, app: &mut App, area: Rect) {
 
     f.render_widget(next_block, area);
 
-    // Get the next tetromino type from game state
-    if let Some(game_state) = app.world.get_resource::<GameState>() {
-        if let Some(next_type) = game_state.next_tetromino {
-            // Get blocks for the tetromino type
-            let blocks = next_type.get_blocks();
-
-            // Calculate size needed for the tetromino
-            let mut min_x = i32::MAX;
-            let mut max_x = i32::MIN;
-            let mut min_y = i32::MAX;
-            let mut max_y = i32::MIN;
-
-            for &(x, y) in &blocks {
-                min_x = min_x.min(x);
-                max_x = max_x.max(x);
-                min_y = min_y.min(y);
-                max_y = max_y.max(y);
-            }
+    let preview_count = app.config.visible_previews();
+    let Some(game_state) = app.world.get_resource::<GameState>() else {
+        return;
+    };
+    if game_state.paused {
+        return;
+    }
+
+    // The first slot gets twice the height of the others
+    let constraints: Vec<Constraint> = (0..preview_count)
+        .map(|i| Constraint::Fill(if i == 0 { 2 } else { 1 }))
+        .collect();
+    let slots = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner_area);
 
-            // Calculate cell dimensions to match board aspect ratio
-            let width = (max_x - min_x + 1) as u16;
-            let height = (max_y - min_y + 1) as u16;
+    let piece_set = piece_set(app);
+    for (slot, next_type) in slots.iter().zip(game_state.next_queue.iter()) {
+        let blocks = piece_set.blocks(&Tetromino::new(*next_type));
+        render_preview_piece(f, *slot, &blocks, piece_set.color(*next_type));
+    }
+}
 
-            // Calculate available space in the preview area
-            let available_width = inner_area.width;
-            let available_height = inner_area.height;
+// Function to render the hold slot, grayed out while a swap isn't allowed
+pub fn render_hold_tetromino(f: &mut Frame, app: &mut App, area: Rect) {
+    let hold_block = Block::default().title("HOLD").borders(Borders::ALL);
+    let inner_area = hold_block.inner(area);
+    f.render_widget(hold_block, area);
 
-            // Calculate block size that fits within the preview area
-            // Accounting for terminal character aspect ratio (2:1 width-to-height)
-            let max_block_width = available_width / width;
-            let max_block_height = available_height / height;
+    let Some(game_state) = app.world.get_resource::<GameState>() else {
+        return;
+    };
+    if game_state.paused {
+        return;
+    }
+
+    if let Some(held_type) = game_state.held_tetromino {
+        let piece_set = piece_set(app);
+        let color = if game_state.hold_used {
+            Color::DarkGray
+        } else {
+            piece_set.color(held_type)
+        };
+        let blocks = piece_set.blocks(&Tetromino::new(held_type));
+        render_preview_piece(f, inner_area, &blocks, color);
+    }
+}
 
-            // Ensure proper aspect ratio (terminal characters are typically twice as tall as they are wide)
-            let block_width = max_block_width.min(max_block_height * 2);
-            // Make sure width is even for better appearance
-            let block_width = (block_width / 2) * 2;
-            let block_height = (block_width / 2).max(1);
+// Draws a single piece in its spawn state centered in a preview box, scaled to fit
+fn render_preview_piece(f: &mut Frame, inner_area: Rect, blocks: &[(i32, i32)], color: Color) {
+    // Calculate size needed for the tetromino
+    let mut min_x = i32::MAX;
+    let mut max_x = i32::MIN;
+    let mut min_y = i32::MAX;
+    let mut max_y = i32::MIN;
+
+    for &(x, y) in blocks {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
 
-            // Center the tetromino in the preview area
-            let total_width = width.saturating_mul(block_width);
-            let total_height = height.saturating_mul(block_height);
+    // Calculate cell dimensions to match board aspect ratio
+    let width = (max_x - min_x + 1) as u16;
+    let height = (max_y - min_y + 1) as u16;
+
+    // Calculate available space in the preview area
+    let available_width = inner_area.width;
+    let available_height = inner_area.height;
+
+    // Calculate block size that fits within the preview area
+    // Accounting for terminal character aspect ratio (2:1 width-to-height)
+    let max_block_width = available_width / width;
+    let max_block_height = available_height / height;
+
+    // Ensure proper aspect ratio (terminal characters are typically twice as tall as they are wide)
+    let block_width = max_block_width.min(max_block_height * 2);
+    // Make sure width is even for better appearance
+    let block_width = (block_width / 2) * 2;
+    let block_height = (block_width / 2).max(1);
+
+    // Center the tetromino in the preview area
+    let total_width = width.saturating_mul(block_width);
+    let total_height = height.saturating_mul(block_height);
+
+    let start_x = inner_area
+        .left()
+        .saturating_add((available_width.saturating_sub(total_width)) / 2);
+    let start_y = inner_area
+        .top()
+        .saturating_add((available_height.saturating_sub(total_height)) / 2);
+
+    // Draw the tetromino blocks
+    for &(x, y) in blocks {
+        let block_x = start_x.saturating_add((x - min_x) as u16 * block_width);
+        let block_y = start_y.saturating_add((y - min_y) as u16 * block_height);
+
+        if block_x < inner_area.right() && block_y < inner_area.bottom() {
+            // Draw a single block with proper proportional size
+            let block_char = if block_width >= 2 && block_height >= 1 {
+                "█"
+            } else {
+                "■"
+            };
 
-            let start_x = inner_area
-                .left()
-                .saturating_add((available_width.saturating_sub(total_width)) / 2);
-            let start_y = inner_area
-                .top()
-                .saturating_add((available_height.saturating_sub(total_height)) / 2);
-
-            let color = next_type.get_color();
-
-            // Draw the tetromino blocks
-            for &(x, y) in &blocks {
-                let block_x = start_x.saturating_add((x - min_x) as u16 * block_width);
-                let block_y = start_y.saturating_add((y - min_y) as u16 * block_height);
-
-                if block_x < inner_area.right() && block_y < inner_area.bottom() {
-                    // Draw a single block with proper proportional size
-                    let block_char = if block_width >= 2 && block_height >= 1 {
-                        "█"
-                    } else {
-                        "■"
-                    };
-
-                    for dx in 0..block_width {
-                        for dy in 0..block_height {
-                            if let Some(cell) =
-                                f.buffer_mut().cell_mut((block_x + dx, block_y + dy))
-                            {
-                                cell.set_symbol(block_char);
-                                cell.set_fg(color);
-                                cell.set_bg(Color::Black);
-                            }
-                        }
+            for dx in 0..block_width {
+                for dy in 0..block_height {
+                    if let Some(cell) = f.buffer_mut().cell_mut((block_x + dx, block_y + dy)) {
+                        cell.set_symbol(block_char);
+                        cell.set_fg(color);
+                        cell.set_bg(Color::Black);
                     }
                 }
             }